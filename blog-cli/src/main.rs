@@ -1,21 +1,64 @@
 use anyhow::{Context, Result};
-use blog_client::{BlogClient, Transport};
+use blog_client::error::BlogClientError;
+use blog_client::models::{ContentFormat, CreatePostRequest, ListPostsFilter, PostPage};
+use blog_client::token_store::{FileTokenStore, TokenStore};
+use blog_client::{BlogClient, BlogClientConfig, RetryPolicy, Transport};
 use clap::{Parser, Subcommand};
-use std::fs;
+use serde::Serialize;
+use std::fmt::Display;
+use std::future::Future;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+mod password;
+
+/// Set by `--json`: human-readable output is suppressed and results go to stdout as JSON
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--quiet`: human-readable output is suppressed, errors go to stderr
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+// println!, который молчит в режимах --json и --quiet
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !JSON_OUTPUT.load(Ordering::Relaxed) && !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    #[arg(short, long)]
+    /// Server URL [default: http://localhost:3000, or http://localhost:50051 with --grpc].
+    /// Precedence: --server, then BLOG_SERVER, then the default
+    #[arg(short, long, env = "BLOG_SERVER")]
     server: Option<String>,
 
-    #[arg(long)]
+    /// Use gRPC instead of HTTP. Precedence: --grpc, then BLOG_GRPC=1, then HTTP
+    #[arg(long, env = "BLOG_GRPC", value_parser = clap::builder::FalseyValueParser::new())]
     grpc: bool,
 
     #[arg(long)]
     token_file: Option<PathBuf>,
 
+    /// PEM CA certificate to trust for an https:// gRPC server (e.g. self-signed)
+    #[arg(long)]
+    ca_cert: Option<PathBuf>,
+
+    /// Print results as JSON (errors as {"error": "..."}) instead of human-readable text
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Log client internals to stderr: -v client debug, -vv everything debug, -vvv trace
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Print nothing but errors (to stderr); the exit code tells the outcome
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -29,26 +72,61 @@ enum Commands {
         #[arg(short, long)]
         email: String,
 
-        #[arg(short, long)]
-        password: String,
+        /// Password (visible in shell history; omit it to be prompted without echo)
+        #[arg(short, long, conflicts_with = "password_stdin")]
+        password: Option<String>,
+
+        /// Read the password from the first line of stdin
+        #[arg(long)]
+        password_stdin: bool,
     },
 
     Login {
-        #[arg(short, long)]
-        username: String,
+        #[arg(short, long, required_unless_present = "email")]
+        username: Option<String>,
 
-        #[arg(short, long)]
-        password: String,
+        /// Log in by email instead of username
+        #[arg(long, conflicts_with = "username")]
+        email: Option<String>,
+
+        /// Password (visible in shell history; omit it to be prompted without echo)
+        #[arg(short, long, conflicts_with = "password_stdin")]
+        password: Option<String>,
+
+        /// Read the password from the first line of stdin
+        #[arg(long)]
+        password_stdin: bool,
     },
 
     Status,
 
+    /// Show the account the saved token belongs to (exits nonzero when not logged in)
+    Whoami,
+
+    /// Log out and remove the saved tokens
+    Logout,
+
+    /// Get a new access token using the refresh token saved on login
+    Refresh,
+
     Create {
         #[arg(short, long)]
         title: String,
 
         #[arg(short, long)]
         content: String,
+
+        /// Tag to attach to the post (can be repeated)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Save as a draft visible only to you
+        #[arg(long)]
+        draft: bool,
+
+        /// Content is Markdown (rendered as HTML by the web app)
+        #[arg(long)]
+        markdown: bool,
     },
 
     Get {
@@ -79,71 +157,161 @@ enum Commands {
         #[arg(short, long, default_value_t = 0)]
         offset: i64,
     },
-}
 
-struct TokenManager {
-    token_path: PathBuf,
-}
+    /// List only your published posts
+    Mine {
+        #[arg(short, long, default_value_t = 10)]
+        limit: i64,
 
-impl TokenManager {
-    fn new(custom_path: Option<PathBuf>) -> Result<Self> {
-        let token_path = match custom_path {
-            Some(path) => path,
-            None => {
-                let home = dirs::home_dir().context("Failed to get home directory")?;
-                home.join(".blog_token")
-            }
-        };
+        #[arg(short, long, default_value_t = 0)]
+        offset: i64,
+    },
 
-        Ok(Self { token_path })
-    }
+    /// List your own posts including drafts
+    Dashboard {
+        #[arg(short, long, default_value_t = 10)]
+        limit: i64,
 
-    fn save_token(&self, token: &str) -> Result<()> {
-        fs::write(&self.token_path, token)
-            .with_context(|| format!("Failed to save token to {:?}", self.token_path))?;
+        #[arg(short, long, default_value_t = 0)]
+        offset: i64,
+    },
+
+    /// Save all your published posts to a JSON file
+    Export {
+        #[arg(short, long)]
+        out: PathBuf,
+    },
 
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(&self.token_path)?.permissions();
-            perms.set_mode(0o600);
-            fs::set_permissions(&self.token_path, perms)?;
+    /// Recreate posts from a JSON file written by `export`; malformed entries
+    /// are skipped, and the exit code is nonzero if any post failed to be created
+    Import {
+        #[arg(short, long = "in")]
+        input: PathBuf,
+    },
+
+    Doctor {
+        #[arg(long, default_value_t = 50051)]
+        grpc_port: u16,
+    },
+}
+
+/// Posts per request when exporting
+const EXPORT_PAGE_SIZE: i64 = 100;
+/// A token expiring sooner than this is refreshed before the call
+const TOKEN_REFRESH_MARGIN_SECONDS: i64 = 30;
+
+/// Token file: `--token-file` or ~/.blog_token
+fn token_file_path(custom_path: Option<PathBuf>) -> Result<PathBuf> {
+    match custom_path {
+        Some(path) => Ok(path),
+        None => {
+            let home = dirs::home_dir().context("Failed to get home directory")?;
+            Ok(home.join(".blog_token"))
         }
+    }
+}
 
-        println!("✓ Token saved to {:?}", self.token_path);
-        Ok(())
+/// Run an authenticated call; an access token that is about to expire is
+/// refreshed beforehand with the saved refresh token (the client persists it),
+/// and one rejected as expired anyway is refreshed once and the call retried
+async fn with_token_refresh<T, F, Fut>(client: &BlogClient, call: F) -> Result<T, BlogClientError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, BlogClientError>>,
+{
+    let refresh_after =
+        chrono::Utc::now() + chrono::Duration::seconds(TOKEN_REFRESH_MARGIN_SECONDS);
+    let expiring = client
+        .token_expires_at()
+        .await
+        .is_some_and(|at| at <= refresh_after);
+    if expiring && client.get_refresh_token().await.is_some() {
+        say!("🔄 Access token is about to expire, refreshing...");
+        // Не удалось - делаем запрос со старым токеном, дальше как обычно
+        if let Err(e) = client.refresh().await {
+            say!("⚠️  Token refresh failed: {}", e);
+        }
     }
 
-    fn load_token(&self) -> Result<Option<String>> {
-        match fs::read_to_string(&self.token_path) {
-            Ok(token) => {
-                let token = token.trim().to_string();
-                if !token.is_empty() {
-                    println!("✓ Token loaded from {:?}", self.token_path);
-                    Ok(Some(token))
-                } else {
-                    Ok(None)
-                }
+    match call().await {
+        Err(e) if e.is_expired() => {
+            if client.get_refresh_token().await.is_none() {
+                return Err(e);
             }
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
-            Err(e) => Err(e).context("Failed to read token file"),
+
+            say!("🔄 Access token expired, refreshing...");
+            client.refresh().await?;
+
+            call().await
         }
+        result => result,
     }
+}
 
-    #[allow(dead_code)]
-    fn clear_token(&self) -> Result<()> {
-        if self.token_path.exists() {
-            fs::remove_file(&self.token_path)
-                .with_context(|| format!("Failed to remove token file {:?}", self.token_path))?;
-            println!("✓ Token file removed");
+/// Print the command result as JSON when `--json` is set
+fn emit<T: Serialize>(value: &T) {
+    if JSON_OUTPUT.load(Ordering::Relaxed) {
+        match serde_json::to_string_pretty(value) {
+            Ok(json) => println!("{}", json),
+            Err(e) => fail(format!("Failed to serialize output: {}", e)),
         }
-        Ok(())
     }
 }
 
+/// Exit with a nonzero code; in JSON mode the error is printed as {"error": "..."}
+fn fail(error: impl Display) -> ! {
+    if JSON_OUTPUT.load(Ordering::Relaxed) {
+        println!("{}", serde_json::json!({ "error": error.to_string() }));
+    } else if QUIET.load(Ordering::Relaxed) {
+        eprintln!("Error: {}", error);
+    }
+    std::process::exit(1);
+}
+
+/// Install a stderr tracing subscriber for `-v`; without it only RUST_LOG
+/// enables logging, so the default output stays the same
+fn init_logging(verbose: u8, quiet: bool) {
+    use tracing_subscriber::EnvFilter;
+
+    let filter = match verbose {
+        _ if quiet => return,
+        0 => match EnvFilter::try_from_default_env() {
+            Ok(filter) => filter,
+            Err(_) => return,
+        },
+        1 => EnvFilter::new("warn,blog_client=debug"),
+        2 => EnvFilter::new("debug"),
+        _ => EnvFilter::new("trace"),
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    JSON_OUTPUT.store(cli.json, Ordering::Relaxed);
+    QUIET.store(cli.quiet, Ordering::Relaxed);
+    init_logging(cli.verbose, cli.quiet);
+
+    match run(cli).await {
+        Err(e) if JSON_OUTPUT.load(Ordering::Relaxed) => fail(format!("{:#}", e)),
+        result => result,
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    if let Commands::Doctor { grpc_port } = &cli.command {
+        let http_base = cli
+            .server
+            .clone()
+            .unwrap_or_else(|| "http://localhost:3000".to_string());
+        let token_store = FileTokenStore::new(token_file_path(cli.token_file.clone())?);
+        return run_doctor(&http_base, *grpc_port, &token_store).await;
+    }
 
     let transport = if cli.grpc {
         let addr = cli
@@ -157,16 +325,30 @@ async fn main() -> Result<()> {
         Transport::Http(addr)
     };
 
-    println!("🔌 Connecting to: {}", transport_url(&transport));
+    say!("🔌 Connecting to: {}", transport_url(&transport));
 
-    let client = BlogClient::new(transport)
+    let tls_ca_certificate = match &cli.ca_cert {
+        Some(path) => Some(
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read CA certificate {}", path.display()))?,
+        ),
+        None => None,
+    };
+    // Токены из файла подхватывает и сохраняет сам клиент
+    let token_store = FileTokenStore::new(token_file_path(cli.token_file)?);
+    let config = BlogClientConfig {
+        tls_ca_certificate,
+        ..BlogClientConfig::default()
+    }
+    .with_token_store(token_store.clone());
+
+    let client = BlogClient::with_config(transport, config)
         .await
         .context("Failed to create blog client")?;
 
-    let token_manager = TokenManager::new(cli.token_file)?;
-    if let Some(token) = token_manager.load_token()? {
-        client.set_token(token).await;
-        println!("🔑 Authenticated with saved token");
+    if client.get_token().await.is_some() {
+        say!("✓ Token loaded from {:?}", token_store.path());
+        say!("🔑 Authenticated with saved token");
     }
 
     match &cli.command {
@@ -174,206 +356,688 @@ async fn main() -> Result<()> {
             username,
             email,
             password,
+            password_stdin,
         } => {
-            println!("📝 Registering user: {}", username);
+            say!("📝 Registering user: {}", username);
+            let password = password::resolve(password.as_deref(), *password_stdin, true)?;
 
             match client.register(username, email, password).await {
                 Ok(response) => {
-                    println!("✅ Registration successful!");
-                    println!("   User ID: {}", response.user.id);
-                    println!("   Username: {}", response.user.username);
-                    println!("   Email: {}", response.user.email);
+                    say!("✅ Registration successful!");
+                    say!("   User ID: {}", response.user.id);
+                    say!("   Username: {}", response.user.username);
+                    say!("   Email: {}", response.user.email);
 
-                    token_manager.save_token(&response.token)?;
+                    say!("✓ Token saved to {:?}", token_store.path());
+                    emit(&response);
                 }
                 Err(e) => {
-                    println!("❌ Registration failed: {}", e);
-                    std::process::exit(1);
+                    say!("❌ Registration failed: {}", e);
+                    fail(&e);
                 }
             }
         }
 
-        Commands::Login { username, password } => {
-            println!("🔑 Logging in as: {}", username);
+        Commands::Login {
+            username,
+            email,
+            password,
+            password_stdin,
+        } => {
+            let login = username.as_deref().or(email.as_deref()).unwrap_or_default();
+            say!("🔑 Logging in as: {}", login);
+            let password = password::resolve(password.as_deref(), *password_stdin, false)?;
 
-            match client.login(username, password).await {
+            let result = match email {
+                Some(email) => client.login_with_email(email, password).await,
+                None => client.login(login, password).await,
+            };
+            match result {
                 Ok(response) => {
-                    println!("✅ Login successful!");
-                    println!("   User ID: {}", response.user.id);
-                    println!("   Username: {}", response.user.username);
-                    println!("   Email: {}", response.user.email);
+                    say!("✅ Login successful!");
+                    say!("   User ID: {}", response.user.id);
+                    say!("   Username: {}", response.user.username);
+                    say!("   Email: {}", response.user.email);
+                    if response.is_admin {
+                        say!("   Role: admin");
+                    }
 
-                    token_manager.save_token(&response.token)?;
+                    say!("✓ Token saved to {:?}", token_store.path());
+                    emit(&response);
                 }
                 Err(e) => {
-                    println!("❌ Login failed: {}", e);
-                    std::process::exit(1);
+                    say!("❌ Login failed: {}", e);
+                    fail(&e);
                 }
             }
         }
 
-        Commands::Status => match token_manager.load_token()? {
+        Commands::Status => match client.get_token().await {
             Some(token) => {
-                println!("🔑 Token file: {:?}", token_manager.token_path);
-                println!("   Token: {}...", &token[..20]);
-                println!("   Length: {} characters", token.len());
-                println!("   Status: ✅ Active");
-                println!("\n   To verify token, try: cargo run -- list");
+                let has_refresh_token = client.get_refresh_token().await.is_some();
+                // Подпись не проверяется: это только подсказка, решает сервер
+                let claims = client.decode_token_unverified().await;
+                let expired = claims.as_ref().is_some_and(|c| c.is_expired());
+                let expires_at = claims.as_ref().and_then(|c| c.expires_at());
+
+                // Файл могли отредактировать вручную: токен режется по символам, а не байтам
+                let length = token.chars().count();
+                let preview: String = token.chars().take(20).collect();
+                let looks_like_jwt = token.matches('.').count() == 2;
+
+                say!("🔑 Token file: {:?}", token_store.path());
+                say!(
+                    "   Token: {}{}",
+                    preview,
+                    if length > 20 { "..." } else { "" }
+                );
+                say!("   Length: {} characters", length);
+                if !looks_like_jwt {
+                    say!("   ⚠️  Token does not look like a JWT (header.payload.signature), try logging in again");
+                }
+                if let Some(claims) = &claims {
+                    say!("   User: {} (ID: {})", claims.username, claims.user_id);
+                }
+                match expires_at {
+                    Some(at) => say!(
+                        "   Expires: {}",
+                        at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S")
+                    ),
+                    None => say!("   Expires: unknown"),
+                }
+                say!(
+                    "   Refresh token: {}",
+                    if has_refresh_token {
+                        "saved (expired tokens are refreshed automatically)"
+                    } else {
+                        "none"
+                    }
+                );
+                if expired {
+                    say!("   Status: ⌛ Expired");
+                } else {
+                    say!("   Status: ✅ Active");
+                }
+                say!("\n   To verify token, try: cargo run -- list");
+                emit(&serde_json::json!({
+                    "logged_in": true,
+                    "token_file": token_store.path(),
+                    "has_refresh_token": has_refresh_token,
+                    "looks_like_jwt": looks_like_jwt,
+                    "user_id": claims.as_ref().map(|c| c.user_id),
+                    "username": claims.as_ref().map(|c| c.username.as_str()),
+                    "expires_at": expires_at.map(|at| at.to_rfc3339()),
+                    "expired": expired,
+                }));
             }
             None => {
-                println!("❌ No token found");
-                println!("   Please login first: cargo run -- login --username <username> --password <password>");
+                say!("❌ No token found");
+                say!("   Please login first: cargo run -- login --username <username> --password <password>");
+                emit(&serde_json::json!({
+                    "logged_in": false,
+                    "token_file": token_store.path(),
+                }));
             }
         },
 
-        Commands::Create { title, content } => {
-            println!("📝 Creating new post...");
+        Commands::Whoami => {
+            if client.get_token().await.is_none() {
+                say!("❌ Not logged in");
+                fail("Not logged in");
+            }
 
-            match client.create_post(title, content).await {
+            match with_token_refresh(&client, || client.current_user()).await {
+                Ok(user) => {
+                    say!("👤 {}", user.username);
+                    say!("   User ID: {}", user.id);
+                    say!("   Email: {}", user.email);
+                    if let Some(at) = &user.last_login_at {
+                        say!("   Last login: {}", at);
+                    }
+                    emit(&user);
+                }
+                Err(e) => {
+                    if e.is_expired() {
+                        say!("❌ Not logged in: the token has expired");
+                        print_expired_hint();
+                    } else if e.is_unauthorized() {
+                        say!("❌ Not logged in: the saved token was rejected");
+                    } else {
+                        say!("❌ Failed to load the current user: {}", e);
+                    }
+                    fail(&e);
+                }
+            }
+        }
+
+        Commands::Logout => {
+            let had_session = client.get_token().await.is_some();
+            if let Err(e) = client.logout().await {
+                say!("⚠️  Server logout failed: {}", e);
+            }
+
+            // Сохраненные токены клиент удаляет сам, даже если сервер недоступен
+            let was_logged_in = had_session;
+            if was_logged_in {
+                say!("👋 Logged out. See you next time!");
+            } else {
+                say!("ℹ️  You were not logged in, nothing to do");
+            }
+            emit(&serde_json::json!({ "logged_out": true, "was_logged_in": was_logged_in }));
+        }
+
+        Commands::Refresh => {
+            if client.get_refresh_token().await.is_none() {
+                say!("❌ No refresh token found");
+                say!("   Please login first: cargo run -- login --username <username> --password <password>");
+                fail("No refresh token found");
+            }
+
+            say!("🔄 Refreshing access token...");
+
+            match client.refresh().await {
+                Ok(access_token) => {
+                    say!("✅ Access token refreshed!");
+                    say!("✓ Token saved to {:?}", token_store.path());
+                    emit(&serde_json::json!({ "token": access_token }));
+                }
+                Err(e) => {
+                    say!("❌ Refresh failed: {}", e);
+                    say!("   Please login again: cargo run -- login --username <username> --password <password>");
+                    fail(&e);
+                }
+            }
+        }
+
+        Commands::Create {
+            title,
+            content,
+            tags,
+            draft,
+            markdown,
+        } => {
+            say!("📝 Creating new post...");
+
+            let request = CreatePostRequest {
+                title: title.clone(),
+                content: content.clone(),
+                tags: tags.clone(),
+                content_format: if *markdown {
+                    ContentFormat::Markdown
+                } else {
+                    ContentFormat::Plain
+                },
+                published: !draft,
+            };
+
+            match with_token_refresh(&client, || client.create_post_with(request.clone())).await {
                 Ok(post) => {
-                    println!("✅ Post created successfully!");
-                    println!("   ID: {}", post.id);
-                    println!("   Title: {}", post.title);
-                    println!("   Author ID: {}", post.author_id);
-                    println!("   Created: {}", post.created_at);
+                    say!("✅ Post created successfully!");
+                    say!("   ID: {}", post.id);
+                    say!("   Title: {}", post.title);
+                    say!("   Author ID: {}", post.author_id);
+                    if !post.tags.is_empty() {
+                        say!("   Tags: {}", post.tags.join(", "));
+                    }
+                    if !post.published {
+                        say!("   Status: draft");
+                    }
+                    if post.content_format == ContentFormat::Markdown {
+                        say!("   Format: markdown");
+                    }
+                    say!("   Created: {}", post.created_at);
+                    emit(&post);
                 }
                 Err(e) => {
-                    if e.is_unauthorized() {
-                        println!("❌ Unauthorized. Please login first:");
-                        println!(
-                            "   cargo run -- login --username <username> --password <password>"
-                        );
+                    if e.is_expired() {
+                        print_expired_hint();
+                    } else if e.is_unauthorized() {
+                        say!("❌ Unauthorized. Please login first:");
+                        say!("   cargo run -- login --username <username> --password <password>");
                     } else {
-                        println!("❌ Failed to create post: {}", e);
+                        say!("❌ Failed to create post: {}", e);
                     }
-                    std::process::exit(1);
+                    fail(&e);
                 }
             }
         }
 
         Commands::Get { id } => {
-            println!("🔍 Getting post #{}", id);
+            say!("🔍 Getting post #{}", id);
 
             match client.get_post(*id).await {
                 Ok(post) => {
-                    println!("✅ Post retrieved:");
-                    println!("   ID: {}", post.id);
-                    println!("   Title: {}", post.title);
-                    println!("   Content: {}", post.content);
-                    println!("   Author ID: {}", post.author_id);
-                    println!("   Created: {}", post.created_at);
-                    println!("   Updated: {}", post.updated_at);
+                    say!("✅ Post retrieved:");
+                    say!("   ID: {}", post.id);
+                    say!("   Title: {}", post.title);
+                    say!("   Content: {}", post.content);
+                    say!("   Author ID: {}", post.author_id);
+                    say!("   Created: {}", post.created_at);
+                    say!("   Updated: {}", post.updated_at);
+                    emit(&post);
                 }
                 Err(e) => {
                     if e.is_not_found() {
-                        println!("❌ Post #{} not found", id);
-                        println!("   Tip: Use 'list' command to see available posts");
+                        say!("❌ Post #{} not found", id);
+                        say!("   Tip: Use 'list' command to see available posts");
                     } else {
-                        println!("❌ Error: {}", e);
+                        say!("❌ Error: {}", e);
                     }
-                    std::process::exit(1);
+                    fail(&e);
                 }
             }
         }
 
         Commands::Update { id, title, content } => {
-            println!("✏️ Updating post #{}", id);
+            say!("✏️ Updating post #{}", id);
 
-            match client
-                .update_post(*id, title.clone(), content.clone())
-                .await
+            match with_token_refresh(&client, || {
+                client.update_post(*id, title.clone(), content.clone())
+            })
+            .await
             {
                 Ok(post) => {
-                    println!("✅ Post updated successfully!");
-                    println!("   ID: {}", post.id);
-                    println!("   Title: {}", post.title);
-                    println!("   Content: {}", post.content);
-                    println!("   Author ID: {}", post.author_id);
-                    println!("   Updated: {}", post.updated_at);
+                    say!("✅ Post updated successfully!");
+                    say!("   ID: {}", post.id);
+                    say!("   Title: {}", post.title);
+                    say!("   Content: {}", post.content);
+                    say!("   Author ID: {}", post.author_id);
+                    say!("   Updated: {}", post.updated_at);
+                    emit(&post);
                 }
                 Err(e) => {
                     if e.is_not_found() {
-                        println!("❌ Post #{} not found", id);
+                        say!("❌ Post #{} not found", id);
+                    } else if e.is_expired() {
+                        print_expired_hint();
                     } else if e.is_unauthorized() {
-                        println!(
-                            "❌ Unauthorized. You may not own this post or need to login again"
-                        );
+                        say!("❌ Unauthorized. You may not own this post or need to login again");
                     } else {
-                        println!("❌ Failed to update post: {}", e);
+                        say!("❌ Failed to update post: {}", e);
                     }
-                    std::process::exit(1);
+                    fail(&e);
                 }
             }
         }
 
         Commands::Delete { id } => {
-            println!("🗑️ Deleting post #{}", id);
+            say!("🗑️ Deleting post #{}", id);
 
-            match client.delete_post(*id).await {
+            match with_token_refresh(&client, || client.delete_post(*id)).await {
                 Ok(()) => {
-                    println!("✅ Post deleted successfully!");
+                    say!("✅ Post deleted successfully!");
+                    emit(&serde_json::json!({ "deleted": id }));
                 }
                 Err(e) => {
                     if e.is_not_found() {
-                        println!("❌ Post #{} not found", id);
+                        say!("❌ Post #{} not found", id);
+                    } else if e.is_expired() {
+                        print_expired_hint();
                     } else if e.is_unauthorized() {
-                        println!(
-                            "❌ Unauthorized. You may not own this post or need to login again"
-                        );
+                        say!("❌ Unauthorized. You may not own this post or need to login again");
                     } else {
-                        println!("❌ Failed to delete post: {}", e);
+                        say!("❌ Failed to delete post: {}", e);
                     }
-                    std::process::exit(1);
+                    fail(&e);
                 }
             }
         }
 
         Commands::List { limit, offset } => {
-            println!("📋 Listing posts (limit={}, offset={})", limit, offset);
+            say!("📋 Listing posts (limit={}, offset={})", limit, offset);
 
-            match client.list_posts(Some(*limit), Some(*offset)).await {
+            match client
+                .list_posts_filtered(list_filter(*limit, *offset))
+                .await
+            {
+                Ok(response) => print_posts(&response),
+                Err(e) => {
+                    say!("❌ Failed to list posts: {}", e);
+                    fail(&e);
+                }
+            }
+        }
+
+        Commands::Mine { limit, offset } => {
+            if client.get_token().await.is_none() {
+                say!("❌ Not logged in. Please login first:");
+                say!("   cargo run -- login --username <username> --password <password>");
+                fail("Not logged in");
+            }
+
+            let result = match with_token_refresh(&client, || client.current_user()).await {
+                Ok(user) => {
+                    say!(
+                        "📋 Posts by {} (limit={}, offset={})",
+                        user.username,
+                        limit,
+                        offset
+                    );
+                    client
+                        .list_posts_filtered(ListPostsFilter {
+                            author_id: Some(user.id),
+                            ..list_filter(*limit, *offset)
+                        })
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(response) => print_posts(&response),
+                Err(e) => {
+                    if e.is_expired() {
+                        print_expired_hint();
+                    } else if e.is_unauthorized() {
+                        say!("❌ Unauthorized. Please login first:");
+                        say!("   cargo run -- login --username <username> --password <password>");
+                    } else {
+                        say!("❌ Failed to list your posts: {}", e);
+                    }
+                    fail(&e);
+                }
+            }
+        }
+
+        Commands::Dashboard { limit, offset } => {
+            say!("🗂  Your posts (limit={}, offset={})", limit, offset);
+
+            match with_token_refresh(&client, || client.dashboard(Some(*limit), Some(*offset)))
+                .await
+            {
                 Ok(response) => {
-                    println!(
+                    say!(
                         "✅ Found {} posts (total: {})",
                         response.posts.len(),
                         response.total
                     );
-                    println!();
+                    say!();
 
-                    if response.posts.is_empty() {
-                        println!("   No posts found");
-                        println!("   Tip: Create your first post: cargo run -- create --title \"My Post\" --content \"Hello\"");
+                    for (i, entry) in response.posts.iter().enumerate() {
+                        say!(
+                            "   {}. [{}] {} ({:?})",
+                            i + 1,
+                            entry.post.id,
+                            entry.post.title,
+                            entry.status
+                        );
+                        say!("      Updated: {}", entry.post.updated_at);
+                        say!();
+                    }
+                    emit(&response);
+                }
+                Err(e) => {
+                    if e.is_expired() {
+                        print_expired_hint();
+                    } else if e.is_unauthorized() {
+                        say!("❌ Unauthorized. Please login first");
                     } else {
-                        for (i, post) in response.posts.iter().enumerate() {
-                            println!("   {}. [{}] {}", i + 1, post.id, post.title);
-                            println!("      Created: {}", post.created_at);
-                            println!("      Content: {}", truncate(&post.content, 50));
-                            println!();
-                        }
+                        say!("❌ Failed to load dashboard: {}", e);
                     }
+                    fail(&e);
+                }
+            }
+        }
+
+        Commands::Export { out } => {
+            if client.get_token().await.is_none() {
+                say!("❌ Not logged in. Please login first:");
+                say!("   cargo run -- login --username <username> --password <password>");
+                fail("Not logged in");
+            }
+
+            let result = match with_token_refresh(&client, || client.current_user()).await {
+                Ok(user) => {
+                    say!("📦 Exporting posts by {}...", user.username);
+                    client.list_all_posts(EXPORT_PAGE_SIZE).await.map(|posts| {
+                        posts
+                            .into_iter()
+                            .filter(|post| post.author_id == user.id)
+                            .collect::<Vec<_>>()
+                    })
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(posts) => {
+                    let json = serde_json::to_string_pretty(&posts)?;
+                    std::fs::write(out, json)
+                        .with_context(|| format!("Failed to write {}", out.display()))?;
+                    say!("✅ Exported {} posts to {}", posts.len(), out.display());
+                    emit(&serde_json::json!({ "exported": posts.len(), "out": out }));
                 }
                 Err(e) => {
-                    println!("❌ Failed to list posts: {}", e);
-                    std::process::exit(1);
+                    if e.is_expired() {
+                        print_expired_hint();
+                    } else if e.is_unauthorized() {
+                        say!("❌ Unauthorized. Please login first");
+                    } else {
+                        say!("❌ Failed to export posts: {}", e);
+                    }
+                    fail(&e);
                 }
             }
         }
+
+        Commands::Import { input } => {
+            let content = std::fs::read_to_string(input)
+                .with_context(|| format!("Failed to read {}", input.display()))?;
+            let entries: Vec<serde_json::Value> = serde_json::from_str(&content)
+                .with_context(|| format!("{} is not a JSON array", input.display()))?;
+
+            if client.get_token().await.is_none() {
+                say!("❌ Not logged in. Please login first:");
+                say!("   cargo run -- login --username <username> --password <password>");
+                fail("Not logged in");
+            }
+
+            say!(
+                "📥 Importing {} posts from {}",
+                entries.len(),
+                input.display()
+            );
+
+            let (mut created, mut failed, mut skipped) = (0, 0, 0);
+            for (i, entry) in entries.into_iter().enumerate() {
+                // Лишние поля экспорта (id, даты, лайки) игнорируются
+                let request: CreatePostRequest = match serde_json::from_value(entry) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        say!("   ⚠️  #{}: skipped malformed entry: {}", i + 1, e);
+                        skipped += 1;
+                        continue;
+                    }
+                };
+
+                match with_token_refresh(&client, || client.create_post_with(request.clone())).await
+                {
+                    Ok(post) => {
+                        say!("   ✅ #{}: [{}] {}", i + 1, post.id, post.title);
+                        created += 1;
+                    }
+                    Err(e) => {
+                        say!("   ❌ #{}: {}: {}", i + 1, request.title, e);
+                        failed += 1;
+                    }
+                }
+            }
+
+            say!();
+            say!(
+                "📊 Created: {}, failed: {}, skipped: {}",
+                created,
+                failed,
+                skipped
+            );
+            emit(&serde_json::json!({
+                "created": created,
+                "failed": failed,
+                "skipped": skipped,
+            }));
+            // Итог уже напечатан, код выхода сообщает скриптам о неудачах
+            if failed > 0 {
+                std::process::exit(1);
+            }
+        }
+
+        // Обрабатывается до создания клиента
+        Commands::Doctor { .. } => {}
     }
 
     Ok(())
 }
 
-fn transport_url(transport: &Transport) -> String {
-    match transport {
-        Transport::Http(url) => format!("HTTP: {}", url),
-        Transport::Grpc(addr) => format!("gRPC: {}", addr),
+/// Run every diagnostic check independently and print a checklist
+async fn run_doctor(http_base: &str, grpc_port: u16, token_store: &FileTokenStore) -> Result<()> {
+    // Диагностика не должна зависать на ретраях
+    let config = BlogClientConfig {
+        connect_timeout: Duration::from_secs(3),
+        retry: RetryPolicy::disabled(),
+        ..BlogClientConfig::default()
+    };
+
+    let mut failures = 0;
+
+    println!("🩺 Running diagnostics");
+    println!();
+
+    // 1. HTTP health
+    let http_client =
+        BlogClient::with_config(Transport::Http(http_base.to_string()), config.clone()).await?;
+    match http_client.ping().await {
+        Ok(()) => println!("   ✅ HTTP API reachable at {}", http_base),
+        Err(e) => {
+            failures += 1;
+            println!("   ❌ HTTP API at {}: {}", http_base, e);
+        }
+    }
+
+    // 2. gRPC connection on the sibling port
+    let grpc_addr = sibling_grpc_addr(http_base, grpc_port);
+    let grpc_client =
+        match BlogClient::with_config(Transport::Grpc(grpc_addr.clone()), config).await {
+            Ok(client) => match client.ping().await {
+                Ok(()) => {
+                    println!("   ✅ gRPC API reachable at {}", grpc_addr);
+                    Some(client)
+                }
+                Err(e) => {
+                    failures += 1;
+                    println!("   ❌ gRPC API at {}: {}", grpc_addr, e);
+                    None
+                }
+            },
+            Err(e) => {
+                failures += 1;
+                println!("   ❌ gRPC connection to {}: {}", grpc_addr, e);
+                None
+            }
+        };
+
+    // 3. Saved token
+    match token_store.load() {
+        Ok(Some(tokens)) => {
+            let client = grpc_client.as_ref().unwrap_or(&http_client);
+            match client.validate_token(tokens.access_token).await {
+                Ok(validation) if validation.valid => println!(
+                    "   ✅ Saved token is valid (user ID: {})",
+                    validation.user_id.unwrap_or_default()
+                ),
+                Ok(_) => {
+                    failures += 1;
+                    println!("   ❌ Saved token is invalid or expired, please login again");
+                }
+                Err(e) => {
+                    failures += 1;
+                    println!("   ❌ Could not verify saved token: {}", e);
+                }
+            }
+        }
+        Ok(None) => println!("   ⚠️ No saved token, skipping token check"),
+        Err(e) => {
+            failures += 1;
+            println!("   ❌ Failed to read token file: {}", e);
+        }
+    }
+
+    println!();
+    if failures == 0 {
+        println!("✅ All checks passed");
+        Ok(())
+    } else {
+        println!("❌ {} check(s) failed", failures);
+        std::process::exit(1);
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
+/// Build the gRPC address on the same host as the HTTP base URL
+fn sibling_grpc_addr(http_base: &str, grpc_port: u16) -> String {
+    let (scheme, rest) = http_base.split_once("://").unwrap_or(("http", http_base));
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => authority,
+    };
+
+    format!("{}://{}:{}", scheme, host, grpc_port)
+}
+
+// Для текстового вывода хватает отрывка от сервера, в --json отдается полный текст
+fn list_filter(limit: i64, offset: i64) -> ListPostsFilter {
+    ListPostsFilter {
+        limit: Some(limit),
+        offset: Some(offset),
+        summary: !JSON_OUTPUT.load(Ordering::Relaxed),
+        ..ListPostsFilter::default()
+    }
+}
+
+fn print_posts(response: &PostPage) {
+    emit(response);
+    say!(
+        "✅ Found {} posts (total: {})",
+        response.posts.len(),
+        response.total
+    );
+    say!();
+
+    if response.posts.is_empty() {
+        say!("   No posts found");
+        say!("   Tip: Create your first post: cargo run -- create --title \"My Post\" --content \"Hello\"");
     } else {
-        format!("{}...", &s[..max_len])
+        for (i, post) in response.posts.iter().enumerate() {
+            say!("   {}. [{}] {}", i + 1, post.id, post.title);
+            say!("      Created: {}", post.created_at);
+            say!(
+                "      Content: {}",
+                post.excerpt.as_deref().unwrap_or(&post.content)
+            );
+            say!();
+        }
+        say!(
+            "   Page {} of {}",
+            response.current_page(),
+            response.total_pages()
+        );
+        if response.has_next() {
+            say!(
+                "   Tip: Next page: --offset {}",
+                response.offset + response.limit
+            );
+        }
+    }
+}
+
+fn print_expired_hint() {
+    say!("⌛ Your token has expired. Get a new one without logging in again:");
+    say!("   cargo run -- refresh");
+}
+
+fn transport_url(transport: &Transport) -> String {
+    match transport {
+        Transport::Http(url) => format!("HTTP: {}", url),
+        Transport::Grpc(addr) => format!("gRPC: {}", addr),
+        Transport::Mock => "in-memory mock".to_string(),
     }
 }
@@ -0,0 +1,82 @@
+use anyhow::{bail, Context, Result};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Password for `register`/`login`: the `--password` value, the first line of
+/// stdin with `--password-stdin`, or an interactive prompt without echo
+pub fn resolve(password: Option<&str>, from_stdin: bool, confirm: bool) -> Result<String> {
+    let password = match (password, from_stdin) {
+        (Some(password), _) => password.to_string(),
+        (None, true) => {
+            read_line(&mut io::stdin().lock()).context("Failed to read the password from stdin")?
+        }
+        (None, false) => {
+            if !io::stdin().is_terminal() {
+                bail!("No password given: pass --password or pipe it with --password-stdin");
+            }
+            let password = prompt("Password: ")?;
+            if confirm && prompt("Confirm password: ")? != password {
+                bail!("Passwords do not match");
+            }
+            password
+        }
+    };
+
+    if password.is_empty() {
+        bail!("Password must not be empty");
+    }
+    Ok(password)
+}
+
+// Подсказка идет в stderr, чтобы не смешиваться с выводом --json
+fn prompt(message: &str) -> Result<String> {
+    eprint!("{}", message);
+    io::stderr().flush()?;
+    read_hidden_line().context("Failed to read the password")
+}
+
+// Пробелы - часть пароля, отрезается только перевод строки
+fn read_line(input: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    let len = line.trim_end_matches(['\r', '\n']).len();
+    line.truncate(len);
+    Ok(line)
+}
+
+/// Read a line from the terminal with echo turned off
+#[cfg(unix)]
+fn read_hidden_line() -> io::Result<String> {
+    use std::os::fd::AsRawFd;
+
+    let stdin = io::stdin();
+    let fd = stdin.as_raw_fd();
+
+    let mut original = std::mem::MaybeUninit::<libc::termios>::uninit();
+    // SAFETY: fd - открытый stdin, tcgetattr заполняет структуру целиком при успехе
+    if unsafe { libc::tcgetattr(fd, original.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: tcgetattr вернул 0, структура инициализирована
+    let original = unsafe { original.assume_init() };
+
+    let mut hidden = original;
+    hidden.c_lflag &= !libc::ECHO;
+    // Перевод строки после Enter все же печатается
+    hidden.c_lflag |= libc::ECHONL;
+    // SAFETY: hidden - корректная структура, полученная из tcgetattr
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &hidden) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let line = read_line(&mut stdin.lock());
+
+    // SAFETY: восстанавливаем исходные настройки терминала
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+    line
+}
+
+/// Without termios the password is read as a plain (echoed) line
+#[cfg(not(unix))]
+fn read_hidden_line() -> io::Result<String> {
+    read_line(&mut io::stdin().lock())
+}
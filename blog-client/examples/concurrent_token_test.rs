@@ -0,0 +1,44 @@
+use blog_client::{BlogClient, Transport};
+
+const TASKS: usize = 500;
+
+// Нагрузочная проверка: много параллельных get_post через один клиент,
+// пока токен периодически перезаписывается. Чтения токена не должны
+// блокировать друг друга, а запросы - видеть рассинхронизированный токен
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🚀 Параллельные запросы через один клиент");
+    println!("==========================================\n");
+
+    let client = BlogClient::new(Transport::Mock).await?;
+    client
+        .register("stress_user", "stress@example.com", "testpassword123")
+        .await?;
+    let post = client
+        .create_post("Shared post", "Read concurrently")
+        .await?;
+    let token = client.get_token().await.expect("token after register");
+
+    let started = std::time::Instant::now();
+    let mut handles = Vec::with_capacity(TASKS);
+    for i in 0..TASKS {
+        let client = client.clone();
+        let token = token.clone();
+        handles.push(tokio::spawn(async move {
+            if i % 50 == 0 {
+                client.set_token(token).await;
+            }
+            assert!(client.get_token().await.is_some());
+            client.get_post(post.id).await
+        }));
+    }
+
+    for handle in handles {
+        let fetched = handle.await??;
+        assert_eq!(fetched.id, post.id);
+    }
+    assert_eq!(client.get_token().await, Some(token));
+    println!("   ✅ {} запросов за {:?}", TASKS, started.elapsed());
+
+    Ok(())
+}
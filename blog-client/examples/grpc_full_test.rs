@@ -55,7 +55,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         Err(e) => println!("   ❌ Ошибка регистрации: {}", e),
     }
-    println!("");
+    println!();
 
     // Тест 2: Логин
     println!("🔑 Тест 2: Логин пользователя");
@@ -99,7 +99,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             // Сохраняем ID поста для следующих тестов
             let post_id = post.id;
-            println!("");
+            println!();
 
             // Тест 4: Получение поста по ID
             println!("🔍 Тест 4: Получение поста #{}", post_id);
@@ -111,7 +111,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 Err(e) => println!("   ❌ Ошибка получения поста: {}", e),
             }
-            println!("");
+            println!();
 
             // Тест 5: Обновление поста
             println!("✏️ Тест 5: Обновление поста #{}", post_id);
@@ -130,7 +130,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 Err(e) => println!("   ❌ Ошибка обновления поста: {}", e),
             }
-            println!("");
+            println!();
 
             // Тест 6: Список постов
             println!("📋 Тест 6: Список постов");
@@ -144,7 +144,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 Err(e) => println!("   ❌ Ошибка получения списка: {}", e),
             }
-            println!("");
+            println!();
 
             // Тест 7: Удаление поста
             println!("🗑️ Тест 7: Удаление поста #{}", post_id);
@@ -152,10 +152,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(()) => println!("   ✅ Пост успешно удален!"),
                 Err(e) => println!("   ❌ Ошибка удаления поста: {}", e),
             }
+            match client.delete_post(post_id).await {
+                Err(e) if e.is_not_found() => println!("   ✅ Повторное удаление: пост не найден"),
+                Ok(()) => println!("   ❌ Повторное удаление прошло успешно"),
+                Err(e) => println!("   ❌ Повторное удаление: неожиданная ошибка: {}", e),
+            }
         }
         Err(e) => println!("   ❌ Ошибка создания поста: {}", e),
     }
-    println!("");
+    println!();
 
     // Тест 8: Проверка токена
     println!("🔐 Тест 8: Проверка токена");
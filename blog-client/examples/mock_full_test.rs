@@ -0,0 +1,201 @@
+use blog_client::{
+    models::{CreatePostRequest, ListPostsFilter},
+    BlogClient, Transport,
+};
+
+// Тот же сценарий, что и grpc_full_test, но без сервера:
+// падает на первой несовпавшей проверке, поэтому годится для CI
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🚀 Тестирование клиента на mock транспорте");
+    println!("==========================================\n");
+
+    let client = BlogClient::new(Transport::Mock).await?;
+
+    println!("🔒 Тест 1: Защищенные операции без токена");
+    let err = client.create_post("Title", "Content").await.unwrap_err();
+    assert!(err.is_unauthorized(), "unexpected error: {}", err);
+    println!("   ✅ Отклонено: {}\n", err);
+
+    println!("📝 Тест 2: Регистрация и логин");
+    let registered = client
+        .register("mock_user", "mock@example.com", "testpassword123")
+        .await?;
+    assert!(client.get_token().await.is_some());
+    let err = client
+        .register("mock_user", "other@example.com", "testpassword123")
+        .await
+        .unwrap_err();
+    assert!(err.is_conflict(), "unexpected error: {}", err);
+    println!("   ✅ Повторная регистрация отклонена: {}", err);
+
+    client.logout().await?;
+    assert!(client.login("mock_user", "wrong").await.is_err());
+    let logged_in = client.login("mock_user", "testpassword123").await?;
+    assert_eq!(logged_in.user.id, registered.user.id);
+    let by_email = client
+        .login_with_email("MOCK@example.com", "testpassword123")
+        .await?;
+    assert_eq!(by_email.user.id, registered.user.id);
+    assert!(client.current_user().await?.last_login_at.is_some());
+    assert!(client
+        .get_user(registered.user.id)
+        .await?
+        .last_login_at
+        .is_none());
+    println!("   ✅ User ID: {}\n", logged_in.user.id);
+
+    println!("✍️ Тест 3: Создание, чтение, обновление");
+    let post = client
+        .create_post_with_tags("First post", "Hello from mock", vec!["Rust".into()])
+        .await?;
+    assert_eq!(post.tags, vec!["rust".to_string()]);
+    let draft = client.create_draft("Draft", "Not yet").await?;
+
+    let fetched = client.get_post(post.id).await?;
+    assert_eq!(fetched.title, "First post");
+    assert!(!fetched.edited);
+
+    let updated = client
+        .update_post(post.id, Some("Updated title".into()), None)
+        .await?;
+    assert_eq!(updated.title, "Updated title");
+    assert!(updated.edited);
+    assert_eq!(client.like_post(post.id).await?, 1);
+    let comment = client.add_comment(post.id, "Nice post").await?;
+    assert_eq!(comment.author_id, registered.user.id);
+    assert!(client.add_comment(post.id, "  ").await.is_err());
+    client.add_comment(draft.id, "Note to self").await?;
+    println!(
+        "   ✅ Пост {} обновлен, лайкнут и прокомментирован\n",
+        post.id
+    );
+
+    println!("📋 Тест 4: Список постов");
+    let batch: Vec<CreatePostRequest> = (0..3)
+        .map(|i| CreatePostRequest {
+            title: format!("Post {}", i),
+            content: "Paged content".into(),
+            tags: Vec::new(),
+            content_format: Default::default(),
+            published: true,
+        })
+        .collect();
+    let mut invalid_batch = batch.clone();
+    invalid_batch[1].content = " ".into();
+    let err = client.create_posts(invalid_batch).await.unwrap_err();
+    assert!(err.to_string().contains("posts[1]"), "{err}");
+    let created = client.create_posts(batch).await?;
+    assert_eq!(created.len(), 3);
+    assert_eq!(created[2].title, "Post 2");
+    let page = client.list_posts(Some(2), None).await?;
+    assert_eq!(page.total, 4, "drafts must not be listed");
+    assert_eq!(page.posts.len(), 2);
+    assert_eq!((page.page, page.page_size, page.total_pages), (1, 2, 2));
+
+    let next = client
+        .list_posts_filtered(ListPostsFilter {
+            limit: Some(2),
+            cursor: page.next_cursor.clone(),
+            ..ListPostsFilter::default()
+        })
+        .await?;
+    assert_eq!(next.posts.len(), 2);
+    assert!(next.next_cursor.is_none());
+    println!("   ✅ Всего постов: {}, курсор работает\n", page.total);
+
+    println!("👀 Тест 5: Черновик виден только автору");
+    client.logout().await?;
+    assert!(client.get_post(draft.id).await.unwrap_err().is_not_found());
+    assert!(client.delete_post(post.id).await.is_err());
+
+    client
+        .register("other_user", "other@example.com", "testpassword123")
+        .await?;
+    assert!(client
+        .delete_post(post.id)
+        .await
+        .unwrap_err()
+        .is_forbidden());
+    assert!(client
+        .add_comment(draft.id, "Peek")
+        .await
+        .unwrap_err()
+        .is_not_found());
+    client.add_comment(post.id, "Reply").await?;
+    let comments = client.list_comments(post.id, Some(1), Some(1)).await?;
+    assert_eq!(comments.total, 2);
+    assert_eq!(comments.comments[0].content, "Reply");
+    let other_token = client.get_token().await.unwrap();
+    println!("   ✅ Чужие черновики и посты защищены\n");
+
+    println!("🗑️ Тест 6: Удаление");
+    client.login("mock_user", "testpassword123").await?;
+    // Копия с токеном другого пользователя не меняет токен основного клиента
+    let as_other = client.with_token(other_token).await;
+    assert!(as_other
+        .delete_post(post.id)
+        .await
+        .unwrap_err()
+        .is_forbidden());
+    let foreign = as_other.create_post("Other's post", "Hello").await?;
+    assert_ne!(foreign.author_id, registered.user.id);
+    assert_eq!(client.current_user().await?.id, registered.user.id);
+    as_other.delete_post(foreign.id).await?;
+    client.delete_post(post.id).await?;
+    assert!(client.get_post(post.id).await.unwrap_err().is_not_found());
+    assert!(client
+        .list_comments(post.id, None, None)
+        .await
+        .unwrap_err()
+        .is_not_found());
+    assert!(client
+        .delete_post(post.id)
+        .await
+        .unwrap_err()
+        .is_not_found());
+    assert_eq!(client.delete_my_posts().await?, 4);
+    assert_eq!(client.list_posts(None, None).await?.total, 0);
+    println!("   ✅ Посты удалены\n");
+
+    println!("📦 Тест 7: Пакетные операции в режиме best_effort");
+    let new_post = |title: &str| CreatePostRequest {
+        title: title.into(),
+        content: "Batch".into(),
+        tags: vec![],
+        content_format: Default::default(),
+        published: true,
+    };
+    let batch = client
+        .create_posts_best_effort(vec![
+            new_post("One"),
+            new_post("Two"),
+            new_post("Three"),
+            new_post(""),
+            new_post("Five"),
+        ])
+        .await?;
+    assert_eq!(batch.posts.len(), 4);
+    let failed: Vec<_> = batch.failed().collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].index, 3);
+    assert!(failed[0].error.as_deref().unwrap().contains("Title"));
+    let foreign = as_other.create_post("Other's post", "Hello").await?;
+    let ids = vec![batch.posts[0].id, foreign.id];
+    assert!(client
+        .delete_posts(ids.clone())
+        .await
+        .unwrap_err()
+        .is_forbidden());
+    let deleted = client.delete_posts_best_effort(ids).await?;
+    assert!(deleted[0].ok && !deleted[1].ok);
+    let found = client
+        .get_posts_best_effort(vec![batch.posts[0].id, batch.posts[1].id])
+        .await?;
+    assert_eq!(found.posts.len(), 1);
+    assert!(!found.results[0].ok && found.results[1].ok);
+    println!("   ✅ Ошибки сообщаются по каждому элементу\n");
+
+    println!("🎉 Все проверки пройдены");
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::token_store::TokenStore;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Configuration shared by both transports of [`crate::BlogClient`]
+#[derive(Debug, Clone)]
+pub struct BlogClientConfig {
+    /// Maximum time allowed for a whole request/RPC
+    pub request_timeout: Duration,
+    /// Maximum time allowed to establish a connection
+    pub connect_timeout: Duration,
+    /// Retry policy for idempotent operations
+    pub retry: RetryPolicy,
+    /// PEM CA certificate trusted for `https://` gRPC addresses in addition
+    /// to the public web roots (e.g. for a self-signed server certificate)
+    pub tls_ca_certificate: Option<String>,
+    /// Gzip-compress gRPC messages and ask the server for compressed responses
+    pub compression: bool,
+    /// Where tokens are persisted between runs (`None` keeps them in memory only)
+    pub token_store: Option<Arc<dyn TokenStore>>,
+}
+
+impl Default for BlogClientConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            retry: RetryPolicy::default(),
+            tls_ca_certificate: None,
+            compression: true,
+            token_store: None,
+        }
+    }
+}
+
+impl BlogClientConfig {
+    /// Load saved tokens on client creation and persist them after
+    /// register/login/refresh (e.g. a [`crate::token_store::FileTokenStore`])
+    pub fn with_token_store(mut self, store: impl TokenStore + 'static) -> Self {
+        self.token_store = Some(Arc::new(store));
+        self
+    }
+}
+
+/// Exponential backoff policy applied to idempotent operations
+/// (`get_post`, `list_posts`, ...). Mutating calls are never retried, except
+/// for the single gRPC resend after a broken channel has been rebuilt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on every following attempt
+    pub base_delay: Duration,
+    /// Upper bound for a delay requested by the server via Retry-After
+    pub max_retry_after: Duration,
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::ZERO,
+            max_retry_after: Duration::ZERO,
+        }
+    }
+
+    /// Delay to wait before retry number `attempt` (starting from 0)
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(attempt))
+    }
+
+    /// Delay before retry number `attempt`: the server-requested
+    /// Retry-After (capped at `max_retry_after`) or exponential backoff
+    pub fn delay_with_hint(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        match retry_after {
+            Some(delay) => delay.min(self.max_retry_after),
+            None => self.delay_for(attempt),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay: Duration::from_millis(200),
+            max_retry_after: Duration::from_secs(30),
+        }
+    }
+}
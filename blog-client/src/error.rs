@@ -1,15 +1,32 @@
+use std::time::Duration;
 use thiserror::Error;
 use tonic::transport::Error as GrpcTransportError;
 
+/// Status message the server uses for an expired access token over gRPC
+const TOKEN_EXPIRED_MESSAGE: &str = "Token expired";
+
+/// Status message tonic reports when `request_timeout` expires on a channel
+const GRPC_TIMEOUT_MESSAGE: &str = "Timeout expired";
+
+/// gRPC metadata key carrying the server-requested retry delay in seconds
+pub(crate) const RETRY_AFTER_METADATA: &str = "retry-after";
+
+/// HTTP header / gRPC metadata key with the id the server logged the request under
+pub(crate) const REQUEST_ID_METADATA: &str = "x-request-id";
+
 #[derive(Debug, Error)]
 pub enum BlogClientError {
     // HTTP ошибки
     #[error("HTTP request failed: {0}")]
-    HttpError(#[from] reqwest::Error),
+    HttpError(reqwest::Error),
+
+    // Сервер не ответил за `request_timeout` / `connect_timeout`
+    #[error("Request timed out: {0}")]
+    Timeout(#[source] reqwest::Error),
 
     // gRPC ошибки
     #[error("gRPC error: {0}")]
-    GrpcError(#[from] tonic::Status),
+    GrpcError(tonic::Status),
 
     #[error("gRPC transport error: {0}")]
     GrpcTransportError(#[from] GrpcTransportError),
@@ -25,16 +42,74 @@ pub enum BlogClientError {
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("Token expired")]
+    TokenExpired,
+
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    // Ресурс уже существует (HTTP 409 / gRPC ALREADY_EXISTS)
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    // Недостаточно прав (HTTP 403 / gRPC PERMISSION_DENIED)
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     // Транспортные ошибки
     #[error("Transport error: {0}")]
     TransportError(String),
 
+    // Сервер просит повторить запрос позже (429 / gRPC RESOURCE_EXHAUSTED и 503)
+    #[error("Rate limited by server{}", retry_hint(.retry_after))]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("Service unavailable{}", retry_hint(.retry_after))]
+    ServiceUnavailable { retry_after: Option<Duration> },
+
     // Ошибки сериализации/десериализации
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    // Не удалось прочитать или сохранить токены (TokenStore)
+    #[error("Token store error: {0}")]
+    TokenStoreError(#[source] std::io::Error),
+}
+
+// Таймаут выделяется в отдельный вариант, чтобы его можно было повторить
+impl From<reqwest::Error> for BlogClientError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            BlogClientError::Timeout(error)
+        } else {
+            BlogClientError::HttpError(error)
+        }
+    }
+}
+
+// Коды, которые обе транспортные стороны сообщают одинаково, становятся
+// отдельными вариантами; остальные статусы сохраняются как есть
+impl From<tonic::Status> for BlogClientError {
+    fn from(status: tonic::Status) -> Self {
+        match status.code() {
+            // Как 404 в HTTP: is_not_found() одинаково работает для обоих транспортов
+            tonic::Code::NotFound => BlogClientError::NotFound,
+            tonic::Code::AlreadyExists | tonic::Code::Aborted => {
+                BlogClientError::Conflict(status.message().to_string())
+            }
+            tonic::Code::PermissionDenied => {
+                BlogClientError::Forbidden(status.message().to_string())
+            }
+            tonic::Code::ResourceExhausted => BlogClientError::RateLimited {
+                retry_after: status
+                    .metadata()
+                    .get(RETRY_AFTER_METADATA)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(parse_retry_after),
+            },
+            _ => BlogClientError::GrpcError(status),
+        }
+    }
 }
 
 impl BlogClientError {
@@ -42,7 +117,143 @@ impl BlogClientError {
         matches!(self, BlogClientError::NotFound)
     }
 
+    /// The resource already exists, e.g. the username is taken
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, BlogClientError::Conflict(_))
+    }
+
+    /// The caller is authenticated but not allowed to do this,
+    /// e.g. edit someone else's post
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, BlogClientError::Forbidden(_))
+    }
+
     pub fn is_unauthorized(&self) -> bool {
-        matches!(self, BlogClientError::Unauthorized(_))
+        matches!(
+            self,
+            BlogClientError::Unauthorized(_) | BlogClientError::TokenExpired
+        )
+    }
+
+    /// The server did not answer in time (HTTP timeout or gRPC DEADLINE_EXCEEDED)
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            BlogClientError::Timeout(_) => true,
+            BlogClientError::HttpError(e) => e.is_timeout(),
+            BlogClientError::GrpcError(status) => match status.code() {
+                tonic::Code::DeadlineExceeded => true,
+                tonic::Code::Cancelled => status.message() == GRPC_TIMEOUT_MESSAGE,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// The server throttled the request (HTTP 429 / gRPC RESOURCE_EXHAUSTED);
+    /// see [`BlogClientError::retry_after`] for when to try again
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, BlogClientError::RateLimited { .. })
+    }
+
+    /// The connection to the server could not be established,
+    /// e.g. nothing listens on the port
+    pub fn is_connect(&self) -> bool {
+        match self {
+            BlogClientError::HttpError(e) | BlogClientError::Timeout(e) => e.is_connect(),
+            BlogClientError::GrpcTransportError(_) => true,
+            _ => false,
+        }
+    }
+
+    /// The access token has expired: it can be renewed via
+    /// `BlogClient::refresh` instead of logging in again
+    pub fn is_expired(&self) -> bool {
+        match self {
+            BlogClientError::TokenExpired => true,
+            BlogClientError::GrpcError(status) => {
+                status.code() == tonic::Code::Unauthenticated
+                    && status.message() == TOKEN_EXPIRED_MESSAGE
+            }
+            _ => false,
+        }
+    }
+
+    /// Delay requested by the server via Retry-After (HTTP) or
+    /// `retry-after` metadata (gRPC), if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            BlogClientError::RateLimited { retry_after }
+            | BlogClientError::ServiceUnavailable { retry_after } => *retry_after,
+            BlogClientError::GrpcError(status) => status
+                .metadata()
+                .get(RETRY_AFTER_METADATA)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after),
+            _ => None,
+        }
+    }
+
+    /// Id the server logged the failed request under: `request_id` of an HTTP
+    /// error body or `x-request-id` metadata of a gRPC status, if any
+    pub fn request_id(&self) -> Option<String> {
+        match self {
+            BlogClientError::GrpcError(status) => status
+                .metadata()
+                .get(REQUEST_ID_METADATA)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            BlogClientError::Unauthorized(body)
+            | BlogClientError::InvalidRequest(body)
+            | BlogClientError::Conflict(body)
+            | BlogClientError::Forbidden(body)
+            | BlogClientError::TransportError(body) => request_id_from_body(body),
+            _ => None,
+        }
     }
+
+    /// Failure that is worth retrying for idempotent calls: connection-level
+    /// errors, timeouts and server throttling
+    pub(crate) fn is_transient(&self) -> bool {
+        if self.is_connect() || self.is_timeout() {
+            return true;
+        }
+        match self {
+            BlogClientError::TransportError(_)
+            | BlogClientError::RateLimited { .. }
+            | BlogClientError::ServiceUnavailable { .. } => true,
+            BlogClientError::GrpcError(status) => status.code() == tonic::Code::Unavailable,
+            _ => false,
+        }
+    }
+}
+
+// ", try again in 5 seconds" для сообщения об ошибке, если сервер назвал задержку
+fn retry_hint(retry_after: &Option<Duration>) -> String {
+    match retry_after {
+        Some(delay) => format!(", try again in {} seconds", delay.as_secs().max(1)),
+        None => String::new(),
+    }
+}
+
+// HTTP-ошибки хранят тело ответа ({"error", "code", "request_id"}), иногда с префиксом "HTTP 500: "
+fn request_id_from_body(body: &str) -> Option<String> {
+    let json = &body[body.find('{')?..];
+    serde_json::from_str::<serde_json::Value>(json)
+        .ok()?
+        .get("request_id")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Parse a Retry-After value: delay in seconds or an HTTP-date
+/// (a date in the past means "retry now")
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
 }
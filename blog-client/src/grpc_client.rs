@@ -1,27 +1,79 @@
-use crate::error::BlogClientError;
-use tonic::{metadata::MetadataValue, transport::Channel, Request};
+use crate::config::BlogClientConfig;
+use crate::error::{BlogClientError, REQUEST_ID_METADATA};
+use crate::models::{self, ListPostsFilter};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use std::error::Error as _;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use tonic::{
+    codec::CompressionEncoding,
+    metadata::MetadataValue,
+    transport::{Certificate, Channel, ClientTlsConfig, Endpoint},
+    Extensions, Request, Response, Status,
+};
 
 pub use crate::proto::{
     auth_service_client::AuthServiceClient, post_service_client::PostServiceClient,
-    CreatePostRequest, DeletePostRequest, GetPostRequest, ListPostsRequest, ListPostsResponse,
-    LoginRequest, LoginResponse, Post, RegisterRequest, RegisterResponse, UpdatePostRequest, User,
+    AddCommentRequest, ChangePasswordRequest, CheckAvailabilityRequest, CountPostsRequest,
+    CreatePostRequest, CreatePostsRequest, DashboardRequest, DashboardResponse,
+    DeleteMyPostsRequest, DeletePostRequest, DeletePostsRequest, DeleteUserPostsRequest,
+    GetCurrentUserRequest, GetPostRequest, GetPostsRequest, GetUserRequest, LikePostRequest,
+    ListCommentsRequest, ListPostsCursorRequest, ListPostsCursorResponse, ListPostsRequest,
+    ListPostsResponse, LoginRequest, LoginResponse, LogoutRequest, Post, RefreshTokenRequest,
+    RefreshTokenResponse, RegisterRequest, RegisterResponse, RenameTagRequest, StreamPostsRequest,
+    UpdatePostRequest, UpdateProfileRequest, User, ValidateTokenRequest, ValidateTokenResponse,
 };
 
+/// Generated service clients over one channel
+#[derive(Debug, Clone)]
+struct Services {
+    auth: AuthServiceClient<Channel>,
+    post: PostServiceClient<Channel>,
+}
+
 #[derive(Debug, Clone)]
 pub struct GrpcClient {
-    auth_client: AuthServiceClient<Channel>,
-    post_client: PostServiceClient<Channel>,
+    // Адрес (с TLS и таймаутами), по которому канал пересоздается после обрыва
+    endpoint: Endpoint,
+    channel: Arc<RwLock<Channel>>,
+    reconnect: bool,
+    compression: bool,
     token: Option<String>,
+    request_id: Option<String>,
 }
 
 impl GrpcClient {
     pub async fn new(addr: impl Into<String>) -> Result<Self, BlogClientError> {
+        Self::with_config(addr, &BlogClientConfig::default()).await
+    }
+
+    pub async fn with_config(
+        addr: impl Into<String>,
+        config: &BlogClientConfig,
+    ) -> Result<Self, BlogClientError> {
         let addr = addr.into();
-        let channel = Channel::from_shared(addr.clone())?.connect().await?;
+        let mut endpoint = Channel::from_shared(addr.clone())?
+            .timeout(config.request_timeout)
+            .connect_timeout(config.connect_timeout);
+
+        // https:// включает TLS: публичные корневые сертификаты плюс CA из конфигурации
+        if endpoint.uri().scheme_str() == Some("https") {
+            let mut tls = ClientTlsConfig::new().with_webpki_roots();
+            if let Some(ca) = &config.tls_ca_certificate {
+                tls = tls.ca_certificate(Certificate::from_pem(ca));
+            }
+            endpoint = endpoint.tls_config(tls)?;
+        }
+
+        let channel = endpoint.connect().await?;
         Ok(Self {
-            auth_client: AuthServiceClient::new(channel.clone()),
-            post_client: PostServiceClient::new(channel),
+            endpoint,
+            channel: Arc::new(RwLock::new(channel)),
+            reconnect: config.retry.max_retries > 0,
+            compression: config.compression,
             token: None,
+            request_id: None,
         })
     }
 
@@ -33,7 +85,90 @@ impl GrpcClient {
         self.token.as_ref()
     }
 
-    fn add_auth_header<T>(&self, mut request: Request<T>) -> Request<T> {
+    pub fn clear_token(&mut self) {
+        self.token = None;
+    }
+
+    /// `x-request-id` sent with every following call (`None` lets the server generate one)
+    pub fn set_request_id(&mut self, request_id: Option<String>) {
+        self.request_id = request_id;
+    }
+
+    fn channel(&self) -> Channel {
+        self.channel
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    // С включенным сжатием запросы уходят в gzip, а ответы запрашиваются в gzip
+    fn services(&self, channel: Channel) -> Services {
+        let mut auth = AuthServiceClient::new(channel.clone());
+        let mut post = PostServiceClient::new(channel);
+        if self.compression {
+            auth = auth
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip);
+            post = post
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip);
+        }
+        Services { auth, post }
+    }
+
+    /// Run `rpc` on the current channel. If it fails at the transport level
+    /// (e.g. the server was restarted), rebuild the channel from the stored
+    /// endpoint and run it once more; disabled by `RetryPolicy::disabled()`
+    async fn call<M, T, F, Fut>(
+        &self,
+        request: Request<M>,
+        rpc: F,
+    ) -> Result<Response<T>, BlogClientError>
+    where
+        M: Clone,
+        F: Fn(Services, Request<M>) -> Fut,
+        Fut: Future<Output = Result<Response<T>, Status>>,
+    {
+        let (metadata, _, message) = request.into_parts();
+        let attempt = |channel| {
+            rpc(
+                self.services(channel),
+                Request::from_parts(metadata.clone(), Extensions::default(), message.clone()),
+            )
+        };
+
+        match attempt(self.channel()).await {
+            Err(status) if self.reconnect && is_transport_failure(&status) => {
+                tracing::debug!(
+                    "gRPC transport error: {}, reconnecting to {}",
+                    status,
+                    self.endpoint.uri()
+                );
+                let channel = self.endpoint.connect().await?;
+                *self
+                    .channel
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = channel.clone();
+                Ok(attempt(channel).await?)
+            }
+            result => Ok(result?),
+        }
+    }
+
+    fn add_request_id<T>(&self, mut request: Request<T>) -> Request<T> {
+        if let Some(value) = self
+            .request_id
+            .as_deref()
+            .and_then(|id| id.parse::<MetadataValue<_>>().ok())
+        {
+            request.metadata_mut().insert(REQUEST_ID_METADATA, value);
+        }
+        request
+    }
+
+    // Заодно проставляет x-request-id, так что защищенные вызовы его не теряют
+    fn add_auth_header<T>(&self, request: Request<T>) -> Request<T> {
+        let mut request = self.add_request_id(request);
         if let Some(token) = &self.token {
             let auth_value = format!("Bearer {}", token)
                 .parse::<MetadataValue<_>>()
@@ -49,14 +184,18 @@ impl GrpcClient {
         username: String,
         email: String,
         password: String,
-    ) -> Result<RegisterResponse, BlogClientError> {
-        let request = Request::new(RegisterRequest {
-            username,
-            email,
+    ) -> Result<models::AuthResult, BlogClientError> {
+        let request = self.add_request_id(Request::new(RegisterRequest {
+            username: username.clone(),
+            email: email.clone(),
             password,
-        });
+        }));
 
-        let response = self.auth_client.clone().register(request).await?;
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.register(request).await
+            })
+            .await?;
 
         let register_response = response.into_inner();
 
@@ -64,21 +203,40 @@ impl GrpcClient {
             self.set_token(register_response.token.clone());
         }
 
-        Ok(register_response)
+        // RegisterResponse несет только id, остальной профиль известен из запроса
+        Ok(models::AuthResult {
+            token: register_response.token,
+            refresh_token: None,
+            is_admin: false,
+            expires_in: None,
+            user: models::User {
+                id: register_response.user_id,
+                username,
+                email,
+                full_name: None,
+                bio: None,
+                avatar_url: None,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                last_login_at: None,
+            },
+        })
     }
 
     pub async fn login(
         &mut self,
-        username: String,
-        password: String,
-    ) -> Result<LoginResponse, BlogClientError> {
-        let request = Request::new(LoginRequest {
-            username,
-            email: "".to_string(),
-            password,
-        });
+        req: models::LoginRequest,
+    ) -> Result<models::AuthResult, BlogClientError> {
+        let request = self.add_request_id(Request::new(LoginRequest {
+            username: req.username,
+            email: req.email.unwrap_or_default(),
+            password: req.password,
+        }));
 
-        let response = self.auth_client.clone().login(request).await?;
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.login(request).await
+            })
+            .await?;
 
         // Сохраняем токен, если он есть в ответе
         let token = response.get_ref().token.clone();
@@ -86,49 +244,246 @@ impl GrpcClient {
             self.set_token(token);
         }
 
-        Ok(response.into_inner())
+        let response = response.into_inner();
+        let user = response
+            .user
+            .ok_or_else(|| BlogClientError::InvalidRequest("No user data in response".into()))?;
+
+        Ok(models::AuthResult {
+            token: response.token,
+            refresh_token: Some(response.refresh_token).filter(|t| !t.is_empty()),
+            is_admin: response.is_admin,
+            user: models::User::from(user),
+            expires_in: Some(response.expires_in.into()),
+        })
+    }
+
+    pub async fn validate_token(
+        &self,
+        token: String,
+    ) -> Result<models::TokenValidation, BlogClientError> {
+        let request = self.add_request_id(Request::new(ValidateTokenRequest { token }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.validate_token(request).await
+            })
+            .await?;
+        let response = response.into_inner();
+        Ok(models::TokenValidation {
+            valid: response.valid,
+            user_id: response.valid.then_some(response.user_id),
+        })
+    }
+
+    pub async fn change_password(
+        &self,
+        old_password: String,
+        new_password: String,
+    ) -> Result<(), BlogClientError> {
+        let request = self.add_auth_header(Request::new(ChangePasswordRequest {
+            old_password,
+            new_password,
+        }));
+        self.call(request, |mut services, request| async move {
+            services.auth.change_password(request).await
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub async fn current_user(&self) -> Result<models::User, BlogClientError> {
+        let request = self.add_auth_header(Request::new(GetCurrentUserRequest {}));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.get_current_user(request).await
+            })
+            .await?;
+        Ok(models::User::from(response.into_inner()))
+    }
+
+    pub async fn get_user(&self, id: i64) -> Result<models::User, BlogClientError> {
+        let request = self.add_auth_header(Request::new(GetUserRequest { id }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.get_user(request).await
+            })
+            .await?;
+        Ok(models::User::from(response.into_inner()))
+    }
+
+    pub async fn update_profile(
+        &self,
+        changes: models::UpdateProfileRequest,
+    ) -> Result<models::User, BlogClientError> {
+        let request = self.add_auth_header(Request::new(UpdateProfileRequest {
+            email: changes.email,
+            full_name: changes.full_name,
+            bio: changes.bio,
+            avatar_url: changes.avatar_url,
+        }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.update_profile(request).await
+            })
+            .await?;
+        Ok(models::User::from(response.into_inner()))
+    }
+
+    pub async fn logout(&self, token: String) -> Result<(), BlogClientError> {
+        let request = self.add_auth_header(Request::new(LogoutRequest { token }));
+        self.call(request, |mut services, request| async move {
+            services.auth.logout(request).await
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Exchange a refresh token for a new access token (not stored automatically)
+    pub async fn refresh(&self, refresh_token: String) -> Result<String, BlogClientError> {
+        let request = self.add_request_id(Request::new(RefreshTokenRequest { refresh_token }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.refresh_token(request).await
+            })
+            .await?;
+        Ok(response.into_inner().token)
+    }
+
+    pub async fn check_availability(
+        &self,
+        username: Option<String>,
+        email: Option<String>,
+    ) -> Result<bool, BlogClientError> {
+        let request = self.add_request_id(Request::new(CheckAvailabilityRequest {
+            username: username.unwrap_or_default(),
+            email: email.unwrap_or_default(),
+        }));
+
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.auth.check_availability(request).await
+            })
+            .await?;
+        Ok(response.into_inner().available)
     }
 
     // Post methods
     pub async fn create_post(
         &self,
-        title: String,
-        content: String,
-    ) -> Result<Post, BlogClientError> {
-        let request = self.add_auth_header(Request::new(CreatePostRequest {
-            title,
-            content,
-            author_id: 0,
-            tags: vec![],
-            published: true,
+        post: models::CreatePostRequest,
+        idempotency_key: Option<&str>,
+    ) -> Result<models::Post, BlogClientError> {
+        let mut request = self.add_auth_header(Request::new(create_request_to_proto(post)));
+        if let Some(key) = idempotency_key {
+            let value = key.parse::<MetadataValue<_>>().map_err(|_| {
+                BlogClientError::InvalidRequest("Invalid idempotency key".to_string())
+            })?;
+            request.metadata_mut().insert("idempotency-key", value);
+        }
+
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.create_post(request).await
+            })
+            .await?;
+        Ok(models::Post::from(response.into_inner()))
+    }
+
+    pub async fn create_posts(
+        &self,
+        posts: Vec<models::CreatePostRequest>,
+    ) -> Result<Vec<models::Post>, BlogClientError> {
+        Ok(self.create_posts_batch(posts, false).await?.posts)
+    }
+
+    pub async fn create_posts_best_effort(
+        &self,
+        posts: Vec<models::CreatePostRequest>,
+    ) -> Result<models::BatchResult, BlogClientError> {
+        self.create_posts_batch(posts, true).await
+    }
+
+    async fn create_posts_batch(
+        &self,
+        posts: Vec<models::CreatePostRequest>,
+        best_effort: bool,
+    ) -> Result<models::BatchResult, BlogClientError> {
+        let request = self.add_auth_header(Request::new(CreatePostsRequest {
+            posts: posts.into_iter().map(create_request_to_proto).collect(),
+            best_effort,
+        }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.create_posts(request).await
+            })
+            .await?
+            .into_inner();
+        Ok(batch_result(response.posts, response.results))
+    }
+
+    pub async fn get_post(&self, id: i64) -> Result<models::Post, BlogClientError> {
+        // Токен (если есть) позволяет автору получить свой черновик
+        let request = self.add_auth_header(Request::new(GetPostRequest {
+            id,
+            include_author: false,
         }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.get_post(request).await
+            })
+            .await?;
+        Ok(models::Post::from(response.into_inner()))
+    }
 
-        let response = self.post_client.clone().create_post(request).await?;
-        Ok(response.into_inner())
+    pub async fn get_posts(&self, ids: Vec<i64>) -> Result<Vec<models::Post>, BlogClientError> {
+        Ok(self.get_posts_batch(ids, false).await?.posts)
     }
 
-    pub async fn get_post(&self, id: i64) -> Result<Post, BlogClientError> {
-        let request = Request::new(GetPostRequest { id });
-        let response = self.post_client.clone().get_post(request).await?;
-        Ok(response.into_inner())
+    pub async fn get_posts_best_effort(
+        &self,
+        ids: Vec<i64>,
+    ) -> Result<models::BatchResult, BlogClientError> {
+        self.get_posts_batch(ids, true).await
+    }
+
+    async fn get_posts_batch(
+        &self,
+        ids: Vec<i64>,
+        best_effort: bool,
+    ) -> Result<models::BatchResult, BlogClientError> {
+        let request = self.add_auth_header(Request::new(GetPostsRequest { ids, best_effort }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.get_posts(request).await
+            })
+            .await?
+            .into_inner();
+        Ok(batch_result(response.posts, response.results))
     }
 
     pub async fn update_post(
         &self,
         id: i64,
-        title: Option<String>,
-        content: Option<String>,
-    ) -> Result<Post, BlogClientError> {
+        changes: models::UpdatePostRequest,
+    ) -> Result<models::Post, BlogClientError> {
         let request = self.add_auth_header(Request::new(UpdatePostRequest {
             id,
-            title,
-            content,
+            title: changes.title,
+            content: changes.content,
             tags: vec![],
-            published: None,
+            published: changes.published,
+            content_format: changes
+                .content_format
+                .map(|format| format.as_str().to_string()),
+            expected_updated_at: changes.expected_updated_at,
         }));
 
-        let response = self.post_client.clone().update_post(request).await?;
-        Ok(response.into_inner())
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.update_post(request).await
+            })
+            .await?;
+        Ok(models::Post::from(response.into_inner()))
     }
 
     pub async fn delete_post(&self, id: i64) -> Result<(), BlogClientError> {
@@ -137,31 +492,384 @@ impl GrpcClient {
             token: "".to_string(),
         }));
 
-        let response = self.post_client.clone().delete_post(request).await?;
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.delete_post(request).await
+            })
+            .await?;
         let result = response.into_inner();
 
+        // Сервер сообщает об отсутствии поста статусом NOT_FOUND, но success: false
+        // с пустым или "not found" сообщением означает то же самое
         if result.success {
             Ok(())
+        } else if result.message.trim().is_empty()
+            || result.message.to_lowercase().contains("not found")
+        {
+            Err(BlogClientError::NotFound)
         } else {
             Err(BlogClientError::TransportError(result.message))
         }
     }
 
+    pub async fn delete_posts(&self, ids: Vec<i64>) -> Result<u64, BlogClientError> {
+        let request = self.add_auth_header(Request::new(DeletePostsRequest {
+            ids,
+            best_effort: false,
+        }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.delete_posts(request).await
+            })
+            .await?;
+        Ok(response.into_inner().deleted)
+    }
+
+    pub async fn delete_posts_best_effort(
+        &self,
+        ids: Vec<i64>,
+    ) -> Result<Vec<models::BatchItemResult>, BlogClientError> {
+        let request = self.add_auth_header(Request::new(DeletePostsRequest {
+            ids,
+            best_effort: true,
+        }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.delete_posts(request).await
+            })
+            .await?;
+        Ok(response
+            .into_inner()
+            .results
+            .into_iter()
+            .map(models::BatchItemResult::from)
+            .collect())
+    }
+
+    pub async fn delete_my_posts(&self) -> Result<u64, BlogClientError> {
+        let request = self.add_auth_header(Request::new(DeleteMyPostsRequest {}));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.delete_my_posts(request).await
+            })
+            .await?;
+        Ok(response.into_inner().deleted)
+    }
+
+    pub async fn delete_user_posts(&self, user_id: i64) -> Result<u64, BlogClientError> {
+        let request = self.add_auth_header(Request::new(DeleteUserPostsRequest { user_id }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.delete_user_posts(request).await
+            })
+            .await?;
+        Ok(response.into_inner().deleted)
+    }
+
+    pub async fn rename_tag(
+        &self,
+        old_name: String,
+        new_name: String,
+    ) -> Result<u64, BlogClientError> {
+        let request = self.add_auth_header(Request::new(RenameTagRequest { old_name, new_name }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.rename_tag(request).await
+            })
+            .await?;
+        Ok(response.into_inner().renamed)
+    }
+
+    pub async fn like_post(&self, post_id: i64) -> Result<i64, BlogClientError> {
+        let request = self.add_auth_header(Request::new(LikePostRequest { post_id }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.like_post(request).await
+            })
+            .await?;
+        Ok(response.into_inner().likes_count)
+    }
+
+    pub async fn unlike_post(&self, post_id: i64) -> Result<i64, BlogClientError> {
+        let request = self.add_auth_header(Request::new(LikePostRequest { post_id }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.unlike_post(request).await
+            })
+            .await?;
+        Ok(response.into_inner().likes_count)
+    }
+
+    pub async fn add_comment(
+        &self,
+        post_id: i64,
+        content: String,
+    ) -> Result<models::Comment, BlogClientError> {
+        let request = self.add_auth_header(Request::new(AddCommentRequest { post_id, content }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.add_comment(request).await
+            })
+            .await?;
+        Ok(response.into_inner().into())
+    }
+
+    pub async fn list_comments(
+        &self,
+        post_id: i64,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::CommentPage, BlogClientError> {
+        let limit = limit.unwrap_or(10);
+        let offset = offset.unwrap_or(0);
+
+        let request = self.add_auth_header(Request::new(ListCommentsRequest {
+            post_id,
+            // offset передается как есть, page не используется
+            page: 0,
+            page_size: Some(limit as i32),
+            offset: Some(offset),
+        }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.list_comments(request).await
+            })
+            .await?;
+        let response = response.into_inner();
+
+        Ok(models::CommentPage {
+            comments: response
+                .comments
+                .into_iter()
+                .map(models::Comment::from)
+                .collect(),
+            total: response.total_count as i64,
+            limit,
+            offset,
+        })
+    }
+
+    pub async fn dashboard(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::DashboardResponse, BlogClientError> {
+        let limit = limit.unwrap_or(10);
+        let offset = offset.unwrap_or(0);
+
+        let request = self.add_auth_header(Request::new(DashboardRequest {
+            // offset передается как есть, page не используется
+            page: 0,
+            page_size: Some(limit as i32),
+            offset: Some(offset),
+        }));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.dashboard(request).await
+            })
+            .await?;
+        let response = response.into_inner();
+
+        Ok(models::DashboardResponse {
+            posts: response
+                .posts
+                .into_iter()
+                .map(models::DashboardPost::from)
+                .collect(),
+            total: response.total_count as i64,
+            limit,
+            offset,
+        })
+    }
+
     pub async fn list_posts(
         &self,
-        page: i32,
-        page_size: i32,
-    ) -> Result<ListPostsResponse, BlogClientError> {
-        let request = Request::new(ListPostsRequest {
-            page,
-            page_size,
-            author_username: "".to_string(),
-            tag: "".to_string(),
-            published_only: true,
-            search_query: "".to_string(),
-        });
-
-        let response = self.post_client.clone().list_posts(request).await?;
-        Ok(response.into_inner())
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::PostPage, BlogClientError> {
+        let filter = ListPostsFilter {
+            limit,
+            offset,
+            ..ListPostsFilter::default()
+        };
+
+        self.list_posts_filtered(&filter).await
+    }
+
+    pub async fn list_posts_filtered(
+        &self,
+        filter: &ListPostsFilter,
+    ) -> Result<models::PostPage, BlogClientError> {
+        // Курсор доступен только через отдельный RPC
+        if filter.cursor.is_some() {
+            return self.list_posts_cursor(filter).await;
+        }
+
+        let limit = filter.limit.unwrap_or(10);
+        let offset = filter.offset.unwrap_or(0);
+
+        let request = self.add_request_id(Request::new(ListPostsRequest {
+            // offset передается как есть, page не используется
+            page: 0,
+            page_size: Some(limit as i32),
+            offset: Some(offset),
+            author_username: filter.author_username.clone().unwrap_or_default(),
+            tag: filter.tag.clone().unwrap_or_default(),
+            published_only: Some(true),
+            search_query: filter.search.clone().unwrap_or_default(),
+            author_id: filter.author_id.unwrap_or(0),
+            sort: filter
+                .sort
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_default(),
+            include_author: filter.include_author,
+            created_after: rfc3339_or_empty(filter.created_after),
+            created_before: rfc3339_or_empty(filter.created_before),
+            summary: filter.summary,
+        }));
+
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.list_posts(request).await
+            })
+            .await?;
+        let response = response.into_inner();
+
+        Ok(models::PostPage {
+            posts: response.posts.into_iter().map(models::Post::from).collect(),
+            total: response.total_count as i64,
+            limit,
+            offset,
+            // Старый сервер без page_size - поля заполнит fill_page_info
+            page: response.page as i64,
+            page_size: response.page_size as i64,
+            total_pages: response.total_pages as i64,
+            next_cursor: Some(response.next_cursor).filter(|c| !c.is_empty()),
+        }
+        .fill_page_info())
+    }
+
+    pub async fn stream_posts(
+        &self,
+    ) -> Result<BoxStream<'static, Result<models::Post, BlogClientError>>, BlogClientError> {
+        let request = self.add_request_id(Request::new(StreamPostsRequest {}));
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.stream_posts(request).await
+            })
+            .await?;
+        Ok(response
+            .into_inner()
+            .map_ok(models::Post::from)
+            .map_err(BlogClientError::from)
+            .boxed())
+    }
+
+    pub async fn list_posts_cursor(
+        &self,
+        filter: &ListPostsFilter,
+    ) -> Result<models::PostPage, BlogClientError> {
+        let limit = filter.limit.unwrap_or(10);
+
+        let request = self.add_request_id(Request::new(ListPostsCursorRequest {
+            cursor: filter.cursor.clone().unwrap_or_default(),
+            limit: Some(limit as i32),
+            author_username: filter.author_username.clone().unwrap_or_default(),
+            tag: filter.tag.clone().unwrap_or_default(),
+            published_only: Some(true),
+            search_query: filter.search.clone().unwrap_or_default(),
+            author_id: filter.author_id.unwrap_or(0),
+            sort: filter
+                .sort
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_default(),
+            include_author: filter.include_author,
+            created_after: rfc3339_or_empty(filter.created_after),
+            created_before: rfc3339_or_empty(filter.created_before),
+            summary: filter.summary,
+        }));
+
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.list_posts_cursor(request).await
+            })
+            .await?;
+        let response = response.into_inner();
+
+        Ok(models::PostPage {
+            posts: response.posts.into_iter().map(models::Post::from).collect(),
+            total: response.total_count as i64,
+            limit,
+            offset: 0,
+            page: 0,
+            page_size: 0,
+            total_pages: 0,
+            next_cursor: Some(response.next_cursor).filter(|c| !c.is_empty()),
+        }
+        .fill_page_info())
+    }
+
+    pub async fn count_posts(&self, filter: &ListPostsFilter) -> Result<i64, BlogClientError> {
+        let request = self.add_request_id(Request::new(CountPostsRequest {
+            author_username: filter.author_username.clone().unwrap_or_default(),
+            tag: filter.tag.clone().unwrap_or_default(),
+            published_only: Some(true),
+            search_query: filter.search.clone().unwrap_or_default(),
+            author_id: filter.author_id.unwrap_or(0),
+            created_after: rfc3339_or_empty(filter.created_after),
+            created_before: rfc3339_or_empty(filter.created_before),
+        }));
+
+        let response = self
+            .call(request, |mut services, request| async move {
+                services.post.count_posts(request).await
+            })
+            .await?;
+        Ok(response.into_inner().count)
+    }
+}
+
+// Ошибка соединения (а не ответ сервера): в цепочке причин статуса есть transport::Error
+fn is_transport_failure(status: &Status) -> bool {
+    let mut source = status.source();
+    while let Some(error) = source {
+        if error.is::<tonic::transport::Error>() {
+            return true;
+        }
+        source = error.source();
+    }
+    false
+}
+
+// Граница диапазона дат для proto: пустая строка означает "без ограничения"
+fn rfc3339_or_empty(value: Option<DateTime<Utc>>) -> String {
+    value
+        .map(|dt| dt.to_rfc3339_opts(SecondsFormat::Micros, true))
+        .unwrap_or_default()
+}
+
+fn batch_result(
+    posts: Vec<Post>,
+    results: Vec<crate::proto::BatchItemResult>,
+) -> models::BatchResult {
+    models::BatchResult {
+        posts: posts.into_iter().map(models::Post::from).collect(),
+        results: results
+            .into_iter()
+            .map(models::BatchItemResult::from)
+            .collect(),
+    }
+}
+
+// author_id сервер берет из токена
+fn create_request_to_proto(post: models::CreatePostRequest) -> CreatePostRequest {
+    CreatePostRequest {
+        title: post.title,
+        content: post.content,
+        author_id: 0,
+        tags: post.tags,
+        published: Some(post.published),
+        content_format: post.content_format.as_str().to_string(),
+        published_at: String::new(),
     }
 }
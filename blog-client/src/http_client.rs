@@ -1,63 +1,103 @@
-use crate::error::BlogClientError;
-use reqwest::{Client, RequestBuilder, StatusCode};
+use crate::config::BlogClientConfig;
+use crate::error::{parse_retry_after, BlogClientError, REQUEST_ID_METADATA};
+use crate::models::{
+    AuthResult, BatchItemResult, BatchResult, Comment, CommentPage, Conditional, CreatePostRequest,
+    DashboardResponse, ListPostsFilter, LoginRequest, Post, PostPage, RegisterRequest,
+    TokenValidation, UpdatePostRequest, UpdateProfileRequest, User,
+};
+use chrono::SecondsFormat;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::header::{ETAG, IF_NONE_MATCH, RETRY_AFTER, WWW_AUTHENTICATE};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Response shape version requested from the server via Accept-Version
+pub const API_VERSION: &str = "v2";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UserResponse {
-    pub id: i64,
-    pub username: String,
-    pub email: String,
-    pub created_at: String,
+pub struct BatchPostsRequest {
+    pub ids: Vec<i64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub best_effort: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AuthResponse {
-    pub token: String,
-    pub user: UserResponse,
+pub struct BatchCreatePostsRequest {
+    pub posts: Vec<CreatePostRequest>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub best_effort: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PostResponse {
-    pub id: i64,
-    pub title: String,
-    pub content: String,
-    pub author_id: i64,
-    pub created_at: String,
-    pub updated_at: String,
+pub struct BatchPostsResponse {
+    pub posts: Vec<Post>,
+    /// Set only for `best_effort` requests
+    #[serde(default)]
+    pub results: Vec<BatchItemResult>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PostsResponse {
-    pub posts: Vec<PostResponse>,
-    pub total: i64,
-    pub limit: i64,
-    pub offset: i64,
+pub struct BatchDeleteResponse {
+    pub deleted: u64,
+    #[serde(default)]
+    pub results: Vec<BatchItemResult>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct CreatePostRequest {
-    pub title: String,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCommentRequest {
     pub content: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMyPostsResponse {
+    pub deleted: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameTagRequest {
+    pub old_name: String,
+    pub new_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameTagResponse {
+    pub renamed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountPostsResponse {
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityResponse {
+    pub available: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LikeResponse {
+    #[serde(alias = "postId")]
+    pub post_id: i64,
+    #[serde(alias = "likesCount")]
+    pub likes_count: i64,
+}
+
 #[derive(Debug, Serialize)]
-pub struct UpdatePostRequest {
-    pub title: Option<String>,
-    pub content: Option<String>,
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct RegisterRequest {
-    pub username: String,
-    pub email: String,
-    pub password: String,
+pub struct ChangePasswordRequest {
+    pub old_password: String,
+    pub new_password: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct LoginRequest {
-    pub username: String,
-    pub password: String,
+pub struct ValidateTokenRequest {
+    pub token: String,
 }
 
 #[derive(Debug, Clone)]
@@ -65,13 +105,24 @@ pub struct HttpClient {
     client: Client,
     base_url: String,
     token: Option<String>,
+    request_id: Option<String>,
+    /// Last ETag of `get_post_if_modified` per post id
+    etags: Arc<Mutex<HashMap<i64, String>>>,
 }
 
 impl HttpClient {
     pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_config(base_url, &BlogClientConfig::default())
+    }
+
+    pub fn with_config(base_url: impl Into<String>, config: &BlogClientConfig) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert("Accept-Version", HeaderValue::from_static(API_VERSION));
+
         let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .connect_timeout(Duration::from_secs(5))
+            .default_headers(headers)
+            .timeout(config.request_timeout)
+            .connect_timeout(config.connect_timeout)
             .build()
             .unwrap_or_else(|_| Client::new());
 
@@ -79,6 +130,8 @@ impl HttpClient {
             client,
             base_url: base_url.into(),
             token: None,
+            request_id: None,
+            etags: Arc::default(),
         }
     }
 
@@ -90,6 +143,15 @@ impl HttpClient {
         self.token.as_ref()
     }
 
+    pub fn clear_token(&mut self) {
+        self.token = None;
+    }
+
+    /// `X-Request-Id` sent with every following request (`None` lets the server generate one)
+    pub fn set_request_id(&mut self, request_id: Option<String>) {
+        self.request_id = request_id;
+    }
+
     fn add_auth_header(&self, mut request: RequestBuilder) -> RequestBuilder {
         if let Some(token) = &self.token {
             request = request.bearer_auth(token);
@@ -97,6 +159,45 @@ impl HttpClient {
         request
     }
 
+    // Отправка запроса; 429 и 503 превращаются в ошибки с задержкой из Retry-After,
+    // 401 с пометкой об истечении в WWW-Authenticate - в TokenExpired,
+    // 403 и 409 - в Forbidden и Conflict, как и соответствующие gRPC статусы
+    async fn send(&self, mut request: RequestBuilder) -> Result<Response, BlogClientError> {
+        if let Some(request_id) = &self.request_id {
+            request = request.header(REQUEST_ID_METADATA, request_id);
+        }
+        let response = request.send().await?;
+
+        let retry_after = || {
+            response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after)
+        };
+
+        match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => Err(BlogClientError::RateLimited {
+                retry_after: retry_after(),
+            }),
+            StatusCode::SERVICE_UNAVAILABLE => Err(BlogClientError::ServiceUnavailable {
+                retry_after: retry_after(),
+            }),
+            StatusCode::UNAUTHORIZED
+                if response
+                    .headers()
+                    .get(WWW_AUTHENTICATE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.contains("Token expired")) =>
+            {
+                Err(BlogClientError::TokenExpired)
+            }
+            StatusCode::FORBIDDEN => Err(BlogClientError::Forbidden(response.text().await?)),
+            StatusCode::CONFLICT => Err(BlogClientError::Conflict(response.text().await?)),
+            _ => Ok(response),
+        }
+    }
+
     fn url(&self, path: &str) -> String {
         format!(
             "{}/{}",
@@ -105,32 +206,123 @@ impl HttpClient {
         )
     }
 
-    pub async fn register(
-        &mut self,
-        req: RegisterRequest,
-    ) -> Result<AuthResponse, BlogClientError> {
+    pub async fn register(&mut self, req: RegisterRequest) -> Result<AuthResult, BlogClientError> {
         let url = self.url("/api/auth/register");
-        let response = self.client.post(&url).json(&req).send().await?;
+        let response = self.send(self.client.post(&url).json(&req)).await?;
 
         self.handle_auth_response(response).await
     }
 
-    pub async fn login(&mut self, req: LoginRequest) -> Result<AuthResponse, BlogClientError> {
+    pub async fn login(&mut self, req: LoginRequest) -> Result<AuthResult, BlogClientError> {
         let url = self.url("/api/auth/login");
-        let response = self.client.post(&url).json(&req).send().await?;
+        let response = self.send(self.client.post(&url).json(&req)).await?;
 
         self.handle_auth_response(response).await
     }
 
+    /// Exchange a refresh token for a new access token (not stored automatically)
+    pub async fn refresh(&self, refresh_token: String) -> Result<String, BlogClientError> {
+        let url = self.url("/api/auth/refresh");
+        let request = RefreshTokenRequest { refresh_token };
+        let response = self.send(self.client.post(&url).json(&request)).await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<AuthResult>().await?.token),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn health(&self) -> Result<(), BlogClientError> {
+        let url = self.url("/api/health");
+        let response = self.send(self.client.get(&url)).await?;
+        let status = response.status();
+
+        if status.is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(BlogClientError::TransportError(format!(
+                "HTTP {}: {}",
+                status, error_text
+            )))
+        }
+    }
+
+    pub async fn validate_token(&self, token: String) -> Result<TokenValidation, BlogClientError> {
+        let url = self.url("/api/auth/validate");
+        let request = ValidateTokenRequest { token };
+        let response = self.send(self.client.post(&url).json(&request)).await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<TokenValidation>().await?),
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn check_available(
+        &self,
+        username: Option<String>,
+        email: Option<String>,
+    ) -> Result<bool, BlogClientError> {
+        let url = self.url("/api/auth/available");
+        let mut params = vec![];
+
+        if let Some(u) = username {
+            params.push(("username", u));
+        }
+        if let Some(e) = email {
+            params.push(("email", e));
+        }
+
+        let response = self.send(self.client.get(&url).query(&params)).await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => {
+                let availability = response.json::<AvailabilityResponse>().await?;
+                Ok(availability.available)
+            }
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
     async fn handle_auth_response(
         &mut self,
         response: reqwest::Response,
-    ) -> Result<AuthResponse, BlogClientError> {
+    ) -> Result<AuthResult, BlogClientError> {
         let status = response.status();
 
         match status {
             StatusCode::OK | StatusCode::CREATED => {
-                let auth_response = response.json::<AuthResponse>().await?;
+                let auth_response = response.json::<AuthResult>().await?;
                 self.set_token(auth_response.token.clone());
                 Ok(auth_response)
             }
@@ -139,7 +331,39 @@ impl HttpClient {
                 Err(BlogClientError::Unauthorized(error_text))
             }
             StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
-            StatusCode::CONFLICT => {
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn change_password(
+        &self,
+        old_password: String,
+        new_password: String,
+    ) -> Result<(), BlogClientError> {
+        let url = self.url("/api/protected/change-password");
+        let request = ChangePasswordRequest {
+            old_password,
+            new_password,
+        };
+        let response = self
+            .send(self.add_auth_header(self.client.post(&url)).json(&request))
+            .await?;
+
+        let status = response.status();
+
+        match status {
+            StatusCode::NO_CONTENT => Ok(()),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::BAD_REQUEST => {
                 let error_text = response.text().await?;
                 Err(BlogClientError::InvalidRequest(error_text))
             }
@@ -153,42 +377,230 @@ impl HttpClient {
         }
     }
 
+    pub async fn current_user(&self) -> Result<User, BlogClientError> {
+        let url = self.url("/api/protected/me");
+        let response = self
+            .send(self.add_auth_header(self.client.get(&url)))
+            .await?;
+        self.handle_user_response(response).await
+    }
+
+    pub async fn get_user(&self, id: i64) -> Result<User, BlogClientError> {
+        let url = self.url(&format!("/api/users/{}", id));
+        // Токен необязателен: с ним в своем профиле приходит и email
+        let response = self
+            .send(self.add_auth_header(self.client.get(&url)))
+            .await?;
+        self.handle_user_response(response).await
+    }
+
+    pub async fn update_profile(
+        &self,
+        request: &UpdateProfileRequest,
+    ) -> Result<User, BlogClientError> {
+        let url = self.url("/api/protected/me");
+        let response = self
+            .send(self.add_auth_header(self.client.put(&url)).json(request))
+            .await?;
+        self.handle_user_response(response).await
+    }
+
+    async fn handle_user_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<User, BlogClientError> {
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<User>().await?),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
     pub async fn create_post(
         &self,
-        title: String,
-        content: String,
-    ) -> Result<PostResponse, BlogClientError> {
+        request: &CreatePostRequest,
+        idempotency_key: Option<&str>,
+    ) -> Result<Post, BlogClientError> {
         let url = self.url("/api/protected/posts");
-        let request = CreatePostRequest { title, content };
 
+        let mut builder = self.add_auth_header(self.client.post(&url)).json(request);
+        if let Some(key) = idempotency_key {
+            builder = builder.header("Idempotency-Key", key);
+        }
+        let response = self.send(builder).await?;
+
+        self.handle_post_response(response).await
+    }
+
+    pub async fn create_posts(
+        &self,
+        requests: &[CreatePostRequest],
+    ) -> Result<Vec<Post>, BlogClientError> {
+        Ok(self.create_posts_batch(requests, false).await?.posts)
+    }
+
+    pub async fn create_posts_best_effort(
+        &self,
+        requests: &[CreatePostRequest],
+    ) -> Result<BatchResult, BlogClientError> {
+        let response = self.create_posts_batch(requests, true).await?;
+        Ok(BatchResult {
+            posts: response.posts,
+            results: response.results,
+        })
+    }
+
+    async fn create_posts_batch(
+        &self,
+        requests: &[CreatePostRequest],
+        best_effort: bool,
+    ) -> Result<BatchPostsResponse, BlogClientError> {
+        let url = self.url("/api/protected/posts/batch");
+        let request = BatchCreatePostsRequest {
+            posts: requests.to_vec(),
+            best_effort,
+        };
         let response = self
-            .add_auth_header(self.client.post(&url))
-            .json(&request)
-            .send()
+            .send(self.add_auth_header(self.client.post(&url)).json(&request))
             .await?;
+        let status = response.status();
 
-        self.handle_post_response(response).await
+        match status {
+            // 201 - все посты созданы, 200 - частичный результат best_effort
+            StatusCode::CREATED | StatusCode::OK => {
+                Ok(response.json::<BatchPostsResponse>().await?)
+            }
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
     }
 
-    pub async fn get_post(&self, id: i64) -> Result<PostResponse, BlogClientError> {
+    pub async fn get_post(&self, id: i64) -> Result<Post, BlogClientError> {
         let url = self.url(&format!("/api/posts/{}", id));
-        let response = self.client.get(&url).send().await?;
+        // Токен (если есть) позволяет автору получить свой черновик
+        let response = self
+            .send(self.add_auth_header(self.client.get(&url)))
+            .await?;
         self.handle_post_response(response).await
     }
 
+    /// `get_post` with `If-None-Match` set to the ETag of the previous response for this id;
+    /// 304 becomes `Conditional::NotModified`
+    pub async fn get_post_if_modified(
+        &self,
+        id: i64,
+    ) -> Result<Conditional<Post>, BlogClientError> {
+        let url = self.url(&format!("/api/posts/{}", id));
+        let mut request = self.add_auth_header(self.client.get(&url));
+        if let Some(etag) = self.etags().get(&id) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = self.send(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Conditional::NotModified);
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let post = self.handle_post_response(response).await?;
+        match etag {
+            Some(etag) => self.etags().insert(id, etag),
+            None => self.etags().remove(&id),
+        };
+        Ok(Conditional::Modified(post))
+    }
+
+    // Блокировка короткая и не переживает await, поэтому std Mutex
+    fn etags(&self) -> MutexGuard<'_, HashMap<i64, String>> {
+        self.etags.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub async fn get_posts(&self, ids: &[i64]) -> Result<Vec<Post>, BlogClientError> {
+        Ok(self.get_posts_batch(ids, false).await?.posts)
+    }
+
+    pub async fn get_posts_best_effort(&self, ids: &[i64]) -> Result<BatchResult, BlogClientError> {
+        let response = self.get_posts_batch(ids, true).await?;
+        Ok(BatchResult {
+            posts: response.posts,
+            results: response.results,
+        })
+    }
+
+    async fn get_posts_batch(
+        &self,
+        ids: &[i64],
+        best_effort: bool,
+    ) -> Result<BatchPostsResponse, BlogClientError> {
+        let url = self.url("/api/posts/batch");
+        let request = BatchPostsRequest {
+            ids: ids.to_vec(),
+            best_effort,
+        };
+        // Как и в get_post: с токеном автор получает и свои черновики
+        let response = self
+            .send(self.add_auth_header(self.client.post(&url).json(&request)))
+            .await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<BatchPostsResponse>().await?),
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
     pub async fn update_post(
         &self,
         id: i64,
-        title: Option<String>,
-        content: Option<String>,
-    ) -> Result<PostResponse, BlogClientError> {
+        request: &UpdatePostRequest,
+    ) -> Result<Post, BlogClientError> {
         let url = self.url(&format!("/api/protected/posts/{}", id));
-        let request = UpdatePostRequest { title, content };
 
         let response = self
-            .add_auth_header(self.client.put(&url))
-            .json(&request)
-            .send()
+            .send(self.add_auth_header(self.client.put(&url)).json(request))
             .await?;
 
         self.handle_post_response(response).await
@@ -197,8 +609,7 @@ impl HttpClient {
     pub async fn delete_post(&self, id: i64) -> Result<(), BlogClientError> {
         let url = self.url(&format!("/api/protected/posts/{}", id));
         let response = self
-            .add_auth_header(self.client.delete(&url))
-            .send()
+            .send(self.add_auth_header(self.client.delete(&url)))
             .await?;
 
         let status = response.status();
@@ -220,32 +631,337 @@ impl HttpClient {
         }
     }
 
-    pub async fn list_posts(
+    pub async fn delete_posts(&self, ids: &[i64]) -> Result<u64, BlogClientError> {
+        Ok(self.delete_posts_batch(ids, false).await?.deleted)
+    }
+
+    pub async fn delete_posts_best_effort(
+        &self,
+        ids: &[i64],
+    ) -> Result<Vec<BatchItemResult>, BlogClientError> {
+        Ok(self.delete_posts_batch(ids, true).await?.results)
+    }
+
+    async fn delete_posts_batch(
+        &self,
+        ids: &[i64],
+        best_effort: bool,
+    ) -> Result<BatchDeleteResponse, BlogClientError> {
+        let url = self.url("/api/protected/posts/batch/delete");
+        let request = BatchPostsRequest {
+            ids: ids.to_vec(),
+            best_effort,
+        };
+        let response = self
+            .send(self.add_auth_header(self.client.post(&url)).json(&request))
+            .await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<BatchDeleteResponse>().await?),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn delete_my_posts(&self) -> Result<u64, BlogClientError> {
+        let url = self.url("/api/protected/posts");
+        let response = self
+            .send(self.add_auth_header(self.client.delete(&url)))
+            .await?;
+
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<DeleteMyPostsResponse>().await?.deleted),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn delete_user_posts(&self, user_id: i64) -> Result<u64, BlogClientError> {
+        let url = self.url(&format!("/api/admin/users/{}/posts", user_id));
+        let response = self
+            .send(self.add_auth_header(self.client.delete(&url)))
+            .await?;
+
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<DeleteMyPostsResponse>().await?.deleted),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn rename_tag(
+        &self,
+        old_name: String,
+        new_name: String,
+    ) -> Result<u64, BlogClientError> {
+        let url = self.url("/api/admin/tags/rename");
+        let response = self
+            .send(
+                self.add_auth_header(self.client.post(&url))
+                    .json(&RenameTagRequest { old_name, new_name }),
+            )
+            .await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<RenameTagResponse>().await?.renamed),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn dashboard(
         &self,
         limit: Option<i64>,
         offset: Option<i64>,
-    ) -> Result<PostsResponse, BlogClientError> {
-        let mut url = self.url("/api/posts");
+    ) -> Result<DashboardResponse, BlogClientError> {
+        let url = self.url("/api/protected/posts/dashboard");
         let mut params = vec![];
 
         if let Some(l) = limit {
-            params.push(format!("limit={}", l));
+            params.push(("limit", l.to_string()));
         }
         if let Some(o) = offset {
-            params.push(format!("offset={}", o));
+            params.push(("offset", o.to_string()));
         }
 
-        if !params.is_empty() {
-            url = format!("{}?{}", url, params.join("&"));
+        let response = self
+            .send(self.add_auth_header(self.client.get(&url)).query(&params))
+            .await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<DashboardResponse>().await?),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
         }
+    }
 
-        let response = self.client.get(&url).send().await?;
+    pub async fn add_comment(
+        &self,
+        post_id: i64,
+        content: String,
+    ) -> Result<Comment, BlogClientError> {
+        let url = self.url(&format!("/api/protected/posts/{}/comments", post_id));
+        let response = self
+            .send(
+                self.add_auth_header(self.client.post(&url))
+                    .json(&CreateCommentRequest { content }),
+            )
+            .await?;
         let status = response.status();
 
         match status {
-            StatusCode::OK => {
-                let posts_response = response.json::<PostsResponse>().await?;
-                Ok(posts_response)
+            StatusCode::CREATED => Ok(response.json::<Comment>().await?),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    // Токен не обязателен: с ним видны и комментарии собственных черновиков
+    pub async fn list_comments(
+        &self,
+        post_id: i64,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<CommentPage, BlogClientError> {
+        let url = self.url(&format!("/api/posts/{}/comments", post_id));
+        let mut params = vec![];
+
+        if let Some(l) = limit {
+            params.push(("limit", l.to_string()));
+        }
+        if let Some(o) = offset {
+            params.push(("offset", o.to_string()));
+        }
+
+        let response = self
+            .send(self.add_auth_header(self.client.get(&url)).query(&params))
+            .await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<CommentPage>().await?),
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn like_post(&self, id: i64) -> Result<i64, BlogClientError> {
+        let url = self.url(&format!("/api/protected/posts/{}/like", id));
+        let response = self
+            .send(self.add_auth_header(self.client.post(&url)))
+            .await?;
+        self.handle_like_response(response).await
+    }
+
+    pub async fn unlike_post(&self, id: i64) -> Result<i64, BlogClientError> {
+        let url = self.url(&format!("/api/protected/posts/{}/like", id));
+        let response = self
+            .send(self.add_auth_header(self.client.delete(&url)))
+            .await?;
+        self.handle_like_response(response).await
+    }
+
+    pub async fn list_posts(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<PostPage, BlogClientError> {
+        let filter = ListPostsFilter {
+            limit,
+            offset,
+            ..ListPostsFilter::default()
+        };
+
+        self.list_posts_filtered(&filter).await
+    }
+
+    pub async fn list_posts_filtered(
+        &self,
+        filter: &ListPostsFilter,
+    ) -> Result<PostPage, BlogClientError> {
+        let url = self.url("/api/posts");
+        let mut params = vec![];
+
+        if let Some(l) = filter.limit {
+            params.push(("limit", l.to_string()));
+        }
+        if let Some(o) = filter.offset {
+            params.push(("offset", o.to_string()));
+        }
+        if let Some(sort) = filter.sort {
+            params.push(("sort", sort.as_str().to_string()));
+        }
+        if let Some(cursor) = &filter.cursor {
+            params.push(("cursor", cursor.clone()));
+        }
+        if filter.include_author {
+            params.push(("include", "author".to_string()));
+        }
+        if filter.summary {
+            params.push(("fields", "summary".to_string()));
+        }
+        params.extend(filter_params(filter));
+
+        let response = self.send(self.client.get(&url).query(&params)).await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<PostPage>().await?.fill_page_info()),
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
+            }
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
+    pub async fn count_posts(&self, filter: &ListPostsFilter) -> Result<i64, BlogClientError> {
+        let url = self.url("/api/posts/count");
+        let response = self
+            .send(self.client.get(&url).query(&filter_params(filter)))
+            .await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<CountPostsResponse>().await?.count),
+            StatusCode::BAD_REQUEST => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::InvalidRequest(error_text))
             }
             _ => {
                 let error_text = response.text().await?;
@@ -257,29 +973,49 @@ impl HttpClient {
         }
     }
 
+    async fn handle_like_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<i64, BlogClientError> {
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(response.json::<LikeResponse>().await?.likes_count),
+            StatusCode::UNAUTHORIZED => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::Unauthorized(error_text))
+            }
+            StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
+            _ => {
+                let error_text = response.text().await?;
+                Err(BlogClientError::TransportError(format!(
+                    "HTTP {}: {}",
+                    status, error_text
+                )))
+            }
+        }
+    }
+
     async fn handle_post_response(
         &self,
         response: reqwest::Response,
-    ) -> Result<PostResponse, BlogClientError> {
+    ) -> Result<Post, BlogClientError> {
         let status = response.status();
 
         match status {
             StatusCode::OK | StatusCode::CREATED => {
-                let post = response.json::<PostResponse>().await?;
+                let post = response.json::<Post>().await?;
                 Ok(post)
             }
             StatusCode::UNAUTHORIZED => {
                 let error_text = response.text().await?;
                 Err(BlogClientError::Unauthorized(error_text))
             }
-            StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
-            StatusCode::FORBIDDEN => {
+            StatusCode::BAD_REQUEST => {
                 let error_text = response.text().await?;
-                Err(BlogClientError::InvalidRequest(format!(
-                    "Forbidden: {}",
-                    error_text
-                )))
+                Err(BlogClientError::InvalidRequest(error_text))
             }
+            StatusCode::NOT_FOUND => Err(BlogClientError::NotFound),
             _ => {
                 let error_text = response.text().await?;
                 Err(BlogClientError::TransportError(format!(
@@ -290,3 +1026,32 @@ impl HttpClient {
         }
     }
 }
+
+// Query-параметры фильтров, общие для /api/posts и /api/posts/count
+fn filter_params(filter: &ListPostsFilter) -> Vec<(&'static str, String)> {
+    let mut params = vec![];
+
+    if let Some(author_id) = filter.author_id {
+        params.push(("author_id", author_id.to_string()));
+    }
+    if let Some(author) = &filter.author_username {
+        params.push(("author", author.clone()));
+    }
+    if let Some(search) = &filter.search {
+        params.push(("search", search.clone()));
+    }
+    if let Some(tag) = &filter.tag {
+        params.push(("tag", tag.clone()));
+    }
+    if let Some(after) = filter.created_after {
+        params.push(("after", after.to_rfc3339_opts(SecondsFormat::Micros, true)));
+    }
+    if let Some(before) = filter.created_before {
+        params.push((
+            "before",
+            before.to_rfc3339_opts(SecondsFormat::Micros, true),
+        ));
+    }
+
+    params
+}
@@ -1,15 +1,34 @@
+pub mod config;
 pub mod error;
 pub mod grpc_client;
 pub mod http_client;
+pub mod mock_client;
 pub mod models;
+pub mod token_store;
 
 pub mod proto {
     tonic::include_proto!("blog");
 }
 
+use chrono::{DateTime, Utc};
+pub use config::{BlogClientConfig, RetryPolicy};
 use error::BlogClientError;
+use futures::stream::BoxStream;
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use token_store::{StoredTokens, TokenStore};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+
+/// Largest page the server returns in one list request
+const MAX_PAGE_SIZE: i64 = 100;
+
+// Срок действия по expires_in (секунды от текущего момента), иначе по exp из JWT
+fn token_expiry(token: &str, expires_in: Option<i64>) -> Option<DateTime<Utc>> {
+    match expires_in.filter(|secs| *secs > 0) {
+        Some(secs) => Some(Utc::now() + chrono::Duration::seconds(secs)),
+        None => models::TokenClaims::decode_unverified(token)?.expires_at(),
+    }
+}
 
 /// Transport type for the client
 #[derive(Debug, Clone, PartialEq)]
@@ -18,45 +37,97 @@ pub enum Transport {
     Http(String),
     /// gRPC transport with server address (e.g., "http://localhost:50051")
     Grpc(String),
+    /// In-memory server emulation for tests, no network involved
+    Mock,
 }
 
-/// Unified Blog Client that can use either HTTP or gRPC transport
+/// Unified Blog Client that can use HTTP, gRPC or in-memory mock transport
 #[derive(Debug, Clone)]
 pub struct BlogClient {
     transport: Transport,
     http_client: Option<Arc<Mutex<http_client::HttpClient>>>,
     grpc_client: Option<Arc<Mutex<grpc_client::GrpcClient>>>,
-    token: Arc<Mutex<Option<String>>>,
+    mock_client: Option<Arc<Mutex<mock_client::MockClient>>>,
+    // RwLock: параллельные чтения токена не ждут друг друга
+    token: Arc<RwLock<Option<String>>>,
+    /// Срок действия access-токена; меняется вместе с `token`
+    token_expires_at: Arc<RwLock<Option<DateTime<Utc>>>>,
+    refresh_token: Arc<RwLock<Option<String>>>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    retry: RetryPolicy,
 }
 
 impl BlogClient {
     /// Create a new blog client with the specified transport
     pub async fn new(transport: Transport) -> Result<Self, BlogClientError> {
-        match &transport {
+        Self::with_config(transport, BlogClientConfig::default()).await
+    }
+
+    /// Create a new blog client with the specified transport and configuration
+    pub async fn with_config(
+        transport: Transport,
+        config: BlogClientConfig,
+    ) -> Result<Self, BlogClientError> {
+        let client = match &transport {
             Transport::Http(base_url) => {
-                let http_client = http_client::HttpClient::new(base_url.clone());
-                Ok(Self {
+                let http_client = http_client::HttpClient::with_config(base_url.clone(), &config);
+                Self {
                     transport,
                     http_client: Some(Arc::new(Mutex::new(http_client))),
                     grpc_client: None,
-                    token: Arc::new(Mutex::new(None)),
-                })
+                    mock_client: None,
+                    token: Arc::new(RwLock::new(None)),
+                    token_expires_at: Arc::new(RwLock::new(None)),
+                    refresh_token: Arc::new(RwLock::new(None)),
+                    token_store: config.token_store,
+                    retry: config.retry,
+                }
             }
             Transport::Grpc(addr) => {
-                let grpc_client = grpc_client::GrpcClient::new(addr.clone()).await?;
-                Ok(Self {
+                let grpc_client =
+                    grpc_client::GrpcClient::with_config(addr.clone(), &config).await?;
+                Self {
                     transport,
                     http_client: None,
                     grpc_client: Some(Arc::new(Mutex::new(grpc_client))),
-                    token: Arc::new(Mutex::new(None)),
-                })
+                    mock_client: None,
+                    token: Arc::new(RwLock::new(None)),
+                    token_expires_at: Arc::new(RwLock::new(None)),
+                    refresh_token: Arc::new(RwLock::new(None)),
+                    token_store: config.token_store,
+                    retry: config.retry,
+                }
+            }
+            Transport::Mock => Self {
+                transport,
+                http_client: None,
+                grpc_client: None,
+                mock_client: Some(Arc::new(Mutex::new(mock_client::MockClient::new()))),
+                token: Arc::new(RwLock::new(None)),
+                token_expires_at: Arc::new(RwLock::new(None)),
+                refresh_token: Arc::new(RwLock::new(None)),
+                token_store: config.token_store,
+                retry: config.retry,
+            },
+        };
+
+        // Токены, сохраненные в прошлый запуск
+        if let Some(store) = &client.token_store {
+            if let Some(tokens) = store.load().map_err(BlogClientError::TokenStoreError)? {
+                client.set_token(tokens.access_token).await;
+                *client.refresh_token.write().await = tokens.refresh_token;
             }
         }
+
+        Ok(client)
     }
 
-    /// Set the JWT token for authenticated requests
+    /// Set the JWT token for authenticated requests.
+    /// The shared slot stays write-locked until the sub-client has the token too,
+    /// so concurrent readers never see the two out of sync
     pub async fn set_token(&self, token: String) {
-        let mut token_lock = self.token.lock().await;
+        let mut token_lock = self.token.write().await;
+        *self.token_expires_at.write().await = token_expiry(&token, None);
         *token_lock = Some(token.clone());
 
         match &self.transport {
@@ -72,18 +143,239 @@ impl BlogClient {
                     grpc.set_token(token);
                 }
             }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    client.lock().await.set_token(token);
+                }
+            }
+        }
+    }
+
+    /// A copy of the client that sends `token` with every request instead of the
+    /// shared one, e.g. for server-side code acting on behalf of many users.
+    ///
+    /// The copy reuses the connection, retry policy and request id but keeps its own
+    /// token: `set_token`, `login` or `clear_token` on either client don't affect the
+    /// other, and the copy never writes to the token store. It has no refresh token,
+    /// so `refresh()` on it fails
+    pub async fn with_token(&self, token: impl Into<String>) -> BlogClient {
+        let token = token.into();
+
+        let http_client = match &self.http_client {
+            Some(client) => {
+                let mut http = client.lock().await.clone();
+                http.set_token(token.clone());
+                Some(Arc::new(Mutex::new(http)))
+            }
+            None => None,
+        };
+        let grpc_client = match &self.grpc_client {
+            Some(client) => {
+                let mut grpc = client.lock().await.clone();
+                grpc.set_token(token.clone());
+                Some(Arc::new(Mutex::new(grpc)))
+            }
+            None => None,
+        };
+
+        BlogClient {
+            transport: self.transport.clone(),
+            http_client,
+            grpc_client,
+            // Данные mock общие, токен подставляется перед каждым вызовом (lock_mock)
+            mock_client: self.mock_client.clone(),
+            token_expires_at: Arc::new(RwLock::new(token_expiry(&token, None))),
+            token: Arc::new(RwLock::new(Some(token))),
+            refresh_token: Arc::new(RwLock::new(None)),
+            token_store: None,
+            retry: self.retry.clone(),
+        }
+    }
+
+    // Mock хранит сессию вместе с данными и делит их со всеми копиями клиента
+    // (with_token), поэтому перед вызовом получает токен именно этого клиента.
+    // Токен читается до блокировки mock - set_token берет блокировки в обратном порядке
+    async fn lock_mock<'a>(
+        &self,
+        client: &'a Mutex<mock_client::MockClient>,
+    ) -> MutexGuard<'a, mock_client::MockClient> {
+        let token = self.token.read().await.clone();
+        let mut mock = client.lock().await;
+        match token {
+            Some(token) => mock.set_token(token),
+            None => mock.clear_token(),
         }
+        mock
     }
 
     /// Get the current JWT token
     pub async fn get_token(&self) -> Option<String> {
-        self.token.lock().await.clone()
+        self.token.read().await.clone()
     }
 
-    /// Clear the current JWT token (logout)
+    /// When the current access token expires: the `expires_in` reported on
+    /// login, otherwise the `exp` claim of the JWT. `None` without a token
+    /// or when the expiry is unknown
+    pub async fn token_expires_at(&self) -> Option<DateTime<Utc>> {
+        *self.token_expires_at.read().await
+    }
+
+    /// The access token's expiry has passed according to the local clock;
+    /// `false` when there is no token or its expiry is unknown
+    pub async fn is_token_expired(&self) -> bool {
+        self.token_expires_at()
+            .await
+            .is_some_and(|at| at <= Utc::now())
+    }
+
+    /// Claims of the current access token, decoded **without verifying the
+    /// signature** (see [`models::TokenClaims::decode_unverified`]).
+    /// `None` if there is no token or it is not a JWT
+    pub async fn decode_token_unverified(&self) -> Option<models::TokenClaims> {
+        let token = self.token.read().await;
+        models::TokenClaims::decode_unverified(token.as_deref()?)
+    }
+
+    /// Clear the current JWT token (logout), also from the token store
     pub async fn clear_token(&self) {
-        let mut token_lock = self.token.lock().await;
+        let mut token_lock = self.token.write().await;
         *token_lock = None;
+        *self.token_expires_at.write().await = None;
+        *self.refresh_token.write().await = None;
+
+        if let Some(store) = &self.token_store {
+            if let Err(e) = store.clear() {
+                tracing::warn!("Failed to clear saved tokens: {}", e);
+            }
+        }
+
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    client.lock().await.clear_token();
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    client.lock().await.clear_token();
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    client.lock().await.clear_token();
+                }
+            }
+        }
+    }
+
+    /// Send `request_id` as `X-Request-Id` (HTTP) / `x-request-id` (gRPC) with every
+    /// following call, so the call can be found in server logs; `None` lets the
+    /// server generate an id. A failed call reports it via `BlogClientError::request_id`
+    pub async fn set_request_id(&self, request_id: Option<String>) {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    client.lock().await.set_request_id(request_id);
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    client.lock().await.set_request_id(request_id);
+                }
+            }
+            // Серверных логов у mock нет
+            Transport::Mock => {}
+        }
+    }
+
+    /// Log out: notify the server over gRPC or mock (HTTP has no logout endpoint)
+    /// and clear the stored tokens. Tokens are cleared even if the server call fails.
+    pub async fn logout(&self) -> Result<(), BlogClientError> {
+        let result = match (&self.transport, self.get_token().await) {
+            (Transport::Grpc(_), Some(token)) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.logout(token).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            (Transport::Mock, Some(token)) => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.logout(token).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+            _ => Ok(()),
+        };
+
+        self.clear_token().await;
+        result
+    }
+
+    /// Refresh token received on the last login, if any
+    pub async fn get_refresh_token(&self) -> Option<String> {
+        self.refresh_token.read().await.clone()
+    }
+
+    /// Set the refresh token used by [`BlogClient::refresh`] (e.g. restored from disk)
+    pub async fn set_refresh_token(&self, refresh_token: String) {
+        *self.refresh_token.write().await = Some(refresh_token);
+    }
+
+    /// Exchange the stored refresh token for a fresh access token,
+    /// store it for subsequent requests (and in the token store, if any) and return it
+    pub async fn refresh(&self) -> Result<String, BlogClientError> {
+        let refresh_token = self.get_refresh_token().await.ok_or_else(|| {
+            BlogClientError::Unauthorized("No refresh token available, please login".into())
+        })?;
+
+        let token = match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.refresh(refresh_token).await?
+                } else {
+                    return Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ));
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.refresh(refresh_token).await?
+                } else {
+                    return Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ));
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.refresh(refresh_token).await?
+                } else {
+                    return Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ));
+                }
+            }
+        };
+
+        self.set_token(token.clone()).await;
+        self.persist_tokens(StoredTokens {
+            access_token: token.clone(),
+            refresh_token: self.get_refresh_token().await,
+        })
+        .map_err(BlogClientError::TokenStoreError)?;
+        Ok(token)
     }
 
     /// Register a new user
@@ -92,98 +384,53 @@ impl BlogClient {
         username: impl Into<String>,
         email: impl Into<String>,
         password: impl Into<String>,
-    ) -> Result<models::AuthResponse, BlogClientError> {
+    ) -> Result<models::AuthResult, BlogClientError> {
         let username = username.into();
         let email = email.into();
         let password = password.into();
 
         tracing::debug!("Register called for username: {}", username);
 
-        match &self.transport {
+        let response = match &self.transport {
             Transport::Http(_) => {
                 if let Some(client) = &self.http_client {
                     let mut http = client.lock().await;
-                    tracing::debug!("Got HTTP client lock");
-
-                    let req = http_client::RegisterRequest {
-                        username: username.clone(),
-                        email: email.clone(),
+                    http.register(models::RegisterRequest {
+                        username,
+                        email,
                         password,
-                    };
-
-                    tracing::debug!("Sending register request...");
-                    let response = http.register(req).await?;
-                    tracing::debug!("Register response received, setting token...");
-
-                    if let Some(token) = http.get_token() {
-                        tracing::debug!("Setting token in main client");
-                        let token = token.clone();
-                        let token_clone = self.token.clone();
-                        tokio::spawn(async move {
-                            let mut token_lock = token_clone.lock().await;
-                            *token_lock = Some(token);
-                        });
-                    }
-
-                    tracing::debug!("Returning response");
-                    Ok(models::AuthResponse {
-                        token: response.token,
-                        user: models::User {
-                            id: response.user.id,
-                            username: response.user.username,
-                            email: response.user.email,
-                            created_at: response.user.created_at,
-                        },
                     })
+                    .await?
                 } else {
-                    Err(BlogClientError::TransportError(
+                    return Err(BlogClientError::TransportError(
                         "HTTP client not initialized".into(),
-                    ))
+                    ));
                 }
             }
             Transport::Grpc(_) => {
                 if let Some(client) = &self.grpc_client {
                     let mut grpc = client.lock().await;
-                    tracing::debug!("Got gRPC client lock for register");
-
-                    let response = grpc
-                        .register(username.clone(), email.clone(), password)
-                        .await?;
-                    tracing::debug!(
-                        "gRPC register response received, user_id: {}, token: {}",
-                        response.user_id,
-                        if response.token.is_empty() {
-                            "empty"
-                        } else {
-                            "received"
-                        }
-                    );
-
-                    if !response.token.is_empty() {
-                        let token = response.token.clone();
-                        let token_clone = self.token.clone();
-                        tokio::spawn(async move {
-                            let mut token_lock = token_clone.lock().await;
-                            *token_lock = Some(token);
-                        });
-                    }
-
-                    Ok(models::AuthResponse {
-                        token: response.token,
-                        user: models::User {
-                            id: response.user_id,
-                            username,
-                            email,
-                            created_at: chrono::Utc::now().to_rfc3339(),
-                        },
-                    })
+                    grpc.register(username, email, password).await?
                 } else {
-                    Err(BlogClientError::TransportError(
+                    return Err(BlogClientError::TransportError(
                         "gRPC client not initialized".into(),
-                    ))
+                    ));
                 }
             }
-        }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.register(username, email, password).await?
+                } else {
+                    return Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ));
+                }
+            }
+        };
+
+        self.remember_auth(&response).await?;
+        Ok(response)
     }
 
     /// Login with username and password
@@ -191,116 +438,151 @@ impl BlogClient {
         &self,
         username: impl Into<String>,
         password: impl Into<String>,
-    ) -> Result<models::AuthResponse, BlogClientError> {
+    ) -> Result<models::AuthResult, BlogClientError> {
         let username = username.into();
-        let password = password.into();
-
         tracing::debug!("Login called for username: {}", username);
 
-        match &self.transport {
+        self.login_with(models::LoginRequest {
+            username,
+            email: None,
+            password: password.into(),
+        })
+        .await
+    }
+
+    /// Login with email and password
+    pub async fn login_with_email(
+        &self,
+        email: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Result<models::AuthResult, BlogClientError> {
+        let email = email.into();
+        tracing::debug!("Login called for email: {}", email);
+
+        self.login_with(models::LoginRequest {
+            username: String::new(),
+            email: Some(email),
+            password: password.into(),
+        })
+        .await
+    }
+
+    async fn login_with(
+        &self,
+        req: models::LoginRequest,
+    ) -> Result<models::AuthResult, BlogClientError> {
+        let response = match &self.transport {
             Transport::Http(_) => {
                 if let Some(client) = &self.http_client {
                     let mut http = client.lock().await;
-                    tracing::debug!("Got HTTP client lock");
-
-                    let req = http_client::LoginRequest {
-                        username: username.clone(),
-                        password,
-                    };
-
-                    tracing::debug!("Sending login request...");
-                    let response = http.login(req).await?;
-                    tracing::debug!("Login response received, setting token...");
-
-                    if let Some(token) = http.get_token() {
-                        tracing::debug!("Setting token in main client");
-                        let token = token.clone();
-                        let token_clone = self.token.clone();
-                        tokio::spawn(async move {
-                            let mut token_lock = token_clone.lock().await;
-                            *token_lock = Some(token);
-                        });
-                    }
-
-                    tracing::debug!("Returning response");
-                    Ok(models::AuthResponse {
-                        token: response.token,
-                        user: models::User {
-                            id: response.user.id,
-                            username: response.user.username,
-                            email: response.user.email,
-                            created_at: response.user.created_at,
-                        },
-                    })
+                    http.login(req).await?
                 } else {
-                    Err(BlogClientError::TransportError(
+                    return Err(BlogClientError::TransportError(
                         "HTTP client not initialized".into(),
-                    ))
+                    ));
                 }
             }
             Transport::Grpc(_) => {
                 if let Some(client) = &self.grpc_client {
                     let mut grpc = client.lock().await;
-                    tracing::debug!("Got gRPC client lock for login");
+                    grpc.login(req).await?
+                } else {
+                    return Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ));
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.login(req).await?
+                } else {
+                    return Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ));
+                }
+            }
+        };
 
-                    let response = grpc.login(username.clone(), password).await?;
-                    tracing::debug!("gRPC login response received, token received");
+        self.remember_auth(&response).await?;
+        Ok(response)
+    }
+
+    // Сохраняем токены, выданные при регистрации или логине, для следующих запросов
+    async fn remember_auth(&self, response: &models::AuthResult) -> Result<(), BlogClientError> {
+        // Оба токена меняются в одной критической секции
+        let mut token_lock = self.token.write().await;
+        let mut refresh_lock = self.refresh_token.write().await;
+        *self.token_expires_at.write().await = Some(response.token.as_str())
+            .filter(|t| !t.is_empty())
+            .and_then(|t| token_expiry(t, response.expires_in));
+        *token_lock = Some(response.token.clone()).filter(|t| !t.is_empty());
+        *refresh_lock = response.refresh_token.clone();
+        drop((token_lock, refresh_lock));
+        if response.token.is_empty() {
+            return Ok(());
+        }
+        self.persist_tokens(StoredTokens {
+            access_token: response.token.clone(),
+            refresh_token: response.refresh_token.clone(),
+        })
+        .map_err(BlogClientError::TokenStoreError)
+    }
 
-                    if !response.token.is_empty() {
-                        let token = response.token.clone();
-                        let token_clone = self.token.clone();
-                        tokio::spawn(async move {
-                            let mut token_lock = token_clone.lock().await;
-                            *token_lock = Some(token);
-                        });
-                    }
+    fn persist_tokens(&self, tokens: StoredTokens) -> std::io::Result<()> {
+        match &self.token_store {
+            Some(store) => store.save(&tokens),
+            None => Ok(()),
+        }
+    }
 
-                    if let Some(user) = response.user {
-                        Ok(models::AuthResponse {
-                            token: response.token,
-                            user: models::User {
-                                id: user.id,
-                                username,
-                                email: user.email,
-                                created_at: user.created_at,
-                            },
-                        })
-                    } else {
-                        Err(BlogClientError::InvalidRequest(
-                            "No user data in response".into(),
-                        ))
-                    }
+    /// Check that the server is reachable and answering requests.
+    /// Uses `/api/health` over HTTP and a cheap `ValidateToken` round-trip over gRPC.
+    pub async fn ping(&self) -> Result<(), BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.health().await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.validate_token(String::new()).await.map(|_| ())
                 } else {
                     Err(BlogClientError::TransportError(
                         "gRPC client not initialized".into(),
                     ))
                 }
             }
+            Transport::Mock => Ok(()),
         }
     }
 
-    /// Create a new post (requires authentication)
-    pub async fn create_post(
+    /// Ask the server whether a token is valid (retried on transient failures)
+    pub async fn validate_token(
         &self,
-        title: impl Into<String>,
-        content: impl Into<String>,
-    ) -> Result<models::Post, BlogClientError> {
-        let title = title.into();
-        let content = content.into();
+        token: impl Into<String>,
+    ) -> Result<models::TokenValidation, BlogClientError> {
+        let token = token.into();
+        self.with_retry(|| self.validate_token_once(token.clone()))
+            .await
+    }
 
+    async fn validate_token_once(
+        &self,
+        token: String,
+    ) -> Result<models::TokenValidation, BlogClientError> {
         match &self.transport {
             Transport::Http(_) => {
                 if let Some(client) = &self.http_client {
                     let http = client.lock().await;
-                    let response = http.create_post(title, content).await?;
-                    Ok(models::Post {
-                        id: response.id,
-                        title: response.title,
-                        content: response.content,
-                        author_id: response.author_id,
-                        created_at: response.created_at,
-                        updated_at: response.updated_at,
-                    })
+                    http.validate_token(token).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "HTTP client not initialized".into(),
@@ -310,32 +592,46 @@ impl BlogClient {
             Transport::Grpc(_) => {
                 if let Some(client) = &self.grpc_client {
                     let grpc = client.lock().await;
-                    let post = grpc.create_post(title, content).await?;
-                    Ok(models::Post::from(post))
+                    grpc.validate_token(token).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "gRPC client not initialized".into(),
                     ))
                 }
             }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.validate_token(token).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
         }
     }
 
-    /// Get a post by ID
-    pub async fn get_post(&self, id: i64) -> Result<models::Post, BlogClientError> {
+    /// Check whether a username and/or email is still free to register
+    pub async fn check_available(
+        &self,
+        username: Option<String>,
+        email: Option<String>,
+    ) -> Result<bool, BlogClientError> {
+        self.with_retry(|| self.check_available_once(username.clone(), email.clone()))
+            .await
+    }
+
+    async fn check_available_once(
+        &self,
+        username: Option<String>,
+        email: Option<String>,
+    ) -> Result<bool, BlogClientError> {
         match &self.transport {
             Transport::Http(_) => {
                 if let Some(client) = &self.http_client {
                     let http = client.lock().await;
-                    let response = http.get_post(id).await?;
-                    Ok(models::Post {
-                        id: response.id,
-                        title: response.title,
-                        content: response.content,
-                        author_id: response.author_id,
-                        created_at: response.created_at,
-                        updated_at: response.updated_at,
-                    })
+                    http.check_available(username, email).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "HTTP client not initialized".into(),
@@ -345,37 +641,41 @@ impl BlogClient {
             Transport::Grpc(_) => {
                 if let Some(client) = &self.grpc_client {
                     let grpc = client.lock().await;
-                    let post = grpc.get_post(id).await?;
-                    Ok(models::Post::from(post))
+                    grpc.check_availability(username, email).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "gRPC client not initialized".into(),
                     ))
                 }
             }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.check_available(username, email).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
         }
     }
 
-    /// Update a post (requires authentication, must be author)
-    pub async fn update_post(
+    /// Change the password of the authenticated user.
+    /// Fails with `Unauthorized` when `old_password` is wrong.
+    pub async fn change_password(
         &self,
-        id: i64,
-        title: Option<String>,
-        content: Option<String>,
-    ) -> Result<models::Post, BlogClientError> {
+        old_password: impl Into<String>,
+        new_password: impl Into<String>,
+    ) -> Result<(), BlogClientError> {
+        let old_password = old_password.into();
+        let new_password = new_password.into();
+
         match &self.transport {
             Transport::Http(_) => {
                 if let Some(client) = &self.http_client {
                     let http = client.lock().await;
-                    let response = http.update_post(id, title, content).await?;
-                    Ok(models::Post {
-                        id: response.id,
-                        title: response.title,
-                        content: response.content,
-                        author_id: response.author_id,
-                        created_at: response.created_at,
-                        updated_at: response.updated_at,
-                    })
+                    http.change_password(old_password, new_password).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "HTTP client not initialized".into(),
@@ -385,24 +685,37 @@ impl BlogClient {
             Transport::Grpc(_) => {
                 if let Some(client) = &self.grpc_client {
                     let grpc = client.lock().await;
-                    let post = grpc.update_post(id, title, content).await?;
-                    Ok(models::Post::from(post))
+                    grpc.change_password(old_password, new_password).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "gRPC client not initialized".into(),
                     ))
                 }
             }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.change_password(old_password, new_password).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
         }
     }
 
-    /// Delete a post (requires authentication, must be author)
-    pub async fn delete_post(&self, id: i64) -> Result<(), BlogClientError> {
+    /// Profile of the authenticated user (retried on transient failures)
+    pub async fn current_user(&self) -> Result<models::User, BlogClientError> {
+        self.with_retry(|| self.current_user_once()).await
+    }
+
+    async fn current_user_once(&self) -> Result<models::User, BlogClientError> {
         match &self.transport {
             Transport::Http(_) => {
                 if let Some(client) = &self.http_client {
                     let http = client.lock().await;
-                    http.delete_post(id).await
+                    http.current_user().await
                 } else {
                     Err(BlogClientError::TransportError(
                         "HTTP client not initialized".into(),
@@ -412,44 +725,38 @@ impl BlogClient {
             Transport::Grpc(_) => {
                 if let Some(client) = &self.grpc_client {
                     let grpc = client.lock().await;
-                    grpc.delete_post(id).await
+                    grpc.current_user().await
                 } else {
                     Err(BlogClientError::TransportError(
                         "gRPC client not initialized".into(),
                     ))
                 }
             }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.current_user().await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
         }
     }
 
-    /// List posts with pagination
-    pub async fn list_posts(
-        &self,
-        limit: Option<i64>,
-        offset: Option<i64>,
-    ) -> Result<models::PostsResponse, BlogClientError> {
+    /// Public profile of any user, e.g. the author of a post.
+    /// The email is filled only for the authenticated user's own profile
+    pub async fn get_user(&self, id: i64) -> Result<models::User, BlogClientError> {
+        self.with_retry(|| self.get_user_once(id)).await
+    }
+
+    async fn get_user_once(&self, id: i64) -> Result<models::User, BlogClientError> {
         match &self.transport {
             Transport::Http(_) => {
                 if let Some(client) = &self.http_client {
                     let http = client.lock().await;
-                    let response = http.list_posts(limit, offset).await?;
-                    Ok(models::PostsResponse {
-                        posts: response
-                            .posts
-                            .into_iter()
-                            .map(|p| models::Post {
-                                id: p.id,
-                                title: p.title,
-                                content: p.content,
-                                author_id: p.author_id,
-                                created_at: p.created_at,
-                                updated_at: p.updated_at,
-                            })
-                            .collect(),
-                        total: response.total,
-                        limit: response.limit,
-                        offset: response.offset,
-                    })
+                    http.get_user(id).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "HTTP client not initialized".into(),
@@ -459,35 +766,1178 @@ impl BlogClient {
             Transport::Grpc(_) => {
                 if let Some(client) = &self.grpc_client {
                     let grpc = client.lock().await;
-
-                    let page = (offset.unwrap_or(0) / limit.unwrap_or(10)) as i32 + 1;
-                    let page_size = limit.unwrap_or(10) as i32;
-
-                    let response = grpc.list_posts(page, page_size).await?;
-
-                    Ok(models::PostsResponse {
-                        posts: response.posts.into_iter().map(models::Post::from).collect(),
-                        total: response.total_count as i64,
-                        limit: limit.unwrap_or(10),
-                        offset: offset.unwrap_or(0),
-                    })
+                    grpc.get_user(id).await
                 } else {
                     Err(BlogClientError::TransportError(
                         "gRPC client not initialized".into(),
                     ))
                 }
             }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.get_user(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
         }
     }
 
-    /// Check if the client is using HTTP transport
-    pub fn is_http(&self) -> bool {
-        matches!(self.transport, Transport::Http(_))
+    /// Update email, full name, bio or avatar of the authenticated user.
+    /// Fields left as `None` are not changed.
+    pub async fn update_profile(
+        &self,
+        changes: models::UpdateProfileRequest,
+    ) -> Result<models::User, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.update_profile(&changes).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.update_profile(changes).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.update_profile(&changes).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
     }
 
-    /// Check if the client is using gRPC transport
-    pub fn is_grpc(&self) -> bool {
-        matches!(self.transport, Transport::Grpc(_))
+    /// Create a new post (requires authentication)
+    pub async fn create_post(
+        &self,
+        title: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Result<models::Post, BlogClientError> {
+        self.create_post_with_tags(title, content, Vec::new()).await
+    }
+
+    /// Create a new post with tags (requires authentication)
+    pub async fn create_post_with_tags(
+        &self,
+        title: impl Into<String>,
+        content: impl Into<String>,
+        tags: Vec<String>,
+    ) -> Result<models::Post, BlogClientError> {
+        self.create_post_with(models::CreatePostRequest {
+            title: title.into(),
+            content: content.into(),
+            tags,
+            content_format: models::ContentFormat::Plain,
+            published: true,
+        })
+        .await
+    }
+
+    /// Create an unpublished draft visible only to its author (requires authentication)
+    pub async fn create_draft(
+        &self,
+        title: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Result<models::Post, BlogClientError> {
+        self.create_post_with(models::CreatePostRequest {
+            title: title.into(),
+            content: content.into(),
+            tags: Vec::new(),
+            content_format: models::ContentFormat::Plain,
+            published: false,
+        })
+        .await
+    }
+
+    /// Create a post from a full request (requires authentication)
+    pub async fn create_post_with(
+        &self,
+        request: models::CreatePostRequest,
+    ) -> Result<models::Post, BlogClientError> {
+        self.create_post_once(request, None).await
+    }
+
+    /// Create several posts in one transaction (requires authentication).
+    ///
+    /// Either all posts are created or none: every request is validated first,
+    /// and a validation error names the offending item (`posts[2]: ...`).
+    /// Posts come back in the order of `requests`; not retried, like `create_post`
+    pub async fn create_posts(
+        &self,
+        requests: Vec<models::CreatePostRequest>,
+    ) -> Result<Vec<models::Post>, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.create_posts(&requests).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.create_posts(requests).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.create_posts(requests).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Create several posts one by one (requires authentication): an invalid item does
+    /// not stop the rest. `BatchResult::results` has an entry for every request, in order;
+    /// unlike `create_posts`, some posts may be created while others are rejected
+    pub async fn create_posts_best_effort(
+        &self,
+        requests: Vec<models::CreatePostRequest>,
+    ) -> Result<models::BatchResult, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.create_posts_best_effort(&requests).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.create_posts_best_effort(requests).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.create_posts_best_effort(requests).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Create a post under a caller-chosen idempotency key (requires authentication).
+    ///
+    /// The server remembers the key, so the request is retried on transient
+    /// failures: a repeat returns the originally created post instead of a duplicate.
+    /// Use a fresh unique key (e.g. a UUID) for every logical post.
+    pub async fn create_post_idempotent(
+        &self,
+        request: models::CreatePostRequest,
+        idempotency_key: impl Into<String>,
+    ) -> Result<models::Post, BlogClientError> {
+        let key = idempotency_key.into();
+        self.with_retry(|| self.create_post_once(request.clone(), Some(&key)))
+            .await
+    }
+
+    async fn create_post_once(
+        &self,
+        request: models::CreatePostRequest,
+        idempotency_key: Option<&str>,
+    ) -> Result<models::Post, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.create_post(&request, idempotency_key).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.create_post(request, idempotency_key).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.create_post(request, idempotency_key).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Get a post by ID (retried on transient failures)
+    pub async fn get_post(&self, id: i64) -> Result<models::Post, BlogClientError> {
+        self.with_retry(|| self.get_post_once(id)).await
+    }
+
+    /// Get a post only if it changed since the previous call for the same id.
+    ///
+    /// Over HTTP the client remembers the ETag of each post and sends it in
+    /// `If-None-Match`; an unchanged post comes back as
+    /// [`models::Conditional::NotModified`] without a body. The first call for an id,
+    /// and every call over gRPC and mock, returns `Modified`
+    pub async fn get_post_if_modified(
+        &self,
+        id: i64,
+    ) -> Result<models::Conditional<models::Post>, BlogClientError> {
+        self.with_retry(|| self.get_post_if_modified_once(id)).await
+    }
+
+    async fn get_post_if_modified_once(
+        &self,
+        id: i64,
+    ) -> Result<models::Conditional<models::Post>, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.get_post_if_modified(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) | Transport::Mock => self
+                .get_post_once(id)
+                .await
+                .map(models::Conditional::Modified),
+        }
+    }
+
+    async fn get_post_once(&self, id: i64) -> Result<models::Post, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.get_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.get_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.get_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Get several posts in one round-trip, in the order of `ids`.
+    /// Missing ids are skipped (retried on transient failures)
+    pub async fn get_posts(&self, ids: Vec<i64>) -> Result<Vec<models::Post>, BlogClientError> {
+        self.with_retry(|| self.get_posts_once(&ids)).await
+    }
+
+    async fn get_posts_once(&self, ids: &[i64]) -> Result<Vec<models::Post>, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.get_posts(ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.get_posts(ids.to_vec()).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.get_posts(ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// `get_posts` that also reports every id: found, or an error for missing and
+    /// invisible posts (`BatchResult::results`, in the order of `ids`)
+    pub async fn get_posts_best_effort(
+        &self,
+        ids: Vec<i64>,
+    ) -> Result<models::BatchResult, BlogClientError> {
+        self.with_retry(|| self.get_posts_best_effort_once(&ids))
+            .await
+    }
+
+    async fn get_posts_best_effort_once(
+        &self,
+        ids: &[i64],
+    ) -> Result<models::BatchResult, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.get_posts_best_effort(ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.get_posts_best_effort(ids.to_vec()).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.get_posts_best_effort(ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Update a post (requires authentication, must be author)
+    pub async fn update_post(
+        &self,
+        id: i64,
+        title: Option<String>,
+        content: Option<String>,
+    ) -> Result<models::Post, BlogClientError> {
+        self.update_post_with(
+            id,
+            models::UpdatePostRequest {
+                title,
+                content,
+                content_format: None,
+                published: None,
+                expected_updated_at: None,
+            },
+        )
+        .await
+    }
+
+    /// Publish a draft (`true`) or move a post back to drafts (`false`)
+    pub async fn set_published(
+        &self,
+        id: i64,
+        published: bool,
+    ) -> Result<models::Post, BlogClientError> {
+        self.update_post_with(
+            id,
+            models::UpdatePostRequest {
+                title: None,
+                content: None,
+                content_format: None,
+                published: Some(published),
+                expected_updated_at: None,
+            },
+        )
+        .await
+    }
+
+    /// Update a post from a full request (requires authentication, must be author)
+    pub async fn update_post_with(
+        &self,
+        id: i64,
+        changes: models::UpdatePostRequest,
+    ) -> Result<models::Post, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.update_post(id, &changes).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.update_post(id, changes).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.update_post(id, changes).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Delete a post (requires authentication, must be author)
+    pub async fn delete_post(&self, id: i64) -> Result<(), BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.delete_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.delete_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.delete_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Delete several own posts (any posts for admins) in one transaction: if any of them
+    /// is missing or foreign, nothing is deleted (requires authentication).
+    /// Returns the number of deleted posts
+    pub async fn delete_posts(&self, ids: Vec<i64>) -> Result<u64, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.delete_posts(&ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.delete_posts(ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.delete_posts(&ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Delete several posts one by one (requires authentication); a missing or foreign
+    /// post gets an error entry, the rest are still deleted. One result per id, in order
+    pub async fn delete_posts_best_effort(
+        &self,
+        ids: Vec<i64>,
+    ) -> Result<Vec<models::BatchItemResult>, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.delete_posts_best_effort(&ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.delete_posts_best_effort(ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.delete_posts_best_effort(&ids).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Delete every post of the authenticated user, drafts included (requires authentication).
+    /// Returns the number of deleted posts
+    pub async fn delete_my_posts(&self) -> Result<u64, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.delete_my_posts().await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.delete_my_posts().await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.delete_my_posts().await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Delete every post of `user_id` (requires an admin account, see `AuthResult::is_admin`).
+    /// Returns the number of deleted posts
+    pub async fn delete_user_posts(&self, user_id: i64) -> Result<u64, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.delete_user_posts(user_id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.delete_user_posts(user_id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.delete_user_posts(user_id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Rename a tag on every post, e.g. to fix a typo (requires an admin account).
+    /// Posts that already have the new tag keep a single copy of it.
+    /// Returns the number of posts that carried the old tag
+    pub async fn rename_tag(
+        &self,
+        old_name: impl Into<String>,
+        new_name: impl Into<String>,
+    ) -> Result<u64, BlogClientError> {
+        let old_name = old_name.into();
+        let new_name = new_name.into();
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.rename_tag(old_name, new_name).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.rename_tag(old_name, new_name).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.rename_tag(old_name, new_name).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Own posts of the authenticated user in every state (drafts included),
+    /// most recently updated first
+    pub async fn dashboard(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::DashboardResponse, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.dashboard(limit, offset).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.dashboard(limit, offset).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.dashboard(limit, offset).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Like a post (requires authentication). Liking twice is a no-op.
+    /// Returns the current number of likes.
+    pub async fn like_post(&self, id: i64) -> Result<i64, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.like_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.like_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.like_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Remove a like from a post (requires authentication).
+    /// Returns the current number of likes.
+    pub async fn unlike_post(&self, id: i64) -> Result<i64, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.unlike_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.unlike_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.unlike_post(id).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Comment on a post (requires authentication). The content must not be blank;
+    /// drafts can be commented only by their author
+    pub async fn add_comment(
+        &self,
+        post_id: i64,
+        content: impl Into<String>,
+    ) -> Result<models::Comment, BlogClientError> {
+        let content = content.into();
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.add_comment(post_id, content).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.add_comment(post_id, content).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mut mock = self.lock_mock(client).await;
+                    mock.add_comment(post_id, content).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Comments of a post, oldest first (retried on transient failures).
+    /// Comments of a draft are visible only to its author
+    pub async fn list_comments(
+        &self,
+        post_id: i64,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::CommentPage, BlogClientError> {
+        self.with_retry(|| self.list_comments_once(post_id, limit, offset))
+            .await
+    }
+
+    async fn list_comments_once(
+        &self,
+        post_id: i64,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::CommentPage, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.list_comments(post_id, limit, offset).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.list_comments(post_id, limit, offset).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.list_comments(post_id, limit, offset).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// List posts with pagination (retried on transient failures)
+    pub async fn list_posts(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::PostPage, BlogClientError> {
+        let filter = models::ListPostsFilter {
+            limit,
+            offset,
+            ..models::ListPostsFilter::default()
+        };
+
+        self.list_posts_filtered(filter).await
+    }
+
+    /// List posts with pagination and filters (retried on transient failures)
+    pub async fn list_posts_filtered(
+        &self,
+        filter: models::ListPostsFilter,
+    ) -> Result<models::PostPage, BlogClientError> {
+        self.with_retry(|| self.list_posts_once(&filter)).await
+    }
+
+    /// Number of posts matching the filters of `filter` (pagination, sort and
+    /// include options are ignored), without fetching the posts themselves
+    pub async fn count_posts(
+        &self,
+        filter: &models::ListPostsFilter,
+    ) -> Result<i64, BlogClientError> {
+        self.with_retry(|| self.count_posts_once(filter)).await
+    }
+
+    /// List posts written by the given author
+    pub async fn list_posts_by_author(
+        &self,
+        author_id: i64,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::PostPage, BlogClientError> {
+        let filter = models::ListPostsFilter {
+            limit,
+            offset,
+            author_id: Some(author_id),
+            ..models::ListPostsFilter::default()
+        };
+
+        self.list_posts_filtered(filter).await
+    }
+
+    /// Fetch every post, paging through [`BlogClient::list_posts`] with
+    /// `page_size` posts per request (clamped to the 1..=100 the server accepts).
+    ///
+    /// The number of requests is capped by the `total` of the first page (plus one
+    /// page for posts created meanwhile), so an inconsistent `total` cannot loop forever
+    pub async fn list_all_posts(
+        &self,
+        page_size: i64,
+    ) -> Result<Vec<models::Post>, BlogClientError> {
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        let mut posts = Vec::new();
+        let mut offset = 0;
+        let mut max_pages = 1;
+        let mut fetched = 0;
+
+        loop {
+            let page = self.list_posts(Some(page_size), Some(offset)).await?;
+            if fetched == 0 {
+                max_pages = page.total_pages() + 1;
+            }
+            fetched += 1;
+
+            let done = page.posts.is_empty() || !page.has_next();
+            // Сервер может урезать page_size, поэтому сдвигаемся на число полученных постов
+            offset = page.offset + page.posts.len() as i64;
+            posts.extend(page.posts);
+
+            if done {
+                break;
+            }
+            if fetched >= max_pages {
+                tracing::warn!(
+                    "Stopped listing posts after {} pages: total keeps growing",
+                    fetched
+                );
+                break;
+            }
+        }
+
+        Ok(posts)
+    }
+
+    async fn list_posts_once(
+        &self,
+        filter: &models::ListPostsFilter,
+    ) -> Result<models::PostPage, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.list_posts_filtered(filter).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.list_posts_filtered(filter).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.list_posts(filter).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    async fn count_posts_once(
+        &self,
+        filter: &models::ListPostsFilter,
+    ) -> Result<i64, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => {
+                if let Some(client) = &self.http_client {
+                    let http = client.lock().await;
+                    http.count_posts(filter).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "HTTP client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.count_posts(filter).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    mock.count_posts(filter).await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Subscribe to newly published posts as they are created (gRPC and mock transports only).
+    ///
+    /// The stream ends when the server closes the connection; drafts are never sent.
+    pub async fn subscribe_posts(
+        &self,
+    ) -> Result<BoxStream<'static, Result<models::Post, BlogClientError>>, BlogClientError> {
+        match &self.transport {
+            Transport::Http(_) => Err(BlogClientError::TransportError(
+                "Post streaming is available only over gRPC".into(),
+            )),
+            Transport::Grpc(_) => {
+                if let Some(client) = &self.grpc_client {
+                    let grpc = client.lock().await;
+                    grpc.stream_posts().await
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "gRPC client not initialized".into(),
+                    ))
+                }
+            }
+            Transport::Mock => {
+                if let Some(client) = &self.mock_client {
+                    let mock = self.lock_mock(client).await;
+                    Ok(mock.subscribe_posts())
+                } else {
+                    Err(BlogClientError::TransportError(
+                        "Mock client not initialized".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Run an idempotent operation, retrying transient failures according to
+    /// the configured [`RetryPolicy`]. A Retry-After sent by the server takes
+    /// precedence over the exponential backoff.
+    async fn with_retry<T, F, Fut>(&self, mut operation: F) -> Result<T, BlogClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, BlogClientError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match operation().await {
+                Err(e) if attempt < self.retry.max_retries && e.is_transient() => {
+                    let delay = self.retry.delay_with_hint(attempt, e.retry_after());
+                    tracing::debug!(
+                        "Transient error (attempt {}): {}, retrying in {:?}",
+                        attempt + 1,
+                        e,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Check if the client is using HTTP transport
+    pub fn is_http(&self) -> bool {
+        matches!(self.transport, Transport::Http(_))
+    }
+
+    /// Check if the client is using gRPC transport
+    pub fn is_grpc(&self) -> bool {
+        matches!(self.transport, Transport::Grpc(_))
+    }
+
+    /// Check if the client is using the in-memory mock transport
+    pub fn is_mock(&self) -> bool {
+        matches!(self.transport, Transport::Mock)
     }
 
     /// Get the current transport URL/address
@@ -495,6 +1945,7 @@ impl BlogClient {
         match &self.transport {
             Transport::Http(url) => url.clone(),
             Transport::Grpc(addr) => addr.clone(),
+            Transport::Mock => "mock".to_string(),
         }
     }
 }
@@ -0,0 +1,972 @@
+//! In-memory transport: lets code built on `BlogClient` be tested
+//! deterministically without a running server.
+
+use crate::error::BlogClientError;
+use crate::models::{self, ListPostsFilter, PostSort};
+use chrono::{SecondsFormat, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tokio::sync::broadcast;
+
+/// Matches the server default for new-post subscribers
+const NEW_POSTS_CAPACITY: usize = 64;
+/// Server defaults for post length limits (in characters)
+const MAX_TITLE_LENGTH: usize = 200;
+const MAX_CONTENT_LENGTH: usize = 100_000;
+/// Server limit for comment length (in characters)
+const MAX_COMMENT_LENGTH: usize = 10_000;
+/// Server default excerpt length of list results (in characters)
+const EXCERPT_LENGTH: usize = 200;
+
+#[derive(Debug)]
+struct MockUser {
+    user: models::User,
+    password: String,
+    /// Отдается только в `current_user`, как и на сервере
+    last_login_at: Option<String>,
+}
+
+/// Emulates the server: users, sessions, posts, likes and comments live in memory
+/// and disappear together with the client
+#[derive(Debug)]
+pub struct MockClient {
+    token: Option<String>,
+    users: Vec<MockUser>,
+    /// access token -> user id
+    sessions: HashMap<String, i64>,
+    /// refresh token -> user id
+    refresh_tokens: HashMap<String, i64>,
+    posts: BTreeMap<i64, models::Post>,
+    likes: HashSet<(i64, i64)>,
+    /// В порядке добавления, то есть от старых к новым
+    comments: Vec<models::Comment>,
+    /// (user id, idempotency key) -> post id
+    idempotency_keys: HashMap<(i64, String), i64>,
+    new_posts: broadcast::Sender<models::Post>,
+    last_user_id: i64,
+    last_post_id: i64,
+    last_comment_id: i64,
+    last_token: u64,
+}
+
+impl Default for MockClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Формат времени как у HTTP-сервера
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+// Имена и email сравниваются без учета регистра, как на сервере
+fn same_ignoring_case(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+fn invalid(message: &str) -> BlogClientError {
+    BlogClientError::InvalidRequest(message.to_string())
+}
+
+// Как на сервере: limit вне 1..=100 и отрицательный offset отклоняются, а не урезаются
+fn bad_pagination(limit: i64, offset: i64) -> Option<BlogClientError> {
+    if !(1..=100).contains(&limit) {
+        Some(invalid("Limit must be between 1 and 100"))
+    } else if offset < 0 {
+        Some(invalid("Offset cannot be negative"))
+    } else {
+        None
+    }
+}
+
+// Ошибка для слишком длинного поля (как на сервере, длина в символах)
+fn too_long(field: &str, value: &str, max: usize) -> Option<BlogClientError> {
+    (value.chars().count() > max).then(|| {
+        invalid(&format!(
+            "{} cannot be longer than {} characters",
+            field, max
+        ))
+    })
+}
+
+// Проверки нового поста, как на сервере
+fn new_post_error(request: &models::CreatePostRequest) -> Option<BlogClientError> {
+    if request.title.trim().is_empty() {
+        return Some(invalid("Title cannot be empty"));
+    }
+    if request.content.trim().is_empty() {
+        return Some(invalid("Content cannot be empty"));
+    }
+    too_long("Title", &request.title, MAX_TITLE_LENGTH)
+        .or_else(|| too_long("Content", &request.content, MAX_CONTENT_LENGTH))
+}
+
+// Текст ошибки элемента пакетного запроса в режиме best_effort, как у сервера
+fn item_error(err: BlogClientError) -> String {
+    match err {
+        BlogClientError::InvalidRequest(message) => format!("Validation error: {}", message),
+        BlogClientError::NotFound => "Post not found".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn item_result(
+    index: usize,
+    id: Option<i64>,
+    result: Result<i64, BlogClientError>,
+) -> models::BatchItemResult {
+    match result {
+        Ok(id) => models::BatchItemResult {
+            index,
+            ok: true,
+            id: Some(id),
+            error: None,
+        },
+        Err(err) => models::BatchItemResult {
+            index,
+            ok: false,
+            id,
+            error: Some(item_error(err)),
+        },
+    }
+}
+
+// Отрывок для списков по тем же правилам, что и на сервере:
+// пробелы схлопываются, обрезка по границе слова
+fn excerpt(content: &str, max_chars: usize) -> String {
+    let mut excerpt = String::new();
+    let mut length = 0;
+
+    for word in content.split_whitespace() {
+        let word_length = word.chars().count();
+        let separator = usize::from(length > 0);
+        if length + separator + word_length > max_chars {
+            if length == 0 {
+                excerpt.extend(word.chars().take(max_chars));
+            }
+            excerpt.push_str("...");
+            return excerpt;
+        }
+        if separator > 0 {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+        length += separator + word_length;
+    }
+
+    excerpt
+}
+
+impl MockClient {
+    pub fn new() -> Self {
+        Self {
+            token: None,
+            users: Vec::new(),
+            sessions: HashMap::new(),
+            refresh_tokens: HashMap::new(),
+            posts: BTreeMap::new(),
+            likes: HashSet::new(),
+            comments: Vec::new(),
+            idempotency_keys: HashMap::new(),
+            new_posts: broadcast::channel(NEW_POSTS_CAPACITY).0,
+            last_user_id: 0,
+            last_post_id: 0,
+            last_comment_id: 0,
+            last_token: 0,
+        }
+    }
+
+    pub fn set_token(&mut self, token: String) {
+        self.token = Some(token);
+    }
+
+    pub fn clear_token(&mut self) {
+        self.token = None;
+    }
+
+    pub fn get_token(&self) -> Option<&String> {
+        self.token.as_ref()
+    }
+
+    fn issue_token(&mut self, prefix: &str) -> String {
+        self.last_token += 1;
+        format!("mock-{}-{}", prefix, self.last_token)
+    }
+
+    // Пользователь текущего токена; без токена защищенные операции недоступны
+    async fn current_user_id(&self) -> Result<i64, BlogClientError> {
+        let token = self
+            .token
+            .as_ref()
+            .ok_or_else(|| BlogClientError::Unauthorized("Missing authorization token".into()))?;
+        self.sessions
+            .get(token)
+            .copied()
+            .ok_or_else(|| BlogClientError::Unauthorized("Invalid token".into()))
+    }
+
+    fn user_mut(&mut self, id: i64) -> Option<&mut MockUser> {
+        self.users.iter_mut().find(|u| u.user.id == id)
+    }
+
+    fn login_as(&mut self, user_id: i64) -> (String, String) {
+        let token = self.issue_token("access");
+        let refresh_token = self.issue_token("refresh");
+        self.sessions.insert(token.clone(), user_id);
+        self.refresh_tokens.insert(refresh_token.clone(), user_id);
+        self.token = Some(token.clone());
+        (token, refresh_token)
+    }
+
+    // Черновики видны только автору
+    async fn visible_post(&self, id: i64) -> Result<models::Post, BlogClientError> {
+        let viewer_id = self.current_user_id().await.ok();
+        self.posts
+            .get(&id)
+            .filter(|p| p.published || viewer_id == Some(p.author_id))
+            .cloned()
+            .map(|p| self.with_likes(p))
+            .ok_or(BlogClientError::NotFound)
+    }
+
+    async fn own_post_mut(&mut self, id: i64) -> Result<&mut models::Post, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        let post = self.posts.get_mut(&id).ok_or(BlogClientError::NotFound)?;
+        if post.author_id != user_id {
+            return Err(BlogClientError::Forbidden(
+                "Only the author can modify this post".into(),
+            ));
+        }
+        Ok(post)
+    }
+
+    fn with_likes(&self, mut post: models::Post) -> models::Post {
+        post.likes_count = self.likes.iter().filter(|(p, _)| *p == post.id).count() as i64;
+        post
+    }
+
+    fn author_summary(&self, author_id: i64) -> Option<models::AuthorSummary> {
+        self.users
+            .iter()
+            .find(|u| u.user.id == author_id)
+            .map(|u| models::AuthorSummary {
+                id: u.user.id,
+                username: u.user.username.clone(),
+                avatar_url: u.user.avatar_url.clone(),
+            })
+    }
+
+    // Auth methods
+    pub async fn register(
+        &mut self,
+        username: String,
+        email: String,
+        password: String,
+    ) -> Result<models::AuthResult, BlogClientError> {
+        if username.trim().is_empty() || email.trim().is_empty() || password.is_empty() {
+            return Err(invalid("Username, email and password are required"));
+        }
+        let email = email.to_lowercase();
+        if self.users.iter().any(|u| {
+            same_ignoring_case(&u.user.username, &username)
+                || same_ignoring_case(&u.user.email, &email)
+        }) {
+            return Err(BlogClientError::Conflict("User already exists".into()));
+        }
+
+        self.last_user_id += 1;
+        let user = models::User {
+            id: self.last_user_id,
+            username,
+            email,
+            full_name: None,
+            bio: None,
+            avatar_url: None,
+            created_at: now(),
+            last_login_at: None,
+        };
+        self.users.push(MockUser {
+            user: user.clone(),
+            password,
+            last_login_at: None,
+        });
+
+        let (token, refresh_token) = self.login_as(user.id);
+        Ok(models::AuthResult {
+            token,
+            refresh_token: Some(refresh_token),
+            is_admin: false,
+            user,
+            // Токены мока не истекают
+            expires_in: None,
+        })
+    }
+
+    pub async fn login(
+        &mut self,
+        req: models::LoginRequest,
+    ) -> Result<models::AuthResult, BlogClientError> {
+        // Как и сервер: email учитывается только при пустом username
+        let email = req.email.unwrap_or_default();
+        let user = self
+            .users
+            .iter()
+            .find(|u| {
+                let login_matches = if req.username.is_empty() {
+                    !email.is_empty() && same_ignoring_case(&u.user.email, &email)
+                } else {
+                    same_ignoring_case(&u.user.username, &req.username)
+                };
+                login_matches && u.password == req.password
+            })
+            .map(|u| u.user.clone())
+            .ok_or_else(|| BlogClientError::Unauthorized("Invalid credentials".into()))?;
+
+        if let Some(u) = self.user_mut(user.id) {
+            u.last_login_at = Some(now());
+        }
+        let (token, refresh_token) = self.login_as(user.id);
+        Ok(models::AuthResult {
+            token,
+            refresh_token: Some(refresh_token),
+            is_admin: false,
+            user,
+            // Токены мока не истекают
+            expires_in: None,
+        })
+    }
+
+    pub async fn logout(&mut self, token: String) -> Result<(), BlogClientError> {
+        self.sessions.remove(&token);
+        Ok(())
+    }
+
+    pub async fn refresh(&mut self, refresh_token: String) -> Result<String, BlogClientError> {
+        let user_id = self
+            .refresh_tokens
+            .get(&refresh_token)
+            .copied()
+            .ok_or_else(|| BlogClientError::Unauthorized("Invalid refresh token".into()))?;
+
+        let token = self.issue_token("access");
+        self.sessions.insert(token.clone(), user_id);
+        Ok(token)
+    }
+
+    pub async fn validate_token(
+        &self,
+        token: String,
+    ) -> Result<models::TokenValidation, BlogClientError> {
+        let user_id = self.sessions.get(&token).copied();
+        Ok(models::TokenValidation {
+            valid: user_id.is_some(),
+            user_id,
+        })
+    }
+
+    pub async fn check_available(
+        &self,
+        username: Option<String>,
+        email: Option<String>,
+    ) -> Result<bool, BlogClientError> {
+        if username.is_none() && email.is_none() {
+            return Err(invalid("Specify username and/or email"));
+        }
+        Ok(!self.users.iter().any(|u| {
+            username
+                .as_deref()
+                .is_some_and(|name| same_ignoring_case(name, &u.user.username))
+                || email
+                    .as_deref()
+                    .is_some_and(|email| same_ignoring_case(email, &u.user.email))
+        }))
+    }
+
+    pub async fn change_password(
+        &mut self,
+        old_password: String,
+        new_password: String,
+    ) -> Result<(), BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        if new_password.is_empty() {
+            return Err(invalid("New password cannot be empty"));
+        }
+
+        let user = self.user_mut(user_id).ok_or(BlogClientError::NotFound)?;
+        if user.password != old_password {
+            return Err(BlogClientError::Unauthorized("Invalid credentials".into()));
+        }
+        user.password = new_password;
+        Ok(())
+    }
+
+    pub async fn current_user(&self) -> Result<models::User, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        self.users
+            .iter()
+            .find(|u| u.user.id == user_id)
+            .map(|u| models::User {
+                last_login_at: u.last_login_at.clone(),
+                ..u.user.clone()
+            })
+            .ok_or(BlogClientError::NotFound)
+    }
+
+    pub async fn get_user(&self, id: i64) -> Result<models::User, BlogClientError> {
+        let viewer_id = self.current_user_id().await.ok();
+        let mut user = self
+            .users
+            .iter()
+            .find(|u| u.user.id == id)
+            .map(|u| u.user.clone())
+            .ok_or(BlogClientError::NotFound)?;
+        if viewer_id != Some(id) {
+            user.email.clear();
+        }
+        Ok(user)
+    }
+
+    pub async fn update_profile(
+        &mut self,
+        changes: &models::UpdateProfileRequest,
+    ) -> Result<models::User, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        let user = &mut self
+            .user_mut(user_id)
+            .ok_or(BlogClientError::NotFound)?
+            .user;
+
+        // Пустая строка очищает необязательное поле
+        let optional = |value: &String| Some(value.clone()).filter(|v| !v.is_empty());
+        if let Some(email) = &changes.email {
+            user.email = email.to_lowercase();
+        }
+        if let Some(full_name) = &changes.full_name {
+            user.full_name = optional(full_name);
+        }
+        if let Some(bio) = &changes.bio {
+            user.bio = optional(bio);
+        }
+        if let Some(avatar_url) = &changes.avatar_url {
+            user.avatar_url = optional(avatar_url);
+        }
+        Ok(user.clone())
+    }
+
+    // Post methods
+    pub async fn create_post(
+        &mut self,
+        request: models::CreatePostRequest,
+        idempotency_key: Option<&str>,
+    ) -> Result<models::Post, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        if let Some(err) = new_post_error(&request) {
+            return Err(err);
+        }
+
+        if let Some(key) = idempotency_key {
+            if let Some(post_id) = self.idempotency_keys.get(&(user_id, key.to_string())) {
+                return self.visible_post(*post_id).await;
+            }
+        }
+
+        let post = self.insert_post(user_id, request);
+        if let Some(key) = idempotency_key {
+            self.idempotency_keys
+                .insert((user_id, key.to_string()), post.id);
+        }
+
+        Ok(post)
+    }
+
+    // Как на сервере: сначала проверяются все посты, ошибка называет индекс,
+    // и при ошибке не создается ни один
+    pub async fn create_posts(
+        &mut self,
+        requests: Vec<models::CreatePostRequest>,
+    ) -> Result<Vec<models::Post>, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        for (index, request) in requests.iter().enumerate() {
+            if let Some(BlogClientError::InvalidRequest(message)) = new_post_error(request) {
+                return Err(invalid(&format!("posts[{}]: {}", index, message)));
+            }
+        }
+
+        Ok(requests
+            .into_iter()
+            .map(|request| self.insert_post(user_id, request))
+            .collect())
+    }
+
+    pub async fn create_posts_best_effort(
+        &mut self,
+        requests: Vec<models::CreatePostRequest>,
+    ) -> Result<models::BatchResult, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        let mut posts = Vec::new();
+        let mut results = Vec::with_capacity(requests.len());
+        for (index, request) in requests.into_iter().enumerate() {
+            let result = match new_post_error(&request) {
+                Some(err) => Err(err),
+                None => {
+                    let post = self.insert_post(user_id, request);
+                    let id = post.id;
+                    posts.push(post);
+                    Ok(id)
+                }
+            };
+            results.push(item_result(index, None, result));
+        }
+        Ok(models::BatchResult { posts, results })
+    }
+
+    fn insert_post(&mut self, user_id: i64, request: models::CreatePostRequest) -> models::Post {
+        let mut tags: Vec<String> = request
+            .tags
+            .iter()
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        tags.sort();
+        tags.dedup();
+
+        self.last_post_id += 1;
+        let created_at = now();
+        let post = models::Post {
+            id: self.last_post_id,
+            title: request.title,
+            content_length: request.content.chars().count() as i64,
+            content: request.content,
+            author_id: user_id,
+            created_at: created_at.clone(),
+            updated_at: created_at.clone(),
+            tags,
+            likes_count: 0,
+            excerpt: None,
+            content_format: request.content_format,
+            published: request.published,
+            published_at: request.published.then_some(created_at),
+            edited: false,
+            permalink: None,
+            author: None,
+        };
+        self.posts.insert(post.id, post.clone());
+        if post.published {
+            // Ошибка означает лишь отсутствие подписчиков
+            let _ = self.new_posts.send(post.clone());
+        }
+
+        post
+    }
+
+    pub async fn get_post(&self, id: i64) -> Result<models::Post, BlogClientError> {
+        self.visible_post(id).await
+    }
+
+    pub async fn get_posts(&self, ids: &[i64]) -> Result<Vec<models::Post>, BlogClientError> {
+        let mut seen = HashSet::new();
+        Ok(ids
+            .iter()
+            .filter(|id| seen.insert(**id))
+            .filter_map(|id| self.posts.get(id))
+            .filter(|p| p.published)
+            .map(|p| self.with_likes(p.clone()))
+            .collect())
+    }
+
+    pub async fn get_posts_best_effort(
+        &self,
+        ids: &[i64],
+    ) -> Result<models::BatchResult, BlogClientError> {
+        let posts = self.get_posts(ids).await?;
+        let results = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let found = posts.iter().any(|p| p.id == *id);
+                let result = if found {
+                    Ok(*id)
+                } else {
+                    Err(BlogClientError::NotFound)
+                };
+                item_result(index, Some(*id), result)
+            })
+            .collect();
+        Ok(models::BatchResult { posts, results })
+    }
+
+    pub async fn update_post(
+        &mut self,
+        id: i64,
+        changes: models::UpdatePostRequest,
+    ) -> Result<models::Post, BlogClientError> {
+        let post = self.own_post_mut(id).await?;
+
+        if changes
+            .expected_updated_at
+            .is_some_and(|expected| expected != post.updated_at)
+        {
+            return Err(BlogClientError::Conflict(
+                "Post was modified since it was loaded".into(),
+            ));
+        }
+
+        if let Some(title) = changes.title {
+            if title.trim().is_empty() {
+                return Err(invalid("Title cannot be empty"));
+            }
+            if let Some(err) = too_long("Title", &title, MAX_TITLE_LENGTH) {
+                return Err(err);
+            }
+            post.title = title;
+        }
+        if let Some(content) = changes.content {
+            if content.trim().is_empty() {
+                return Err(invalid("Content cannot be empty"));
+            }
+            if let Some(err) = too_long("Content", &content, MAX_CONTENT_LENGTH) {
+                return Err(err);
+            }
+            post.content_length = content.chars().count() as i64;
+            post.content = content;
+        }
+        if let Some(format) = changes.content_format {
+            post.content_format = format;
+        }
+        let updated_at = now();
+        if let Some(published) = changes.published {
+            if published && !post.published {
+                post.published_at = Some(updated_at.clone());
+            } else if !published {
+                post.published_at = None;
+            }
+            post.published = published;
+        }
+        post.edited = updated_at != post.created_at;
+        post.updated_at = updated_at;
+
+        let post = post.clone();
+        Ok(self.with_likes(post))
+    }
+
+    pub async fn delete_post(&mut self, id: i64) -> Result<(), BlogClientError> {
+        self.own_post_mut(id).await?;
+        self.posts.remove(&id);
+        self.likes.retain(|(post_id, _)| *post_id != id);
+        self.comments.retain(|c| c.post_id != id);
+        Ok(())
+    }
+
+    // Все или ничего: сначала проверяются все id
+    pub async fn delete_posts(&mut self, ids: &[i64]) -> Result<u64, BlogClientError> {
+        let mut seen = HashSet::new();
+        let ids: Vec<i64> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+        for id in &ids {
+            self.own_post_mut(*id).await?;
+        }
+        for id in &ids {
+            self.delete_post(*id).await?;
+        }
+        Ok(ids.len() as u64)
+    }
+
+    pub async fn delete_posts_best_effort(
+        &mut self,
+        ids: &[i64],
+    ) -> Result<Vec<models::BatchItemResult>, BlogClientError> {
+        self.current_user_id().await?;
+        let mut results = Vec::with_capacity(ids.len());
+        for (index, id) in ids.iter().enumerate() {
+            let result = self.delete_post(*id).await.map(|()| *id);
+            results.push(item_result(index, Some(*id), result));
+        }
+        Ok(results)
+    }
+
+    pub async fn delete_my_posts(&mut self) -> Result<u64, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        let before = self.posts.len();
+        self.posts.retain(|_, p| p.author_id != user_id);
+        let posts = &self.posts;
+        self.likes
+            .retain(|(post_id, _)| posts.contains_key(post_id));
+        self.comments.retain(|c| posts.contains_key(&c.post_id));
+        Ok((before - self.posts.len()) as u64)
+    }
+
+    // В mock нет администраторов: любой вошедший пользователь получает Forbidden
+    pub async fn delete_user_posts(&mut self, _user_id: i64) -> Result<u64, BlogClientError> {
+        self.current_user_id().await?;
+        Err(BlogClientError::Forbidden(
+            "Admin role required".to_string(),
+        ))
+    }
+
+    // Как и удаление чужих постов, доступно только администраторам
+    pub async fn rename_tag(
+        &mut self,
+        _old_name: String,
+        _new_name: String,
+    ) -> Result<u64, BlogClientError> {
+        self.current_user_id().await?;
+        Err(BlogClientError::Forbidden(
+            "Admin role required".to_string(),
+        ))
+    }
+
+    pub async fn dashboard(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::DashboardResponse, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        let limit = limit.unwrap_or(10);
+        let offset = offset.unwrap_or(0);
+        if let Some(err) = bad_pagination(limit, offset) {
+            return Err(err);
+        }
+
+        let mut own: Vec<&models::Post> = self
+            .posts
+            .values()
+            .filter(|p| p.author_id == user_id)
+            .collect();
+        own.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(b.id.cmp(&a.id)));
+
+        Ok(models::DashboardResponse {
+            total: own.len() as i64,
+            posts: own
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .map(|p| models::DashboardPost {
+                    post: self.with_likes(p.clone()),
+                    status: if p.published {
+                        models::PostStatus::Published
+                    } else {
+                        models::PostStatus::Draft
+                    },
+                })
+                .collect(),
+            limit,
+            offset,
+        })
+    }
+
+    pub async fn like_post(&mut self, post_id: i64) -> Result<i64, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        self.visible_post(post_id).await?;
+        self.likes.insert((post_id, user_id));
+        Ok(self.visible_post(post_id).await?.likes_count)
+    }
+
+    pub async fn unlike_post(&mut self, post_id: i64) -> Result<i64, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        self.visible_post(post_id).await?;
+        self.likes.remove(&(post_id, user_id));
+        Ok(self.visible_post(post_id).await?.likes_count)
+    }
+
+    pub async fn add_comment(
+        &mut self,
+        post_id: i64,
+        content: String,
+    ) -> Result<models::Comment, BlogClientError> {
+        let user_id = self.current_user_id().await?;
+        if content.trim().is_empty() {
+            return Err(invalid("Content cannot be empty"));
+        }
+        if let Some(err) = too_long("Content", &content, MAX_COMMENT_LENGTH) {
+            return Err(err);
+        }
+        self.visible_post(post_id).await?;
+
+        self.last_comment_id += 1;
+        let comment = models::Comment {
+            id: self.last_comment_id,
+            post_id,
+            author_id: user_id,
+            content,
+            created_at: now(),
+        };
+        self.comments.push(comment.clone());
+        Ok(comment)
+    }
+
+    pub async fn list_comments(
+        &self,
+        post_id: i64,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<models::CommentPage, BlogClientError> {
+        let limit = limit.unwrap_or(10);
+        let offset = offset.unwrap_or(0);
+        if let Some(err) = bad_pagination(limit, offset) {
+            return Err(err);
+        }
+        self.visible_post(post_id).await?;
+
+        let comments: Vec<&models::Comment> = self
+            .comments
+            .iter()
+            .filter(|c| c.post_id == post_id)
+            .collect();
+
+        Ok(models::CommentPage {
+            total: comments.len() as i64,
+            comments: comments
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .cloned()
+                .collect(),
+            limit,
+            offset,
+        })
+    }
+
+    // Опубликованные посты под фильтрами списка, без сортировки и пагинации
+    async fn matching_posts(
+        &self,
+        filter: &ListPostsFilter,
+    ) -> Result<Vec<&models::Post>, BlogClientError> {
+        if let (Some(after), Some(before)) = (filter.created_after, filter.created_before) {
+            if after > before {
+                return Err(invalid(
+                    "created_after must not be later than created_before",
+                ));
+            }
+        }
+
+        let author_id = match &filter.author_username {
+            Some(username) => Some(
+                self.users
+                    .iter()
+                    .find(|u| same_ignoring_case(&u.user.username, username))
+                    .map_or(0, |u| u.user.id),
+            ),
+            None => filter.author_id,
+        };
+        let search = filter.search.as_deref().map(str::to_lowercase);
+        let tag = filter.tag.as_deref().map(|t| t.trim().to_lowercase());
+
+        Ok(self
+            .posts
+            .values()
+            .filter(|p| p.published)
+            .filter(|p| author_id.is_none_or(|id| p.author_id == id))
+            .filter(|p| {
+                search.as_deref().is_none_or(|s| {
+                    p.title.to_lowercase().contains(s) || p.content.to_lowercase().contains(s)
+                })
+            })
+            .filter(|p| tag.as_ref().is_none_or(|t| p.tags.contains(t)))
+            .filter(|p| {
+                p.created_at_dt().is_ok_and(|created| {
+                    filter.created_after.is_none_or(|after| created >= after)
+                        && filter.created_before.is_none_or(|before| created <= before)
+                })
+            })
+            .collect())
+    }
+
+    pub async fn count_posts(&self, filter: &ListPostsFilter) -> Result<i64, BlogClientError> {
+        Ok(self.matching_posts(filter).await?.len() as i64)
+    }
+
+    pub async fn list_posts(
+        &self,
+        filter: &ListPostsFilter,
+    ) -> Result<models::PostPage, BlogClientError> {
+        let limit = filter.limit.unwrap_or(10);
+        let offset = filter.offset.unwrap_or(0);
+        if let Some(err) = bad_pagination(limit, offset) {
+            return Err(err);
+        }
+
+        let mut posts = self.matching_posts(filter).await?;
+
+        let sort = filter.sort.unwrap_or(PostSort::CreatedDesc);
+        // Посты создаются по порядку, поэтому порядок id совпадает с порядком created_at
+        match sort {
+            PostSort::CreatedDesc => posts.sort_by_key(|p| std::cmp::Reverse(p.id)),
+            PostSort::CreatedAsc => posts.sort_by_key(|p| p.id),
+            PostSort::TitleAsc => posts.sort_by(|a, b| {
+                a.title
+                    .to_lowercase()
+                    .cmp(&b.title.to_lowercase())
+                    .then(a.id.cmp(&b.id))
+            }),
+            PostSort::UpdatedDesc => {
+                posts.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(b.id.cmp(&a.id)))
+            }
+        }
+        let total = posts.len() as i64;
+        let supports_cursor = matches!(sort, PostSort::CreatedDesc | PostSort::CreatedAsc);
+
+        // Курсор мока - id последнего поста предыдущей страницы
+        let start = match &filter.cursor {
+            Some(cursor) => {
+                if !supports_cursor {
+                    return Err(invalid(
+                        "Cursor pagination supports only created_desc and created_asc sort",
+                    ));
+                }
+                let after: i64 = cursor.parse().map_err(|_| invalid("Invalid cursor"))?;
+                posts
+                    .iter()
+                    .position(|p| match sort {
+                        PostSort::CreatedAsc => p.id > after,
+                        _ => p.id < after,
+                    })
+                    .unwrap_or(posts.len())
+            }
+            None => (offset as usize).min(posts.len()),
+        };
+        let end = (start + limit as usize).min(posts.len());
+
+        let next_cursor = posts[start..end]
+            .last()
+            .filter(|_| end < posts.len() && supports_cursor)
+            .map(|p| p.id.to_string());
+
+        Ok(models::PostPage {
+            posts: posts[start..end]
+                .iter()
+                .map(|p| {
+                    let mut post = self.with_likes((*p).clone());
+                    if filter.include_author {
+                        post.author = self.author_summary(post.author_id);
+                    }
+                    post.excerpt = Some(excerpt(&post.content, EXCERPT_LENGTH));
+                    if filter.summary {
+                        post.content.clear();
+                    }
+                    post
+                })
+                .collect(),
+            total,
+            limit,
+            offset: if filter.cursor.is_some() { 0 } else { offset },
+            page: 0,
+            page_size: 0,
+            total_pages: 0,
+            next_cursor,
+        }
+        .fill_page_info())
+    }
+
+    pub fn subscribe_posts(&self) -> BoxStream<'static, Result<models::Post, BlogClientError>> {
+        stream::unfold(self.new_posts.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(post) => return Some((Ok(post), receiver)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+}
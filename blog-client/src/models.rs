@@ -1,3 +1,5 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, ParseError, Utc};
 use serde::{Deserialize, Serialize};
 
 // ==================== Модели пользователей ====================
@@ -6,14 +8,40 @@ use serde::{Deserialize, Serialize};
 pub struct User {
     pub id: i64,
     pub username: String,
+    /// Empty in the public profile of another user
+    #[serde(default)]
     pub email: String,
+    #[serde(default, alias = "fullName")]
+    pub full_name: Option<String>,
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[serde(default, alias = "avatarUrl")]
+    pub avatar_url: Option<String>,
+    #[serde(alias = "createdAt")]
     pub created_at: String,
+    /// Set only in the profile of the logged-in user
+    #[serde(
+        default,
+        alias = "lastLoginAt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_login_at: Option<String>,
 }
 
+/// Outcome of register/login/refresh, identical for every transport
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct AuthResponse {
+pub struct AuthResult {
     pub token: String,
+    /// Long-lived token for `BlogClient::refresh`, returned on login
+    #[serde(default, alias = "refreshToken")]
+    pub refresh_token: Option<String>,
+    /// The account has the admin role (may moderate other users' posts)
+    #[serde(default, alias = "isAdmin")]
+    pub is_admin: bool,
     pub user: User,
+    /// Access token lifetime in seconds, when the transport reports it (gRPC)
+    #[serde(default, alias = "expiresIn", skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,39 +54,422 @@ pub struct RegisterRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginRequest {
     pub username: String,
+    /// Used by the server only when `username` is empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
     pub password: String,
 }
 
+/// Result of a server-side token check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenValidation {
+    pub valid: bool,
+    #[serde(alias = "userId")]
+    pub user_id: Option<i64>,
+}
+
+/// Claims carried in the payload of an access token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenClaims {
+    pub user_id: i64,
+    pub username: String,
+    /// Expiry as a Unix timestamp in seconds
+    pub exp: i64,
+}
+
+impl TokenClaims {
+    /// Read the claims of a JWT **without verifying its signature**.
+    ///
+    /// Only for display and client-side hints (e.g. "expires at ..."):
+    /// anyone can forge such a payload, and only the server decides whether
+    /// the token is valid. `None` if `token` is not a well-formed JWT.
+    pub fn decode_unverified(token: &str) -> Option<Self> {
+        let mut parts = token.split('.');
+        let (Some(_header), Some(payload), Some(_signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+
+        let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+        serde_json::from_slice(&payload).ok()
+    }
+
+    /// `exp` as a UTC timestamp
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.exp, 0)
+    }
+
+    /// The expiry has passed according to the local clock
+    pub fn is_expired(&self) -> bool {
+        self.exp <= Utc::now().timestamp()
+    }
+}
+
 // ==================== Модели постов ====================
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Post {
     pub id: i64,
     pub title: String,
+    /// Empty in lists requested with `ListPostsFilter::summary`
+    #[serde(default)]
     pub content: String,
+    /// Content length in characters as computed by the server
+    #[serde(default, alias = "contentLength")]
+    pub content_length: i64,
+    #[serde(alias = "authorId")]
     pub author_id: i64,
+    #[serde(alias = "createdAt")]
     pub created_at: String,
+    #[serde(alias = "updatedAt")]
     pub updated_at: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, alias = "likesCount")]
+    pub likes_count: i64,
+    /// Beginning of the content cut on a word boundary; set by the server in lists only
+    #[serde(default)]
+    pub excerpt: Option<String>,
+    /// How to render `content`; posts from older servers are plain text
+    #[serde(default, alias = "contentFormat")]
+    pub content_format: ContentFormat,
+    /// `false` for drafts
+    #[serde(default = "default_published")]
+    pub published: bool,
+    #[serde(default, alias = "publishedAt")]
+    pub published_at: Option<String>,
+    /// Changed after creation; `false` from servers that don't report it
+    #[serde(default)]
+    pub edited: bool,
+    /// Canonical URL of the post, present when the server has PUBLIC_BASE_URL configured
+    #[serde(default)]
+    pub permalink: Option<String>,
+    /// Present only when requested via `ListPostsFilter::include_author`
+    #[serde(default)]
+    pub author: Option<AuthorSummary>,
+}
+
+/// Public part of the author profile embedded into a post
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorSummary {
+    pub id: i64,
+    pub username: String,
+    #[serde(default, alias = "avatarUrl")]
+    pub avatar_url: Option<String>,
+}
+
+/// Format of the post content
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    #[default]
+    Plain,
+    Markdown,
+}
+
+impl ContentFormat {
+    /// Value of the `content_format` JSON / gRPC field
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Markdown => "markdown",
+        }
+    }
+
+    fn from_proto(format: &str) -> Self {
+        match format {
+            "markdown" => Self::Markdown,
+            _ => Self::Plain,
+        }
+    }
+}
+
+fn default_published() -> bool {
+    true
+}
+
+// Оба транспорта отдают RFC 3339: HTTP - "...Z", gRPC - "...+00:00"
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, ParseError> {
+    DateTime::parse_from_rfc3339(value).map(|dt| dt.with_timezone(&Utc))
+}
+
+impl Post {
+    /// `created_at` parsed as a UTC timestamp
+    pub fn created_at_dt(&self) -> Result<DateTime<Utc>, ParseError> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// `updated_at` parsed as a UTC timestamp
+    pub fn updated_at_dt(&self) -> Result<DateTime<Utc>, ParseError> {
+        parse_timestamp(&self.updated_at)
+    }
+
+    /// `published_at` parsed as a UTC timestamp; `Ok(None)` for drafts
+    pub fn published_at_dt(&self) -> Result<Option<DateTime<Utc>>, ParseError> {
+        self.published_at
+            .as_deref()
+            .map(parse_timestamp)
+            .transpose()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreatePostRequest {
     pub title: String,
     pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub content_format: ContentFormat,
+    /// `false` saves the post as a draft
+    #[serde(default = "default_published")]
+    pub published: bool,
+}
+
+/// Profile changes; `None` keeps a field, an empty string clears
+/// `full_name`, `bio` or `avatar_url`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateProfileRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdatePostRequest {
     pub title: Option<String>,
     pub content: Option<String>,
+    /// `None` keeps the current format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_format: Option<ContentFormat>,
+    /// Publish (`true`) or move back to drafts (`false`); `None` keeps the state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published: Option<bool>,
+    /// `updated_at` of the loaded post; the update fails with `Conflict`
+    /// if someone changed the post since then
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_updated_at: Option<String>,
+}
+
+/// Result of a conditional request such as [`crate::BlogClient::get_post_if_modified`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conditional<T> {
+    /// Fresh data: first request or the resource changed since the last one
+    Modified(T),
+    /// Unchanged since the last request; the previously received copy is current
+    NotModified,
 }
 
+impl<T> Conditional<T> {
+    pub fn is_not_modified(&self) -> bool {
+        matches!(self, Self::NotModified)
+    }
+}
+
+/// One page of the post list, identical for every transport
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PostsResponse {
+pub struct PostPage {
     pub posts: Vec<Post>,
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
+    /// 1-based page number as reported by the gRPC server; computed from
+    /// `offset` and `limit` by the same rule for HTTP and mock
+    #[serde(default)]
+    pub page: i64,
+    /// Page size applied by the server, equal to `limit`
+    #[serde(default)]
+    pub page_size: i64,
+    /// Number of pages as the server counts them (0 for an empty list);
+    /// see [`PostPage::total_pages`] for a value suited for display
+    #[serde(default)]
+    pub total_pages: i64,
+    /// Pass as `ListPostsFilter::cursor` to fetch the next page; `None` on the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+// Навигация по страницам для offset-пагинации; для курсорной достаточно `next_cursor`.
+// `limit <= 0` считается одной страницей со всеми постами
+impl PostPage {
+    // page/page_size/total_pages по тем же формулам, что и у gRPC-сервера,
+    // если транспорт (HTTP, mock, курсорный список) сообщил только limit/offset/total
+    pub(crate) fn fill_page_info(mut self) -> Self {
+        if self.page_size == 0 && self.limit > 0 {
+            self.page = self.offset.max(0) / self.limit + 1;
+            self.page_size = self.limit;
+            self.total_pages = (self.total + self.limit - 1) / self.limit;
+        }
+        self
+    }
+
+    /// Number of pages needed to show `total` posts; an empty list is one page
+    pub fn total_pages(&self) -> i64 {
+        if self.limit <= 0 {
+            return 1;
+        }
+        ((self.total + self.limit - 1) / self.limit).max(1)
+    }
+
+    /// 1-based number of this page
+    pub fn current_page(&self) -> i64 {
+        if self.limit <= 0 {
+            return 1;
+        }
+        self.offset.max(0) / self.limit + 1
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.limit > 0 && self.offset.max(0) + self.limit < self.total
+    }
+
+    pub fn has_prev(&self) -> bool {
+        self.limit > 0 && self.offset > 0
+    }
+}
+
+/// Pagination and filtering options for `BlogClient::list_posts_filtered`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListPostsFilter {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub author_id: Option<i64>,
+    pub author_username: Option<String>,
+    /// Case-insensitive search in title and content
+    pub search: Option<String>,
+    /// Only posts carrying this tag
+    pub tag: Option<String>,
+    /// Order of the list; `None` keeps the server default (newest first)
+    pub sort: Option<PostSort>,
+    /// `next_cursor` of the previous page; used instead of `offset`.
+    /// Only the created_desc and created_asc sorts support cursors
+    pub cursor: Option<String>,
+    /// Embed the author (id, username, avatar) into every post
+    pub include_author: bool,
+    /// Only posts created at or after this moment
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only posts created at or before this moment
+    pub created_before: Option<DateTime<Utc>>,
+    /// Leave out the full content (it comes back empty), keeping only the excerpt
+    pub summary: bool,
+}
+
+/// Order of the post list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSort {
+    CreatedDesc,
+    CreatedAsc,
+    TitleAsc,
+    UpdatedDesc,
+}
+
+impl PostSort {
+    /// Value of the `sort` query parameter / gRPC field
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedDesc => "created_desc",
+            Self::CreatedAsc => "created_asc",
+            Self::TitleAsc => "title_asc",
+            Self::UpdatedDesc => "updated_desc",
+        }
+    }
+}
+
+/// Publication state of a post on the author's dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    Draft,
+    Published,
+    Scheduled,
+}
+
+impl PostStatus {
+    fn from_proto(status: &str) -> Self {
+        match status {
+            "draft" => Self::Draft,
+            "scheduled" => Self::Scheduled,
+            _ => Self::Published,
+        }
+    }
+}
+
+/// Author's own post together with its publication state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DashboardPost {
+    #[serde(flatten)]
+    pub post: Post,
+    pub status: PostStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardResponse {
+    pub posts: Vec<DashboardPost>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// ==================== Пакетные запросы ====================
+
+/// Outcome of one item of a best-effort batch request, identical for every transport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    /// Position of the item in the request
+    pub index: usize,
+    pub ok: bool,
+    /// Post created, found or deleted for this item; absent for a failed create
+    #[serde(default)]
+    pub id: Option<i64>,
+    /// Server's error message for a failed item
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Posts and per-item results of a best-effort batch create or lookup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchResult {
+    /// Created (or found) posts, in request order
+    pub posts: Vec<Post>,
+    /// One entry per request item
+    pub results: Vec<BatchItemResult>,
+}
+
+impl BatchResult {
+    /// Items that failed, e.g. to show which posts were not created and why
+    pub fn failed(&self) -> impl Iterator<Item = &BatchItemResult> {
+        self.results.iter().filter(|result| !result.ok)
+    }
+}
+
+// ==================== Комментарии ====================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: i64,
+    #[serde(alias = "postId")]
+    pub post_id: i64,
+    #[serde(alias = "authorId")]
+    pub author_id: i64,
+    pub content: String,
+    #[serde(alias = "createdAt")]
+    pub created_at: String,
+}
+
+/// One page of a post's comments, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentPage {
+    pub comments: Vec<Comment>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
 }
 
 // ==================== Общие ошибки ====================
@@ -66,6 +477,9 @@ pub struct PostsResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
     pub error: String,
+    /// Machine-readable error code, e.g. `VALIDATION_ERROR` (absent on older servers)
+    #[serde(default)]
+    pub code: Option<String>,
 }
 
 // ==================== Вспомогательные функции ====================
@@ -76,7 +490,22 @@ impl From<crate::proto::User> for User {
             id: proto_user.id,
             username: proto_user.username,
             email: proto_user.email,
+            full_name: Some(proto_user.full_name).filter(|v| !v.is_empty()),
+            bio: Some(proto_user.bio).filter(|v| !v.is_empty()),
+            avatar_url: Some(proto_user.avatar_url).filter(|v| !v.is_empty()),
             created_at: proto_user.created_at,
+            last_login_at: Some(proto_user.last_login_at).filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+impl From<crate::proto::BatchItemResult> for BatchItemResult {
+    fn from(result: crate::proto::BatchItemResult) -> Self {
+        Self {
+            index: result.index as usize,
+            ok: result.ok,
+            id: result.id,
+            error: result.error,
         }
     }
 }
@@ -87,9 +516,44 @@ impl From<crate::proto::Post> for Post {
             id: proto_post.id,
             title: proto_post.title,
             content: proto_post.content,
+            content_length: proto_post.content_length,
             author_id: proto_post.author_id,
             created_at: proto_post.created_at,
             updated_at: proto_post.updated_at,
+            tags: proto_post.tags,
+            likes_count: proto_post.likes_count as i64,
+            excerpt: Some(proto_post.excerpt).filter(|e| !e.is_empty()),
+            content_format: ContentFormat::from_proto(&proto_post.content_format),
+            published: proto_post.published,
+            published_at: Some(proto_post.published_at).filter(|p| !p.is_empty()),
+            edited: proto_post.edited,
+            permalink: Some(proto_post.permalink).filter(|p| !p.is_empty()),
+            author: proto_post.author.map(|author| AuthorSummary {
+                id: author.id,
+                username: author.username,
+                avatar_url: Some(author.avatar_url).filter(|a| !a.is_empty()),
+            }),
+        }
+    }
+}
+
+impl From<crate::proto::Comment> for Comment {
+    fn from(proto_comment: crate::proto::Comment) -> Self {
+        Self {
+            id: proto_comment.id,
+            post_id: proto_comment.post_id,
+            author_id: proto_comment.author_id,
+            content: proto_comment.content,
+            created_at: proto_comment.created_at,
+        }
+    }
+}
+
+impl From<crate::proto::DashboardPost> for DashboardPost {
+    fn from(entry: crate::proto::DashboardPost) -> Self {
+        Self {
+            post: Post::from(entry.post.unwrap_or_default()),
+            status: PostStatus::from_proto(&entry.status),
         }
     }
 }
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Tokens kept between runs of an application
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredTokens {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
+
+/// Persistent storage for the tokens of [`crate::BlogClient`].
+///
+/// With a store in [`crate::BlogClientConfig`] the client loads saved tokens
+/// on creation, saves them after register/login/refresh and clears them on logout.
+pub trait TokenStore: Debug + Send + Sync {
+    /// Saved tokens; `Ok(None)` if nothing is saved
+    fn load(&self) -> io::Result<Option<StoredTokens>>;
+
+    fn save(&self, tokens: &StoredTokens) -> io::Result<()>;
+
+    /// Remove the saved tokens; returns whether there were any
+    fn clear(&self) -> io::Result<bool>;
+}
+
+/// Tokens in a JSON file (`{"access_token": ..., "refresh_token": ...}`),
+/// readable only by the owner on Unix
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> io::Result<Option<StoredTokens>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let content = content.trim();
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        // Старый формат файла - только access-токен без JSON
+        let tokens = serde_json::from_str(content).unwrap_or_else(|_| StoredTokens {
+            access_token: content.to_string(),
+            refresh_token: None,
+        });
+        Ok(Some(tokens))
+    }
+
+    fn save(&self, tokens: &StoredTokens) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(tokens)?;
+        fs::write(&self.path, json)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&self.path, fs::Permissions::from_mode(0o600))?;
+        }
+
+        Ok(())
+    }
+
+    fn clear(&self) -> io::Result<bool> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use blog_client::{BlogClient, Transport};
+
+// Токен сохраняется до возврата из login/register: следующий защищенный
+// запрос, отправленный сразу же (без sleep), уже авторизован
+#[tokio::test]
+async fn create_post_right_after_register_is_authorized() {
+    let client = BlogClient::new(Transport::Mock).await.unwrap();
+
+    client
+        .register("token_user", "token@example.com", "Zq7!kLm2pWx")
+        .await
+        .unwrap();
+    let post = client.create_post("Title", "Content").await.unwrap();
+
+    assert_eq!(post.title, "Title");
+}
+
+// Несколько рабочих потоков: отложенная запись токена проявилась бы здесь
+#[tokio::test(flavor = "multi_thread")]
+async fn create_post_right_after_login_is_authorized() {
+    let client = BlogClient::new(Transport::Mock).await.unwrap();
+    client
+        .register("token_user", "token@example.com", "Zq7!kLm2pWx")
+        .await
+        .unwrap();
+    client.logout().await.unwrap();
+    assert!(client.get_token().await.is_none());
+
+    for attempt in 0..20 {
+        client.login("token_user", "Zq7!kLm2pWx").await.unwrap();
+        let post = client
+            .create_post(format!("Post {}", attempt), "Content")
+            .await
+            .unwrap();
+        assert_eq!(post.title, format!("Post {}", attempt));
+        client.logout().await.unwrap();
+    }
+}
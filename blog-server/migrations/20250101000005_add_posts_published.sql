@@ -0,0 +1,8 @@
+-- Add publication state to posts (existing posts stay published)
+ALTER TABLE posts
+    ADD COLUMN published BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN published_at TIMESTAMP WITH TIME ZONE;
+
+UPDATE posts SET published_at = created_at WHERE published;
+
+CREATE INDEX idx_posts_published ON posts(published, published_at);
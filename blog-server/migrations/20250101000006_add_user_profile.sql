@@ -0,0 +1,5 @@
+-- Profile fields editable after registration
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS full_name VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS bio TEXT,
+    ADD COLUMN IF NOT EXISTS avatar_url VARCHAR(500);
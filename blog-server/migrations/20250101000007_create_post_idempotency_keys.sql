@@ -0,0 +1,19 @@
+-- Idempotency keys for post creation: a repeated request with the same key returns the original post
+CREATE TABLE IF NOT EXISTS post_idempotency_keys (
+    user_id BIGINT NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+    post_id BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT pk_post_idempotency_keys PRIMARY KEY (user_id, idempotency_key),
+
+    CONSTRAINT fk_post_idempotency_keys_user
+        FOREIGN KEY (user_id)
+        REFERENCES users(id)
+        ON DELETE CASCADE,
+
+    CONSTRAINT fk_post_idempotency_keys_post
+        FOREIGN KEY (post_id)
+        REFERENCES posts(id)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+-- Privilege level: 'admin' may moderate any post
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS role VARCHAR(20) NOT NULL DEFAULT 'user'
+        CHECK (role IN ('user', 'admin'));
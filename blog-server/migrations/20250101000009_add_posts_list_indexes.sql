@@ -0,0 +1,10 @@
+-- Индексы под фактические сортировки списков постов.
+-- ORDER BY created_at DESC, id DESC и keyset-пагинация по той же паре
+CREATE INDEX IF NOT EXISTS idx_posts_created_at_id ON posts(created_at DESC, id DESC);
+
+-- Посты автора, новые первыми; покрывает и поиск по author_id (в т.ч. каскадное удаление)
+CREATE INDEX IF NOT EXISTS idx_posts_author_created_at ON posts(author_id, created_at DESC);
+
+-- Одностолбцовые индексы из create_posts теперь покрыты составными
+DROP INDEX IF EXISTS idx_posts_created_at;
+DROP INDEX IF EXISTS idx_posts_author_id;
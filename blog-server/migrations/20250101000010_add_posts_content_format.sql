@@ -0,0 +1,4 @@
+-- Format of the post content: 'plain' (existing posts) or 'markdown'
+ALTER TABLE posts
+    ADD COLUMN IF NOT EXISTS content_format VARCHAR(20) NOT NULL DEFAULT 'plain'
+        CHECK (content_format IN ('plain', 'markdown'));
@@ -0,0 +1,11 @@
+-- Username and email are unique regardless of case: "Bob" cannot register next to "bob".
+-- Emails are stored lowercased; usernames keep the spelling chosen at registration.
+-- If existing rows differ only by case, this migration fails and they must be merged by hand
+UPDATE users SET email = LOWER(email) WHERE email <> LOWER(email);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_username_lower ON users (LOWER(username));
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email_lower ON users (LOWER(email));
+
+-- Lookups now go through LOWER(...), so the plain indexes are no longer used
+DROP INDEX IF EXISTS idx_users_username;
+DROP INDEX IF EXISTS idx_users_email;
@@ -0,0 +1,3 @@
+-- When each account last logged in successfully (NULL: never since this column was added)
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS last_login_at TIMESTAMPTZ;
@@ -1,14 +1,203 @@
 use crate::data::user_repository::UserRepository;
-use crate::domain::user::{LoginUserRequest, RegisterUserRequest, UserResponse};
+use crate::domain::user::{LoginUserRequest, RegisterUserRequest, UpdateUserRequest, UserResponse};
 use crate::domain::DomainError;
 use crate::infrastructure::jwt::JwtService;
 use argon2::password_hash::{rand_core::OsRng, SaltString};
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version};
 use std::sync::Arc;
 
+/// Default minimum length of a password
+pub const DEFAULT_MIN_PASSWORD_LENGTH: usize = 8;
+const MAX_FULL_NAME_LENGTH: usize = 255;
+const MAX_BIO_LENGTH: usize = 1000;
+const MAX_AVATAR_URL_LENGTH: usize = 500;
+
+// Обрезает пробелы и проверяет длину необязательного поля профиля
+fn normalize_profile_field(
+    value: Option<String>,
+    field: &str,
+    max_length: usize,
+) -> Result<Option<String>, DomainError> {
+    let Some(value) = value.map(|v| v.trim().to_string()) else {
+        return Ok(None);
+    };
+
+    if value.chars().count() > max_length {
+        return Err(DomainError::ValidationError(format!(
+            "{} cannot be longer than {} characters",
+            field, max_length
+        )));
+    }
+
+    Ok(Some(value))
+}
+
+// Самые распространённые пароли из утечек - отклоняем независимо от длины
+const COMMON_PASSWORDS: &[&str] = &[
+    "password",
+    "password1",
+    "password123",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "qwerty123",
+    "qwertyuiop",
+    "11111111",
+    "iloveyou",
+    "letmein1",
+    "welcome1",
+    "admin123",
+    "abc12345",
+];
+
+const MIN_USERNAME_LENGTH: usize = 3;
+const MAX_USERNAME_LENGTH: usize = 32;
+const MAX_EMAIL_LENGTH: usize = 254;
+
+// Имя пользователя: 3-32 символа, латиница, цифры, '_' и '-'
+fn validate_username(username: &str) -> Result<(), DomainError> {
+    let length = username.chars().count();
+    if !(MIN_USERNAME_LENGTH..=MAX_USERNAME_LENGTH).contains(&length) {
+        return Err(DomainError::ValidationError(format!(
+            "Username must be between {} and {} characters long",
+            MIN_USERNAME_LENGTH, MAX_USERNAME_LENGTH
+        )));
+    }
+
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(DomainError::ValidationError(
+            "Username may only contain latin letters, digits, '_' and '-'".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Проверка формата email: local@domain.tld без пробелов, домен из корректных меток
+fn validate_email(email: &str) -> Result<(), DomainError> {
+    let invalid = || DomainError::ValidationError("Invalid email format".to_string());
+
+    if email.len() > MAX_EMAIL_LENGTH || email.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+    if local.is_empty() || local.len() > 64 || domain.contains('@') {
+        return Err(invalid());
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err(invalid());
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return Err(invalid());
+    }
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if !labels.iter().all(valid_label) {
+        return Err(invalid());
+    }
+
+    let tld = labels[labels.len() - 1];
+    if tld.len() < 2 || !tld.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
+
+/// Argon2id work factor of new password hashes. Changing it does not break
+/// existing accounts: every hash carries the parameters it was made with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Config {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Config {
+    // Значения по умолчанию крейта argon2 (рекомендация OWASP: 19 МиБ, 2 прохода)
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl Argon2Config {
+    /// Cheapest parameters Argon2 accepts; for tests and local development only
+    pub fn fast() -> Self {
+        Self {
+            memory_kib: Params::MIN_M_COST,
+            iterations: Params::MIN_T_COST,
+            parallelism: 1,
+        }
+    }
+
+    fn build(self) -> Result<Argon2<'static>, DomainError> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+            .map_err(|e| {
+                DomainError::ValidationError(format!("Invalid Argon2 parameters: {}", e))
+            })?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+fn hash_password(argon2: &Argon2, password: &str) -> Result<String, DomainError> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    match argon2.hash_password(password.as_bytes(), &salt) {
+        Ok(hash) => Ok(hash.to_string()),
+        Err(e) => {
+            tracing::error!("Password hashing failed: {}", e);
+            Err(DomainError::InternalError(format!(
+                "Password hashing failed: {}",
+                e
+            )))
+        }
+    }
+}
+
+// InvalidCredentials, если пароль не совпадает с хешем.
+// Параметры берутся из самого хеша, так что старые хеши проверяются и после смены настроек
+fn verify_password(
+    argon2: &Argon2,
+    password_hash: &str,
+    password: &str,
+) -> Result<(), DomainError> {
+    let parsed_hash = PasswordHash::new(password_hash).map_err(|e| {
+        tracing::error!("Invalid password hash format: {}", e);
+        DomainError::InternalError(format!("Invalid password hash: {}", e))
+    })?;
+
+    argon2
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .map_err(|_| DomainError::InvalidCredentials)
+}
+
+/// Tokens issued on successful login
+pub struct LoginTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
 pub struct AuthService {
     user_repo: Arc<dyn UserRepository + Send + Sync>,
     jwt_service: Arc<JwtService>,
+    min_password_length: usize,
+    argon2: Argon2<'static>,
 }
 
 impl AuthService {
@@ -19,17 +208,66 @@ impl AuthService {
         Self {
             user_repo,
             jwt_service,
+            min_password_length: DEFAULT_MIN_PASSWORD_LENGTH,
+            argon2: Argon2::default(),
+        }
+    }
+
+    /// Override the Argon2 work factor of new password hashes; invalid parameters are rejected
+    pub fn with_argon2(mut self, config: Argon2Config) -> Result<Self, DomainError> {
+        self.argon2 = config.build()?;
+        Ok(self)
+    }
+
+    /// Override the minimum password length (at least 1)
+    pub fn with_min_password_length(mut self, min_length: usize) -> Self {
+        self.min_password_length = min_length.max(1);
+        self
+    }
+
+    /// Reject passwords that are too short or trivially guessable.
+    /// Runs before hashing so a weak credential is never stored.
+    pub fn validate_password(&self, password: &str) -> Result<(), DomainError> {
+        if password.chars().count() < self.min_password_length {
+            return Err(DomainError::ValidationError(format!(
+                "Password must be at least {} characters long",
+                self.min_password_length
+            )));
         }
+
+        let mut chars = password.chars();
+        let first = chars.next();
+        if chars.all(|c| Some(c) == first) {
+            return Err(DomainError::ValidationError(
+                "Password must not consist of a single repeated character".to_string(),
+            ));
+        }
+
+        let lowercase = password.to_lowercase();
+        if COMMON_PASSWORDS.contains(&lowercase.as_str()) {
+            return Err(DomainError::ValidationError(
+                "Password is too common, please choose another one".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 
     pub async fn register(
         &self,
-        req: RegisterUserRequest,
+        mut req: RegisterUserRequest,
     ) -> Result<(String, UserResponse), DomainError> {
         tracing::debug!("=== REGISTRATION START ===");
         tracing::debug!("Username: {}, Email: {}", req.username, req.email);
 
-        // Check if user already exists
+        validate_username(&req.username)?;
+        validate_email(&req.email)?;
+        self.validate_password(&req.password)?;
+
+        // Email хранится в нижнем регистре; имя - как введено, но уникально без учета регистра
+        req.email = req.email.to_lowercase();
+
+        // Check if user already exists (case-insensitive)
         tracing::debug!("Checking if username exists...");
         if let Ok(_user) = self.user_repo.find_by_username(&req.username).await {
             tracing::warn!("Registration failed: username already exists");
@@ -44,22 +282,8 @@ impl AuthService {
 
         // Hash password
         tracing::debug!("Hashing password...");
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
-
-        let password_hash = match argon2.hash_password(req.password.as_bytes(), &salt) {
-            Ok(hash) => {
-                tracing::debug!("Password hashed successfully");
-                hash.to_string()
-            }
-            Err(e) => {
-                tracing::error!("Password hashing failed: {}", e);
-                return Err(DomainError::InternalError(format!(
-                    "Password hashing failed: {}",
-                    e
-                )));
-            }
-        };
+        let password_hash = hash_password(&self.argon2, &req.password)?;
+        tracing::debug!("Password hashed successfully");
 
         // Create user
         tracing::debug!("Creating user in database...");
@@ -80,7 +304,7 @@ impl AuthService {
 
         match self
             .jwt_service
-            .generate_token(user.id, user.username.clone())
+            .generate_token(user.id, user.username.clone(), user.role)
         {
             Ok(token) => {
                 tracing::debug!("JWT token generated successfully");
@@ -103,62 +327,72 @@ impl AuthService {
     pub async fn login(
         &self,
         req: LoginUserRequest,
-    ) -> Result<(String, UserResponse), DomainError> {
+    ) -> Result<(LoginTokens, UserResponse), DomainError> {
         tracing::debug!("=== LOGIN START ===");
-        tracing::debug!("Username: {}", req.username);
+        // Пустой username - вход по email (оба поиска без учета регистра)
+        let (login, found) = if !req.username.is_empty() {
+            tracing::debug!("Username: {}", req.username);
+            let found = self.user_repo.find_by_username(&req.username).await;
+            (req.username.as_str(), found)
+        } else if let Some(email) = req.email.as_deref().filter(|e| !e.is_empty()) {
+            tracing::debug!("Email: {}", email);
+            (email, self.user_repo.find_by_email(email).await)
+        } else {
+            return Err(DomainError::ValidationError(
+                "Username or email is required".to_string(),
+            ));
+        };
 
-        // Find user by username
-        tracing::debug!("Finding user in database...");
-        let user = match self.user_repo.find_by_username(&req.username).await {
+        let user = match found {
             Ok(u) => {
                 tracing::debug!("User found with ID: {}", u.id);
                 u
             }
             Err(e) => {
-                tracing::warn!("User not found: {}", req.username);
+                tracing::warn!("User not found: {}", login);
                 return Err(e);
             }
         };
 
         // Verify password
         tracing::debug!("Verifying password...");
-        let parsed_hash = match PasswordHash::new(&user.password_hash) {
-            Ok(h) => h,
-            Err(e) => {
-                tracing::error!("Invalid password hash format: {}", e);
-                return Err(DomainError::InternalError(format!(
-                    "Invalid password hash: {}",
-                    e
-                )));
-            }
-        };
-
-        let argon2 = Argon2::default();
-        match argon2.verify_password(req.password.as_bytes(), &parsed_hash) {
-            Ok(_) => {
-                tracing::debug!("Password verified successfully");
-            }
-            Err(_) => {
+        if let Err(e) = verify_password(&self.argon2, &user.password_hash, &req.password) {
+            if matches!(e, DomainError::InvalidCredentials) {
                 tracing::warn!("Invalid password for user {}", user.username);
-                return Err(DomainError::InvalidCredentials);
             }
-        };
+            return Err(e);
+        }
+        tracing::debug!("Password verified successfully");
 
         // Generate JWT token
         tracing::debug!("Generating JWT token for user ID: {}", user.id);
 
-        match self
+        let tokens = self
             .jwt_service
-            .generate_token(user.id, user.username.clone())
-        {
-            Ok(token) => {
-                tracing::debug!("JWT token generated successfully");
+            .generate_token(user.id, user.username.clone(), user.role)
+            .and_then(|access_token| {
+                let refresh_token = self
+                    .jwt_service
+                    .generate_refresh_token(user.id, user.username.clone())?;
+                Ok(LoginTokens {
+                    access_token,
+                    refresh_token,
+                })
+            });
+
+        match tokens {
+            Ok(tokens) => {
+                tracing::debug!("JWT tokens generated successfully");
+                // Только для аудита: ошибка записи не должна мешать входу
+                if let Err(e) = self.user_repo.touch_last_login(user.id).await {
+                    tracing::warn!("Failed to record last login for user {}: {:?}", user.id, e);
+                }
                 tracing::info!(
                     "User logged in successfully: id={}, username={}",
                     user.id,
                     user.username
                 );
-                Ok((token, UserResponse::from(user)))
+                Ok((tokens, UserResponse::from(user)))
             }
             Err(e) => {
                 tracing::error!("JWT GENERATION FAILED: {:?}", e);
@@ -167,6 +401,166 @@ impl AuthService {
         }
     }
 
+    /// Exchange a valid refresh token for a fresh access token
+    pub async fn refresh(
+        &self,
+        refresh_token: &str,
+    ) -> Result<(String, UserResponse), DomainError> {
+        let user_id = self
+            .jwt_service
+            .verify_refresh_token(refresh_token)
+            .map_err(|e| {
+                tracing::warn!("Refresh token rejected: {:?}", e);
+                DomainError::Unauthorized("Invalid refresh token".to_string())
+            })?;
+
+        // Пользователь мог быть удален после выдачи refresh-токена
+        let user = match self.user_repo.find_by_id(user_id).await {
+            Ok(user) => user,
+            Err(DomainError::UserNotFound) => {
+                tracing::warn!("Refresh token for nonexistent user_id={}", user_id);
+                return Err(DomainError::Unauthorized(
+                    "Invalid refresh token".to_string(),
+                ));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let token = self
+            .jwt_service
+            .generate_token(user.id, user.username.clone(), user.role)?;
+
+        tracing::info!("Access token refreshed for user_id={}", user.id);
+
+        Ok((token, UserResponse::from(user)))
+    }
+
+    /// Change the password after verifying the current one.
+    /// A wrong current password fails with `InvalidCredentials`.
+    pub async fn change_password(
+        &self,
+        user_id: i64,
+        old_password: &str,
+        new_password: &str,
+    ) -> Result<(), DomainError> {
+        self.validate_password(new_password)?;
+
+        let user = self.user_repo.find_by_id(user_id).await?;
+
+        if let Err(e) = verify_password(&self.argon2, &user.password_hash, old_password) {
+            if matches!(e, DomainError::InvalidCredentials) {
+                tracing::warn!("Wrong current password on change for user_id={}", user_id);
+            }
+            return Err(e);
+        }
+
+        let password_hash = hash_password(&self.argon2, new_password)?;
+        self.user_repo
+            .update_password(user_id, &password_hash)
+            .await?;
+
+        tracing::info!("Password changed: user_id={}", user_id);
+
+        Ok(())
+    }
+
+    /// Profile of the authenticated user
+    pub async fn current_user(&self, user_id: i64) -> Result<UserResponse, DomainError> {
+        let user = self.user_repo.find_by_id(user_id).await?;
+        // Время последнего входа видит только сам пользователь
+        let last_login_at = user.last_login_at;
+        Ok(UserResponse {
+            last_login_at,
+            ..UserResponse::from(user)
+        })
+    }
+
+    /// Public profile of any user; the email is included only when `viewer_id` is that user
+    pub async fn get_user(
+        &self,
+        user_id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<UserResponse, DomainError> {
+        let user = UserResponse::from(self.user_repo.find_by_id(user_id).await?);
+
+        if viewer_id == Some(user_id) {
+            Ok(user)
+        } else {
+            Ok(user.into_public())
+        }
+    }
+
+    /// Update email, full name, bio or avatar of the given user.
+    /// Changing email to one that is already taken fails with `UserAlreadyExists`.
+    pub async fn update_profile(
+        &self,
+        user_id: i64,
+        req: UpdateUserRequest,
+    ) -> Result<UserResponse, DomainError> {
+        let email = req.email.map(|e| e.trim().to_lowercase());
+        if let Some(email) = &email {
+            validate_email(email)?;
+        }
+
+        let avatar_url =
+            normalize_profile_field(req.avatar_url, "Avatar URL", MAX_AVATAR_URL_LENGTH)?;
+        if let Some(url) = avatar_url.as_deref().filter(|u| !u.is_empty()) {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(DomainError::ValidationError(
+                    "Avatar URL must start with http:// or https://".to_string(),
+                ));
+            }
+        }
+
+        let req = UpdateUserRequest {
+            email,
+            full_name: normalize_profile_field(req.full_name, "Full name", MAX_FULL_NAME_LENGTH)?,
+            bio: normalize_profile_field(req.bio, "Bio", MAX_BIO_LENGTH)?,
+            avatar_url,
+        };
+
+        let user = self.user_repo.update_profile(user_id, req).await?;
+
+        tracing::info!("Profile updated: user_id={}", user_id);
+
+        Ok(UserResponse::from(user))
+    }
+
+    /// Report whether the given username and/or email are still free.
+    /// Only a single boolean is returned so callers can't tell which one is taken.
+    pub async fn check_availability(
+        &self,
+        username: Option<&str>,
+        email: Option<&str>,
+    ) -> Result<bool, DomainError> {
+        let username = username.map(str::trim).filter(|u| !u.is_empty());
+        let email = email.map(str::trim).filter(|e| !e.is_empty());
+
+        if username.is_none() && email.is_none() {
+            return Err(DomainError::ValidationError(
+                "Username or email must be provided".to_string(),
+            ));
+        }
+
+        if let Some(username) = username {
+            match self.user_repo.find_by_username(username).await {
+                Ok(_) => return Ok(false),
+                Err(DomainError::UserNotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        if let Some(email) = email {
+            match self.user_repo.find_by_email(email).await {
+                Ok(_) => return Ok(false),
+                Err(DomainError::UserNotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(true)
+    }
+
     #[allow(dead_code)]
     pub async fn validate_token(&self, token: &str) -> Result<i64, DomainError> {
         tracing::debug!("Validating token...");
@@ -176,3 +570,187 @@ impl AuthService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::user_repository::PostgresUserRepository;
+    use sqlx::postgres::PgPoolOptions;
+
+    fn service() -> AuthService {
+        // Проверка пароля не обращается к БД, поэтому хватает ленивого пула
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let jwt = JwtService::new(
+            "test-secret-that-is-at-least-32-chars",
+            chrono::Duration::hours(1),
+        )
+        .unwrap();
+        AuthService::new(Arc::new(PostgresUserRepository::new(pool)), Arc::new(jwt))
+    }
+
+    #[tokio::test]
+    async fn validate_password_rejects_empty_password() {
+        assert!(matches!(
+            service().validate_password(""),
+            Err(DomainError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn validate_password_rejects_too_short_password() {
+        let short = "Zq7!kLm";
+        assert_eq!(short.chars().count(), DEFAULT_MIN_PASSWORD_LENGTH - 1);
+        match service().validate_password(short) {
+            Err(DomainError::ValidationError(message)) => {
+                assert!(message.contains("at least 8 characters"), "{}", message)
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn validate_password_accepts_strong_password() {
+        assert!(service().validate_password("Zq7!kLm2pWx").is_ok());
+        assert!(service().validate_password("Zq7!kLm2").is_ok());
+    }
+
+    #[test]
+    fn validate_username_accepts_valid_names() {
+        assert!(validate_username("ivan").is_ok());
+        assert!(validate_username("Ivan_Petrov-2").is_ok());
+        assert!(validate_username(&"a".repeat(MAX_USERNAME_LENGTH)).is_ok());
+    }
+
+    #[test]
+    fn validate_username_rejects_empty_and_over_length_names() {
+        assert!(validate_username("").is_err());
+        assert!(validate_username("ab").is_err());
+        assert!(validate_username(&"a".repeat(MAX_USERNAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn validate_username_rejects_forbidden_characters() {
+        assert!(validate_username("ivan petrov").is_err());
+        assert!(validate_username("иван").is_err());
+        assert!(validate_username("ivan@home").is_err());
+    }
+
+    #[test]
+    fn validate_email_accepts_valid_addresses() {
+        assert!(validate_email("ivan@example.com").is_ok());
+        assert!(validate_email("ivan.petrov+blog@mail.example.org").is_ok());
+    }
+
+    #[test]
+    fn validate_email_rejects_empty_and_over_length_addresses() {
+        assert!(validate_email("").is_err());
+        let local = "a".repeat(64);
+        let domain = format!("{}.com", "b".repeat(MAX_EMAIL_LENGTH - 64 - 1 - 4 + 1));
+        let too_long = format!("{}@{}", local, domain);
+        assert_eq!(too_long.len(), MAX_EMAIL_LENGTH + 1);
+        assert!(validate_email(&too_long).is_err());
+        assert!(validate_email(&format!("{}@example.com", "a".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn validate_email_rejects_malformed_addresses() {
+        for email in [
+            "ivan",
+            "ivan@",
+            "@example.com",
+            "ivan@example",
+            "ivan@@example.com",
+            "ivan@exa mple.com",
+            ".ivan@example.com",
+            "iv..an@example.com",
+            "ivan@-example.com",
+            "ivan@example.c",
+            "ivan@example.c0m",
+        ] {
+            assert!(
+                validate_email(email).is_err(),
+                "{} should be rejected",
+                email
+            );
+        }
+    }
+
+    async fn db_service() -> Option<AuthService> {
+        let pool = crate::infrastructure::database::test_pool().await?;
+        let jwt = JwtService::new(
+            "test-secret-that-is-at-least-32-chars",
+            chrono::Duration::hours(1),
+        )
+        .unwrap();
+        let service = AuthService::new(Arc::new(PostgresUserRepository::new(pool)), Arc::new(jwt))
+            .with_argon2(Argon2Config::fast())
+            .unwrap();
+        Some(service)
+    }
+
+    #[tokio::test]
+    async fn register_rejects_username_differing_only_in_case() {
+        let Some(service) = db_service().await else {
+            return;
+        };
+        let name = format!("Alice{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+
+        service
+            .register(RegisterUserRequest {
+                username: name.clone(),
+                email: format!("{}@example.com", name),
+                password: "Zq7!kLm2pWx".to_string(),
+            })
+            .await
+            .unwrap();
+        let err = service
+            .register(RegisterUserRequest {
+                username: name.to_lowercase(),
+                email: format!("other-{}@example.com", name),
+                password: "Zq7!kLm2pWx".to_string(),
+            })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DomainError::UserAlreadyExists), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn login_ignores_case_of_username_and_email() {
+        let Some(service) = db_service().await else {
+            return;
+        };
+        let name = format!("Alice{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+        let (_, registered) = service
+            .register(RegisterUserRequest {
+                username: name.clone(),
+                email: format!("{}@Example.com", name),
+                password: "Zq7!kLm2pWx".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let (_, by_username) = service
+            .login(LoginUserRequest {
+                username: name.to_lowercase(),
+                email: None,
+                password: "Zq7!kLm2pWx".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(by_username.id, registered.id);
+        assert_eq!(by_username.username, name);
+
+        let (_, by_email) = service
+            .login(LoginUserRequest {
+                username: String::new(),
+                email: Some(format!("{}@example.COM", name.to_uppercase())),
+                password: "Zq7!kLm2pWx".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(by_email.id, registered.id);
+    }
+}
@@ -1,23 +1,391 @@
+use crate::application::post_events::{NoopPostEventSink, PostEvent, PostEventSink};
 use crate::data::post_repository::PostRepository;
-use crate::domain::post::{CreatePostRequest, PostResponse, UpdatePostRequest};
-use crate::domain::DomainError;
+use crate::domain::post::{
+    excerpt, pagination, AuthorSummary, BatchItemResult, CreatePostRequest, DashboardPost,
+    PostCursor, PostFilter, PostPage, PostResponse, UpdatePostRequest,
+};
+use crate::domain::user::Role;
+use crate::domain::{DomainError, Post};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// How long an idempotency key of `create_post` is remembered by default (24 hours)
+pub const DEFAULT_IDEMPOTENCY_TTL_SECONDS: u64 = 24 * 60 * 60;
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+const MAX_TAGS_PER_POST: usize = 10;
+/// Maximum number of posts in one batch (`get_posts`, `create_posts`, `delete_posts`)
+const MAX_BATCH_SIZE: usize = 100;
+const MAX_TAG_LENGTH: usize = 50;
+/// Default maximum post title length in characters
+pub const DEFAULT_MAX_TITLE_LENGTH: usize = 200;
+/// Default maximum post content length in characters
+pub const DEFAULT_MAX_CONTENT_LENGTH: usize = 100_000;
+/// Default length of the excerpt in list results, in characters
+pub const DEFAULT_EXCERPT_LENGTH: usize = 200;
+
+// Приводит теги к нижнему регистру, убирает пустые и дубликаты
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, DomainError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || normalized.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(DomainError::ValidationError(format!(
+                "Tag cannot be longer than {} characters",
+                MAX_TAG_LENGTH
+            )));
+        }
+        normalized.push(tag);
+    }
+
+    if normalized.len() > MAX_TAGS_PER_POST {
+        return Err(DomainError::ValidationError(format!(
+            "A post cannot have more than {} tags",
+            MAX_TAGS_PER_POST
+        )));
+    }
+
+    Ok(normalized)
+}
+
+// Длина поля в символах (не байтах); в ошибке указывается само поле
+fn validate_length(field: &str, value: &str, max: usize) -> Result<(), DomainError> {
+    if value.chars().count() > max {
+        return Err(DomainError::ValidationError(format!(
+            "{} cannot be longer than {} characters",
+            field, max
+        )));
+    }
+    Ok(())
+}
+
+// Имя тега для переименования: приводится как теги поста, но пустым быть не может
+fn tag_name(field: &str, value: &str) -> Result<String, DomainError> {
+    let tag = value.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err(DomainError::ValidationError(format!(
+            "{} cannot be empty",
+            field
+        )));
+    }
+    validate_length(field, &tag, MAX_TAG_LENGTH)?;
+    Ok(tag)
+}
+
+// Ключ идемпотентности: непустая строка из видимых ASCII-символов
+fn validate_idempotency_key(key: &str) -> Result<&str, DomainError> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(DomainError::ValidationError(
+            "Idempotency key cannot be empty".to_string(),
+        ));
+    }
+    if key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(DomainError::ValidationError(format!(
+            "Idempotency key cannot be longer than {} characters",
+            MAX_IDEMPOTENCY_KEY_LENGTH
+        )));
+    }
+    if !key.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(DomainError::ValidationError(
+            "Idempotency key may contain only visible ASCII characters".to_string(),
+        ));
+    }
+    Ok(key)
+}
+
+// Общие для списка и подсчета проверки фильтра; пустые строки поиска и тега отбрасываются
+fn normalize_filter(mut filter: PostFilter) -> Result<PostFilter, DomainError> {
+    if let (Some(after), Some(before)) = (filter.created_after, filter.created_before) {
+        if after > before {
+            return Err(DomainError::ValidationError(
+                "created_after must not be later than created_before".to_string(),
+            ));
+        }
+    }
+
+    filter.search = filter
+        .search
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    filter.tag = filter
+        .tag
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty());
+
+    Ok(filter)
+}
+
+// Ответ с каноническим permalink (если задан PUBLIC_BASE_URL)
+fn post_response(post: Post, public_base_url: Option<&str>) -> PostResponse {
+    let mut response = PostResponse::from(post);
+    response.permalink =
+        public_base_url.map(|base| format!("{}/posts/{}", base.trim_end_matches('/'), response.id));
+    response
+}
 
 pub struct BlogService {
     post_repo: Arc<dyn PostRepository + Send + Sync>,
+    public_base_url: Option<String>,
+    idempotency_ttl: Duration,
+    max_title_length: usize,
+    max_content_length: usize,
+    excerpt_length: usize,
+    /// 0 - без ограничения
+    max_posts_per_user: u64,
+    event_sink: Arc<dyn PostEventSink>,
 }
 
 impl BlogService {
-    pub fn new(post_repo: Arc<dyn PostRepository + Send + Sync>) -> Self {
-        Self { post_repo }
+    pub fn new(
+        post_repo: Arc<dyn PostRepository + Send + Sync>,
+        public_base_url: Option<String>,
+    ) -> Self {
+        Self {
+            post_repo,
+            public_base_url,
+            idempotency_ttl: Duration::from_secs(DEFAULT_IDEMPOTENCY_TTL_SECONDS),
+            max_title_length: DEFAULT_MAX_TITLE_LENGTH,
+            max_content_length: DEFAULT_MAX_CONTENT_LENGTH,
+            excerpt_length: DEFAULT_EXCERPT_LENGTH,
+            max_posts_per_user: 0,
+            event_sink: Arc::new(NoopPostEventSink),
+        }
+    }
+
+    /// Stream of newly created public posts (drafts and scheduled posts are not sent),
+    /// read from the event sink; empty unless the sink can be subscribed to
+    /// (`BroadcastPostEventSink`)
+    pub fn subscribe_posts(&self) -> BoxStream<'static, PostResponse> {
+        let Some(receiver) = self.event_sink.subscribe() else {
+            tracing::warn!("Post event sink does not support subscriptions, stream is empty");
+            return stream::empty().boxed();
+        };
+        let public_base_url = self.public_base_url.clone();
+        stream::unfold(receiver, move |mut receiver| {
+            let public_base_url = public_base_url.clone();
+            async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(PostEvent::Created(post)) if post.is_public() => {
+                            return Some((
+                                post_response(post, public_base_url.as_deref()),
+                                receiver,
+                            ))
+                        }
+                        Ok(event) => {
+                            tracing::trace!("Post event for post {} skipped", event.post().id)
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(
+                                "Post subscriber lagged behind, {} events skipped",
+                                skipped
+                            );
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Set the sink notified after posts are created, updated or deleted;
+    /// it also feeds `subscribe_posts`
+    pub fn with_event_sink(mut self, sink: Arc<dyn PostEventSink>) -> Self {
+        self.event_sink = sink;
+        self
+    }
+
+    /// Override how long idempotency keys of `create_post` are remembered
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency_ttl = ttl;
+        self
+    }
+
+    /// Override the maximum post title length (in characters)
+    pub fn with_max_title_length(mut self, max: usize) -> Self {
+        self.max_title_length = max;
+        self
+    }
+
+    /// Override the maximum post content length (in characters)
+    pub fn with_max_content_length(mut self, max: usize) -> Self {
+        self.max_content_length = max;
+        self
     }
 
+    /// Override the excerpt length of list results (in characters)
+    pub fn with_excerpt_length(mut self, length: usize) -> Self {
+        self.excerpt_length = length;
+        self
+    }
+
+    /// Cap the number of posts (drafts included) a non-admin user can have; 0 means unlimited
+    pub fn with_max_posts_per_user(mut self, max: u64) -> Self {
+        self.max_posts_per_user = max;
+        self
+    }
+
+    fn to_response(&self, post: Post) -> PostResponse {
+        post_response(post, self.public_base_url.as_deref())
+    }
+
+    async fn post_created(&self, post: &Post) {
+        self.event_sink.on_created(post).await;
+    }
+
+    async fn post_updated(&self, post: &Post) {
+        self.event_sink.on_updated(post).await;
+    }
+
+    async fn post_deleted(&self, post: &Post) {
+        self.event_sink.on_deleted(post).await;
+    }
+
+    /// Create a post; a repeated call with the same `idempotency_key` returns
+    /// the originally created post instead of inserting a duplicate
     pub async fn create_post(
         &self,
         author_id: i64,
-        req: CreatePostRequest,
+        role: Role,
+        mut req: CreatePostRequest,
+        idempotency_key: Option<&str>,
     ) -> Result<PostResponse, DomainError> {
-        // Validate input
+        let idempotency_key = idempotency_key.map(validate_idempotency_key).transpose()?;
+
+        self.validate_new_post(&mut req)?;
+
+        // Повтор запроса возвращает уже созданный пост, даже если автор тем временем
+        // достиг лимита постов
+        if let Some(key) = idempotency_key {
+            if let Some(post) = self
+                .post_repo
+                .find_idempotent(author_id, key, self.idempotency_ttl)
+                .await?
+            {
+                tracing::info!("Idempotent replay: post id={} already exists", post.id);
+                return Ok(self.to_response(post));
+            }
+        }
+        self.check_post_limit(author_id, role, 1).await?;
+
+        // Create post
+        let (post, created) = match idempotency_key {
+            Some(key) => {
+                self.post_repo
+                    .create_idempotent(author_id, req, key, self.idempotency_ttl)
+                    .await?
+            }
+            None => (self.post_repo.create(author_id, req).await?, true),
+        };
+
+        if !created {
+            tracing::info!("Idempotent replay: post id={} already exists", post.id);
+            return Ok(self.to_response(post));
+        }
+
+        tracing::info!("Post created: id={}, author_id={}", post.id, author_id);
+
+        self.post_created(&post).await;
+
+        Ok(self.to_response(post))
+    }
+
+    /// Create several posts in one transaction: either all of them or none.
+    /// Every request is validated before anything is inserted; a validation
+    /// error names the offending item (`posts[2]: Title cannot be empty`)
+    pub async fn create_posts(
+        &self,
+        author_id: i64,
+        role: Role,
+        mut reqs: Vec<CreatePostRequest>,
+    ) -> Result<Vec<PostResponse>, DomainError> {
+        if reqs.len() > MAX_BATCH_SIZE {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot create more than {} posts at once",
+                MAX_BATCH_SIZE
+            )));
+        }
+        if reqs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        for (index, req) in reqs.iter_mut().enumerate() {
+            self.validate_new_post(req)
+                .map_err(|err| err.in_batch("posts", index))?;
+        }
+        self.check_post_limit(author_id, role, reqs.len()).await?;
+
+        let posts = self.post_repo.create_many(author_id, reqs).await?;
+
+        tracing::info!("Created {} posts for author_id={}", posts.len(), author_id);
+
+        for post in &posts {
+            self.post_created(post).await;
+        }
+
+        Ok(posts.into_iter().map(|p| self.to_response(p)).collect())
+    }
+
+    /// Best-effort variant of `create_posts`: each valid item is created on its own,
+    /// an invalid one (or one over the post limit) gets an error entry and the rest
+    /// still go in. Results follow the order of `reqs`, posts - the created ones only
+    pub async fn create_posts_best_effort(
+        &self,
+        author_id: i64,
+        role: Role,
+        reqs: Vec<CreatePostRequest>,
+    ) -> Result<(Vec<PostResponse>, Vec<BatchItemResult>), DomainError> {
+        if reqs.len() > MAX_BATCH_SIZE {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot create more than {} posts at once",
+                MAX_BATCH_SIZE
+            )));
+        }
+
+        let mut posts = Vec::new();
+        let mut results = Vec::with_capacity(reqs.len());
+        for (index, req) in reqs.into_iter().enumerate() {
+            match self.create_batch_item(author_id, role, req).await {
+                Ok(post) => {
+                    self.post_created(&post).await;
+                    results.push(BatchItemResult::success(index, post.id));
+                    posts.push(self.to_response(post));
+                }
+                Err(err) => results.push(BatchItemResult::failure(index, None, &err)),
+            }
+        }
+
+        tracing::info!(
+            "Created {} of {} posts for author_id={} (best effort)",
+            posts.len(),
+            results.len(),
+            author_id
+        );
+
+        Ok((posts, results))
+    }
+
+    async fn create_batch_item(
+        &self,
+        author_id: i64,
+        role: Role,
+        mut req: CreatePostRequest,
+    ) -> Result<Post, DomainError> {
+        self.validate_new_post(&mut req)?;
+        self.check_post_limit(author_id, role, 1).await?;
+        self.post_repo.create(author_id, req).await
+    }
+
+    // Проверка и нормализация (теги) нового поста перед вставкой
+    fn validate_new_post(&self, req: &mut CreatePostRequest) -> Result<(), DomainError> {
         if req.title.trim().is_empty() {
             return Err(DomainError::ValidationError(
                 "Title cannot be empty".to_string(),
@@ -28,30 +396,157 @@ impl BlogService {
                 "Content cannot be empty".to_string(),
             ));
         }
+        validate_length("Title", &req.title, self.max_title_length)?;
+        validate_length("Content", &req.content, self.max_content_length)?;
+        req.tags = normalize_tags(std::mem::take(&mut req.tags))?;
+        Ok(())
+    }
 
-        // Create post
-        let post = self.post_repo.create(author_id, req).await?;
-
-        tracing::info!("Post created: id={}, author_id={}", post.id, author_id);
+    // Лимит мягкий: одновременные запросы могут превысить его на несколько постов
+    async fn check_post_limit(
+        &self,
+        author_id: i64,
+        role: Role,
+        adding: usize,
+    ) -> Result<(), DomainError> {
+        if self.max_posts_per_user == 0 || role.is_admin() {
+            return Ok(());
+        }
 
-        Ok(PostResponse::from(post))
+        let count = self.post_repo.count_by_author(author_id).await?;
+        if count as u64 + adding as u64 > self.max_posts_per_user {
+            tracing::warn!(
+                "User {} reached the post limit ({})",
+                author_id,
+                self.max_posts_per_user
+            );
+            return Err(DomainError::ValidationError(format!(
+                "Post limit reached: a user can have at most {} posts",
+                self.max_posts_per_user
+            )));
+        }
+        Ok(())
     }
 
-    pub async fn get_post(&self, id: i64) -> Result<PostResponse, DomainError> {
+    // Черновики и отложенные посты видны только автору; остальным — как несуществующие
+    async fn find_visible(&self, id: i64, viewer_id: Option<i64>) -> Result<Post, DomainError> {
         let post = self.post_repo.find_by_id(id).await?;
-        Ok(PostResponse::from(post))
+
+        if post.is_public() || viewer_id == Some(post.author_id) {
+            Ok(post)
+        } else {
+            Err(DomainError::PostNotFound)
+        }
     }
 
+    /// Embed the public author profile into each post (one lookup for the whole page)
+    pub async fn include_authors(&self, posts: &mut [PostResponse]) -> Result<(), DomainError> {
+        if posts.is_empty() {
+            return Ok(());
+        }
+
+        let mut author_ids: Vec<i64> = posts.iter().map(|p| p.author_id).collect();
+        author_ids.sort_unstable();
+        author_ids.dedup();
+
+        let authors: HashMap<i64, AuthorSummary> = self
+            .post_repo
+            .find_authors(&author_ids)
+            .await?
+            .into_iter()
+            .map(|author| (author.id, author))
+            .collect();
+
+        for post in posts.iter_mut() {
+            post.author = authors.get(&post.author_id).cloned();
+        }
+
+        Ok(())
+    }
+
+    /// Get a post; drafts are returned only when `viewer_id` is their author
+    pub async fn get_post(
+        &self,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<PostResponse, DomainError> {
+        let post = self.find_visible(id, viewer_id).await?;
+        Ok(self.to_response(post))
+    }
+
+    /// Get several posts at once, in the order of `ids`.
+    /// Missing or invisible posts are skipped, duplicate ids are returned once.
+    pub async fn get_posts(
+        &self,
+        ids: Vec<i64>,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<PostResponse>, DomainError> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot request more than {} posts at once",
+                MAX_BATCH_SIZE
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let ids: Vec<i64> = ids.into_iter().filter(|id| seen.insert(*id)).collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut posts: HashMap<i64, Post> = self
+            .post_repo
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .filter(|p| p.is_public() || viewer_id == Some(p.author_id))
+            .map(|p| (p.id, p))
+            .collect();
+
+        Ok(ids
+            .iter()
+            .filter_map(|id| posts.remove(id))
+            .map(|p| self.to_response(p))
+            .collect())
+    }
+
+    /// `get_posts` that also reports every requested id: found, or `Post not found`
+    /// for missing and invisible posts. Results follow the order of `ids`
+    pub async fn get_posts_best_effort(
+        &self,
+        ids: Vec<i64>,
+        viewer_id: Option<i64>,
+    ) -> Result<(Vec<PostResponse>, Vec<BatchItemResult>), DomainError> {
+        let posts = self.get_posts(ids.clone(), viewer_id).await?;
+        let found: HashSet<i64> = posts.iter().map(|p| p.id).collect();
+
+        let results = ids
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| {
+                if found.contains(&id) {
+                    BatchItemResult::success(index, id)
+                } else {
+                    BatchItemResult::failure(index, Some(id), &DomainError::PostNotFound)
+                }
+            })
+            .collect();
+
+        Ok((posts, results))
+    }
+
+    /// Update a post; allowed to its author and to admins
     pub async fn update_post(
         &self,
         id: i64,
         user_id: i64,
-        req: UpdatePostRequest,
+        role: Role,
+        mut req: UpdatePostRequest,
     ) -> Result<PostResponse, DomainError> {
-        // Check if post exists and user is author
+        // Check if post exists and user is author (or an admin)
         let post = self.post_repo.find_by_id(id).await?;
 
-        if post.author_id != user_id {
+        if post.author_id != user_id && !role.is_admin() {
             tracing::warn!(
                 "User {} attempted to update post {} owned by {}",
                 user_id,
@@ -61,19 +556,39 @@ impl BlogService {
             return Err(DomainError::Forbidden);
         }
 
+        if let Some(title) = &req.title {
+            validate_length("Title", title, self.max_title_length)?;
+        }
+        if let Some(content) = &req.content {
+            validate_length("Content", content, self.max_content_length)?;
+        }
+        req.tags = req.tags.map(normalize_tags).transpose()?;
+
         // Update post
         let updated_post = self.post_repo.update(id, req).await?;
 
-        tracing::info!("Post updated: id={}, author_id={}", id, user_id);
+        if post.author_id == user_id {
+            tracing::info!("Post updated: id={}, author_id={}", id, user_id);
+        } else {
+            tracing::info!(
+                "Post updated by admin: id={}, author_id={}, admin_id={}",
+                id,
+                post.author_id,
+                user_id
+            );
+        }
+
+        self.post_updated(&updated_post).await;
 
-        Ok(PostResponse::from(updated_post))
+        Ok(self.to_response(updated_post))
     }
 
-    pub async fn delete_post(&self, id: i64, user_id: i64) -> Result<(), DomainError> {
-        // Check if post exists and user is author
+    /// Delete a post; allowed to its author and to admins
+    pub async fn delete_post(&self, id: i64, user_id: i64, role: Role) -> Result<(), DomainError> {
+        // Check if post exists and user is author (or an admin)
         let post = self.post_repo.find_by_id(id).await?;
 
-        if post.author_id != user_id {
+        if post.author_id != user_id && !role.is_admin() {
             tracing::warn!(
                 "User {} attempted to delete post {} owned by {}",
                 user_id,
@@ -86,39 +601,689 @@ impl BlogService {
         // Delete post
         self.post_repo.delete(id).await?;
 
-        tracing::info!("Post deleted: id={}, author_id={}", id, user_id);
+        if post.author_id == user_id {
+            tracing::info!("Post deleted: id={}, author_id={}", id, user_id);
+        } else {
+            tracing::info!(
+                "Post deleted by admin: id={}, author_id={}, admin_id={}",
+                id,
+                post.author_id,
+                user_id
+            );
+        }
+
+        self.post_deleted(&post).await;
 
         Ok(())
     }
 
-    pub async fn list_posts(
+    /// Delete several posts in one transaction: either all of them or none.
+    /// Every post must exist and belong to `user_id` (any post for admins);
+    /// duplicate ids are deleted once. Returns how many were deleted
+    pub async fn delete_posts(
         &self,
-        limit: i64,
-        offset: i64,
-    ) -> Result<(Vec<PostResponse>, i64), DomainError> {
-        // Validate pagination parameters
-        if !(1..=100).contains(&limit) {
-            return Err(DomainError::ValidationError(
-                "Limit must be between 1 and 100".to_string(),
-            ));
+        ids: Vec<i64>,
+        user_id: i64,
+        role: Role,
+    ) -> Result<u64, DomainError> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot delete more than {} posts at once",
+                MAX_BATCH_SIZE
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let ids: Vec<i64> = ids.into_iter().filter(|id| seen.insert(*id)).collect();
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut posts: HashMap<i64, Post> = self
+            .post_repo
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect();
+        for id in &ids {
+            let Some(post) = posts.get(id) else {
+                return Err(DomainError::PostNotFound);
+            };
+            if post.author_id != user_id && !role.is_admin() {
+                tracing::warn!(
+                    "User {} attempted to delete post {} owned by {}",
+                    user_id,
+                    id,
+                    post.author_id
+                );
+                return Err(DomainError::Forbidden);
+            }
         }
-        if offset < 0 {
+
+        let deleted = self.post_repo.delete_many(&ids).await?;
+
+        tracing::info!("Deleted {} posts for user_id={}", deleted, user_id);
+
+        for id in &ids {
+            if let Some(post) = posts.remove(id) {
+                self.post_deleted(&post).await;
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    /// Best-effort variant of `delete_posts`: each post is deleted on its own, a missing
+    /// or foreign one gets an error entry. Results follow the order of `ids`
+    pub async fn delete_posts_best_effort(
+        &self,
+        ids: Vec<i64>,
+        user_id: i64,
+        role: Role,
+    ) -> Result<Vec<BatchItemResult>, DomainError> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot delete more than {} posts at once",
+                MAX_BATCH_SIZE
+            )));
+        }
+
+        let mut results = Vec::with_capacity(ids.len());
+        for (index, id) in ids.into_iter().enumerate() {
+            results.push(match self.delete_post(id, user_id, role).await {
+                Ok(()) => BatchItemResult::success(index, id),
+                Err(err) => BatchItemResult::failure(index, Some(id), &err),
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Delete every post of `user_id` (drafts included); returns how many were deleted
+    pub async fn delete_posts_by_author(&self, user_id: i64) -> Result<u64, DomainError> {
+        // Посты читаются до удаления ради событий; созданный между чтением
+        // и удалением пост удаляется без события
+        let posts = self.post_repo.find_by_author(user_id).await?;
+        let deleted = self.post_repo.delete_by_author(user_id).await?;
+
+        tracing::info!("Deleted {} posts of author_id={}", deleted, user_id);
+
+        for post in &posts {
+            self.post_deleted(post).await;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Rename a tag on every post (moderation, e.g. fixing "javascirpt").
+    /// A post that already has `new_name` keeps a single copy of it.
+    /// Returns the number of posts that carried the old tag
+    pub async fn rename_tag(&self, old_name: &str, new_name: &str) -> Result<u64, DomainError> {
+        let old_name = tag_name("Old tag", old_name)?;
+        let new_name = tag_name("New tag", new_name)?;
+        if old_name == new_name {
             return Err(DomainError::ValidationError(
-                "Offset cannot be negative".to_string(),
+                "New tag must differ from the old one".to_string(),
             ));
         }
 
-        let (posts, total) = self.post_repo.list(limit, offset).await?;
+        let post_ids = self.post_repo.rename_tag(&old_name, &new_name).await?;
+
+        tracing::info!(
+            "Tag renamed: '{}' -> '{}' on {} posts",
+            old_name,
+            new_name,
+            post_ids.len()
+        );
+
+        // Переименование уже зафиксировано: ошибка чтения стоит лишь событий
+        if !post_ids.is_empty() {
+            match self.post_repo.find_by_ids(&post_ids).await {
+                Ok(posts) => {
+                    for post in &posts {
+                        self.post_updated(post).await;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to load posts after tag rename: {:?}", e),
+            }
+        }
+
+        Ok(post_ids.len() as u64)
+    }
+
+    /// Like a post; liking an already liked post is a no-op.
+    /// Returns the current number of likes.
+    pub async fn like_post(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        // Проверяем существование поста, чтобы вернуть 404 вместо ошибки внешнего ключа
+        self.find_visible(post_id, Some(user_id)).await?;
+
+        let likes_count = self.post_repo.like(post_id, user_id).await?;
+
+        tracing::info!("Post liked: id={}, user_id={}", post_id, user_id);
+
+        Ok(likes_count)
+    }
+
+    /// Remove a like; unliking a post that isn't liked is a no-op.
+    /// Returns the current number of likes.
+    pub async fn unlike_post(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        self.find_visible(post_id, Some(user_id)).await?;
+
+        let likes_count = self.post_repo.unlike(post_id, user_id).await?;
+
+        tracing::info!("Post unliked: id={}, user_id={}", post_id, user_id);
+
+        Ok(likes_count)
+    }
+
+    pub async fn list_posts(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: PostFilter,
+    ) -> Result<PostPage, DomainError> {
+        pagination(Some(limit), Some(offset))?;
+        if filter.after.is_some() {
+            if offset != 0 {
+                return Err(DomainError::ValidationError(
+                    "Cursor and offset cannot be combined".to_string(),
+                ));
+            }
+            if !filter.sort.supports_cursor() {
+                return Err(DomainError::ValidationError(
+                    "Cursor pagination supports only created_desc and created_asc sort".to_string(),
+                ));
+            }
+        }
+
+        let filter = normalize_filter(filter)?;
+
+        // Лишняя строка показывает, есть ли следующая страница
+        let (mut posts, total) = self.post_repo.list(limit + 1, offset, &filter).await?;
 
-        let post_responses = posts.into_iter().map(PostResponse::from).collect();
+        let has_more = posts.len() as i64 > limit;
+        posts.truncate(limit as usize);
+        let next_cursor = posts
+            .last()
+            .filter(|_| has_more && filter.sort.supports_cursor())
+            .map(|p| PostCursor::after(p).to_string());
 
-        Ok((post_responses, total))
+        Ok(PostPage {
+            posts: posts
+                .into_iter()
+                .map(|p| {
+                    let excerpt = excerpt(&p.content, self.excerpt_length);
+                    let mut response = self.to_response(p);
+                    response.excerpt = Some(excerpt);
+                    response
+                })
+                .collect(),
+            total,
+            next_cursor,
+        })
+    }
+
+    /// Number of posts matching `filter`, without fetching them
+    pub async fn count_posts(&self, filter: PostFilter) -> Result<i64, DomainError> {
+        let filter = normalize_filter(filter)?;
+        self.post_repo.count(&filter).await
+    }
+
+    /// Author's own posts in every state (drafts included), most recently updated first
+    pub async fn dashboard(
+        &self,
+        author_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<DashboardPost>, i64), DomainError> {
+        pagination(Some(limit), Some(offset))?;
+
+        let (posts, total) = self
+            .post_repo
+            .list_for_dashboard(author_id, limit, offset)
+            .await?;
+
+        let entries = posts
+            .into_iter()
+            .map(|p| {
+                let status = p.status();
+                DashboardPost {
+                    post: self.to_response(p),
+                    status,
+                }
+            })
+            .collect();
+
+        Ok((entries, total))
+    }
+
+    #[allow(dead_code)]
+    pub async fn list_posts_by_author(
+        &self,
+        author_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<PostPage, DomainError> {
+        let filter = PostFilter {
+            author_id: Some(author_id),
+            ..PostFilter::default()
+        };
+
+        self.list_posts(limit, offset, filter).await
     }
 
     #[allow(dead_code)]
     pub async fn get_user_posts(&self, author_id: i64) -> Result<Vec<PostResponse>, DomainError> {
         let posts = self.post_repo.find_by_author(author_id).await?;
 
-        Ok(posts.into_iter().map(PostResponse::from).collect())
+        Ok(posts.into_iter().map(|p| self.to_response(p)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::memory::InMemoryPostRepository;
+
+    const AUTHOR_ID: i64 = 1;
+
+    fn service() -> BlogService {
+        BlogService::new(Arc::new(InMemoryPostRepository::new()), None)
+    }
+
+    fn new_post(title: &str) -> CreatePostRequest {
+        CreatePostRequest {
+            title: title.to_string(),
+            content: "Content".to_string(),
+            tags: Vec::new(),
+            content_format: Default::default(),
+            published: true,
+            published_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn best_effort_create_reports_invalid_item_and_creates_the_rest() {
+        let service = service();
+        let reqs = ["First", "Second", "Third", "", "Fifth"]
+            .into_iter()
+            .map(new_post)
+            .collect();
+
+        let (posts, results) = service
+            .create_posts_best_effort(AUTHOR_ID, Role::User, reqs)
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 4);
+        assert_eq!(results.len(), 5);
+        for (index, result) in results.iter().enumerate() {
+            assert_eq!(result.index, index);
+        }
+        assert!(!results[3].ok);
+        assert_eq!(results[3].id, None);
+        assert!(results[3]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Title cannot be empty"));
+        let created: Vec<Option<i64>> = results
+            .iter()
+            .filter(|result| result.ok)
+            .map(|result| result.id)
+            .collect();
+        let ids: Vec<Option<i64>> = posts.iter().map(|post| Some(post.id)).collect();
+        assert_eq!(created, ids);
+    }
+
+    #[tokio::test]
+    async fn all_or_nothing_create_rejects_the_whole_batch() {
+        let service = service();
+        let reqs = vec![new_post("First"), new_post(""), new_post("Third")];
+
+        let err = service
+            .create_posts(AUTHOR_ID, Role::User, reqs)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("posts[1]"));
+        assert_eq!(
+            service.post_repo.count_by_author(AUTHOR_ID).await.unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn best_effort_get_and_delete_report_missing_and_foreign_posts() {
+        let service = service();
+        let own = service
+            .create_post(AUTHOR_ID, Role::User, new_post("Own"), None)
+            .await
+            .unwrap();
+        let foreign = service
+            .create_post(AUTHOR_ID + 1, Role::User, new_post("Foreign"), None)
+            .await
+            .unwrap();
+        let missing = foreign.id + 100;
+
+        let (posts, results) = service
+            .get_posts_best_effort(vec![own.id, missing], None)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert!(results[0].ok);
+        assert!(!results[1].ok);
+        assert_eq!(results[1].id, Some(missing));
+
+        let err = service
+            .delete_posts(vec![own.id, foreign.id], AUTHOR_ID, Role::User)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden));
+        assert!(service.post_repo.find_by_id(own.id).await.is_ok());
+
+        let results = service
+            .delete_posts_best_effort(vec![own.id, foreign.id, missing], AUTHOR_ID, Role::User)
+            .await
+            .unwrap();
+        let ok: Vec<bool> = results.iter().map(|result| result.ok).collect();
+        assert_eq!(ok, [true, false, false]);
+        assert!(service.post_repo.find_by_id(own.id).await.is_err());
+        assert!(service.post_repo.find_by_id(foreign.id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn list_posts_on_empty_repository_returns_empty_page() {
+        let page = service()
+            .list_posts(10, 0, PostFilter::default())
+            .await
+            .unwrap();
+
+        assert!(page.posts.is_empty());
+        assert_eq!(page.total, 0);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn list_posts_with_offset_past_total_returns_empty_page_and_total() {
+        let service = service();
+        for title in ["First", "Second", "Third"] {
+            service
+                .create_post(AUTHOR_ID, Role::User, new_post(title), None)
+                .await
+                .unwrap();
+        }
+
+        let page = service
+            .list_posts(10, 5, PostFilter::default())
+            .await
+            .unwrap();
+
+        assert!(page.posts.is_empty());
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    fn update(title: Option<String>, content: Option<String>) -> UpdatePostRequest {
+        UpdatePostRequest {
+            title,
+            content,
+            tags: None,
+            content_format: None,
+            published: None,
+            expected_updated_at: None,
+        }
+    }
+
+    fn assert_validation_error(result: Result<PostResponse, DomainError>, message: &str) {
+        match result {
+            Err(DomainError::ValidationError(error)) => assert_eq!(error, message),
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_post_accepts_title_and_content_at_the_length_limit() {
+        let service = service();
+        let mut req = new_post(&"т".repeat(DEFAULT_MAX_TITLE_LENGTH));
+        req.content = "к".repeat(DEFAULT_MAX_CONTENT_LENGTH);
+
+        let post = service
+            .create_post(AUTHOR_ID, Role::User, req, None)
+            .await
+            .unwrap();
+
+        assert_eq!(post.title.chars().count(), DEFAULT_MAX_TITLE_LENGTH);
+        assert_eq!(post.content.chars().count(), DEFAULT_MAX_CONTENT_LENGTH);
+    }
+
+    #[tokio::test]
+    async fn create_post_rejects_title_and_content_over_the_length_limit() {
+        let service = service();
+
+        let req = new_post(&"т".repeat(DEFAULT_MAX_TITLE_LENGTH + 1));
+        assert_validation_error(
+            service.create_post(AUTHOR_ID, Role::User, req, None).await,
+            "Title cannot be longer than 200 characters",
+        );
+
+        let mut req = new_post("Title");
+        req.content = "к".repeat(DEFAULT_MAX_CONTENT_LENGTH + 1);
+        assert_validation_error(
+            service.create_post(AUTHOR_ID, Role::User, req, None).await,
+            "Content cannot be longer than 100000 characters",
+        );
+    }
+
+    #[tokio::test]
+    async fn update_post_enforces_the_length_limits() {
+        let service = service()
+            .with_max_title_length(5)
+            .with_max_content_length(10);
+        let post = service
+            .create_post(AUTHOR_ID, Role::User, new_post("Title"), None)
+            .await
+            .unwrap();
+
+        let at_limit = update(Some("12345".to_string()), Some("1234567890".to_string()));
+        let updated = service
+            .update_post(post.id, AUTHOR_ID, Role::User, at_limit)
+            .await
+            .unwrap();
+        assert_eq!(updated.title, "12345");
+        assert_eq!(updated.content, "1234567890");
+
+        assert_validation_error(
+            service
+                .update_post(
+                    post.id,
+                    AUTHOR_ID,
+                    Role::User,
+                    update(Some("123456".to_string()), None),
+                )
+                .await,
+            "Title cannot be longer than 5 characters",
+        );
+        assert_validation_error(
+            service
+                .update_post(
+                    post.id,
+                    AUTHOR_ID,
+                    Role::User,
+                    update(None, Some("12345678901".to_string())),
+                )
+                .await,
+            "Content cannot be longer than 10 characters",
+        );
+    }
+
+    #[tokio::test]
+    async fn create_post_allows_posts_up_to_the_limit_and_rejects_the_next() {
+        let service = service().with_max_posts_per_user(2);
+        for title in ["First", "Second"] {
+            service
+                .create_post(AUTHOR_ID, Role::User, new_post(title), None)
+                .await
+                .unwrap();
+        }
+
+        assert_validation_error(
+            service
+                .create_post(AUTHOR_ID, Role::User, new_post("Third"), None)
+                .await,
+            "Post limit reached: a user can have at most 2 posts",
+        );
+        // Лимит считается для каждого автора отдельно, администраторы его не имеют
+        service
+            .create_post(AUTHOR_ID + 1, Role::User, new_post("Other"), None)
+            .await
+            .unwrap();
+        service
+            .create_post(AUTHOR_ID, Role::Admin, new_post("Admin"), None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn create_posts_batch_cannot_exceed_the_limit() {
+        let service = service().with_max_posts_per_user(3);
+        let batch = |titles: &[&str]| titles.iter().copied().map(new_post).collect();
+
+        service
+            .create_posts(AUTHOR_ID, Role::User, batch(&["First", "Second"]))
+            .await
+            .unwrap();
+        let err = service
+            .create_posts(AUTHOR_ID, Role::User, batch(&["Third", "Fourth"]))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DomainError::ValidationError(_)));
+        assert_eq!(
+            service.post_repo.count_by_author(AUTHOR_ID).await.unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn idempotent_replay_at_the_post_limit_returns_the_original_post() {
+        let service = service().with_max_posts_per_user(1);
+        let original = service
+            .create_post(AUTHOR_ID, Role::User, new_post("First"), Some("key-1"))
+            .await
+            .unwrap();
+
+        let replay = service
+            .create_post(AUTHOR_ID, Role::User, new_post("First"), Some("key-1"))
+            .await
+            .unwrap();
+        assert_eq!(replay.id, original.id);
+
+        // Новый ключ - новый пост, и для него лимит уже действует
+        assert_validation_error(
+            service
+                .create_post(AUTHOR_ID, Role::User, new_post("Second"), Some("key-2"))
+                .await,
+            "Post limit reached: a user can have at most 1 posts",
+        );
+    }
+
+    #[tokio::test]
+    async fn update_post_marks_the_post_as_edited() {
+        let service = service();
+        let post = service
+            .create_post(AUTHOR_ID, Role::User, new_post("Title"), None)
+            .await
+            .unwrap();
+        assert!(!post.edited);
+
+        let updated = service
+            .update_post(
+                post.id,
+                AUTHOR_ID,
+                Role::User,
+                update(Some("New title".to_string()), None),
+            )
+            .await
+            .unwrap();
+        assert!(updated.edited);
+        assert!(service.get_post(post.id, None).await.unwrap().edited);
+    }
+
+    #[tokio::test]
+    async fn subscribe_posts_streams_public_posts_from_broadcast_sink() {
+        use crate::application::post_events::BroadcastPostEventSink;
+
+        let service = service().with_event_sink(Arc::new(BroadcastPostEventSink::new()));
+        let mut posts = service.subscribe_posts();
+
+        let mut draft = new_post("Draft");
+        draft.published = false;
+        service
+            .create_post(AUTHOR_ID, Role::User, draft, None)
+            .await
+            .unwrap();
+        let public = service
+            .create_post(AUTHOR_ID, Role::User, new_post("Public"), None)
+            .await
+            .unwrap();
+
+        let streamed = posts.next().await.unwrap();
+        assert_eq!(streamed.id, public.id);
+    }
+
+    #[tokio::test]
+    async fn subscribe_posts_is_empty_with_the_default_sink() {
+        assert!(service().subscribe_posts().next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn event_sink_is_notified_of_post_changes() {
+        #[derive(Default)]
+        struct Recorder(std::sync::Mutex<Vec<String>>);
+
+        #[async_trait::async_trait]
+        impl PostEventSink for Recorder {
+            async fn on_created(&self, post: &Post) {
+                self.0.lock().unwrap().push(format!("created {}", post.id));
+            }
+            async fn on_updated(&self, post: &Post) {
+                self.0.lock().unwrap().push(format!("updated {}", post.id));
+            }
+            async fn on_deleted(&self, post: &Post) {
+                self.0.lock().unwrap().push(format!("deleted {}", post.id));
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let service = service().with_event_sink(recorder.clone());
+        let post = service
+            .create_post(AUTHOR_ID, Role::User, new_post("Title"), None)
+            .await
+            .unwrap();
+        service
+            .update_post(
+                post.id,
+                AUTHOR_ID,
+                Role::User,
+                update(Some("New".to_string()), None),
+            )
+            .await
+            .unwrap();
+        service
+            .delete_post(post.id, AUTHOR_ID, Role::User)
+            .await
+            .unwrap();
+
+        let id = post.id;
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                format!("created {}", id),
+                format!("updated {}", id),
+                format!("deleted {}", id)
+            ]
+        );
     }
 }
@@ -0,0 +1,92 @@
+use crate::data::comment_repository::CommentRepository;
+use crate::data::post_repository::PostRepository;
+use crate::domain::comment::CreateCommentRequest;
+use crate::domain::post::pagination;
+use crate::domain::{Comment, DomainError};
+use std::sync::Arc;
+
+/// Maximum comment length in characters
+const MAX_COMMENT_LENGTH: usize = 10_000;
+
+pub struct CommentService {
+    comment_repo: Arc<dyn CommentRepository + Send + Sync>,
+    post_repo: Arc<dyn PostRepository + Send + Sync>,
+}
+
+impl CommentService {
+    pub fn new(
+        comment_repo: Arc<dyn CommentRepository + Send + Sync>,
+        post_repo: Arc<dyn PostRepository + Send + Sync>,
+    ) -> Self {
+        Self {
+            comment_repo,
+            post_repo,
+        }
+    }
+
+    // Комментарии черновика видны и доступны только его автору, как и сам черновик
+    async fn check_post_visible(
+        &self,
+        post_id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<(), DomainError> {
+        let post = self.post_repo.find_by_id(post_id).await?;
+
+        if post.is_public() || viewer_id == Some(post.author_id) {
+            Ok(())
+        } else {
+            Err(DomainError::PostNotFound)
+        }
+    }
+
+    /// Add a comment to a post; the content must not be blank
+    pub async fn add_comment(
+        &self,
+        post_id: i64,
+        author_id: i64,
+        req: CreateCommentRequest,
+    ) -> Result<Comment, DomainError> {
+        if req.content.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Content cannot be empty".to_string(),
+            ));
+        }
+        if req.content.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(DomainError::ValidationError(format!(
+                "Content cannot be longer than {} characters",
+                MAX_COMMENT_LENGTH
+            )));
+        }
+
+        self.check_post_visible(post_id, Some(author_id)).await?;
+
+        let comment = self
+            .comment_repo
+            .create(post_id, author_id, &req.content)
+            .await?;
+
+        tracing::info!(
+            "Comment created: id={}, post_id={}, author_id={}",
+            comment.id,
+            post_id,
+            author_id
+        );
+
+        Ok(comment)
+    }
+
+    /// Comments of a post, oldest first, with their total number
+    pub async fn list_comments(
+        &self,
+        post_id: i64,
+        viewer_id: Option<i64>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Comment>, i64), DomainError> {
+        pagination(Some(limit), Some(offset))?;
+
+        self.check_post_visible(post_id, viewer_id).await?;
+
+        self.comment_repo.list_by_post(post_id, limit, offset).await
+    }
+}
@@ -1,5 +1,8 @@
 pub mod auth_service;
 pub mod blog_service;
+pub mod comment_service;
+pub mod post_events;
 
 pub use auth_service::AuthService;
 pub use blog_service::BlogService;
+pub use comment_service::CommentService;
@@ -0,0 +1,88 @@
+use crate::domain::Post;
+use async_trait::async_trait;
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber of `BroadcastPostEventSink`; a slower subscriber skips the oldest ones
+const POST_EVENTS_CAPACITY: usize = 64;
+
+/// Side effects of post changes (cache invalidation, notifications, search indexing).
+/// `BlogService` calls it after the change is committed, drafts and scheduled posts included;
+/// the request waits for the sink, so slow work should be moved to a background task
+#[async_trait]
+pub trait PostEventSink: Send + Sync {
+    async fn on_created(&self, _post: &Post) {}
+    async fn on_updated(&self, _post: &Post) {}
+    /// `post` is the state right before the deletion
+    async fn on_deleted(&self, _post: &Post) {}
+
+    /// Receiver of the events behind `BlogService::subscribe_posts`; a sink that
+    /// cannot be subscribed to returns `None`, and the post stream ends at once
+    fn subscribe(&self) -> Option<broadcast::Receiver<PostEvent>> {
+        None
+    }
+}
+
+/// Sink that ignores every event; the default of `BlogService`
+pub struct NoopPostEventSink;
+
+impl PostEventSink for NoopPostEventSink {}
+
+/// Event received from `PostEventSink::subscribe`
+#[derive(Debug, Clone)]
+pub enum PostEvent {
+    Created(Post),
+    Updated(Post),
+    Deleted(Post),
+}
+
+impl PostEvent {
+    pub fn post(&self) -> &Post {
+        match self {
+            Self::Created(post) | Self::Updated(post) | Self::Deleted(post) => post,
+        }
+    }
+}
+
+/// Publishes events to a `tokio::sync::broadcast` channel; each `subscribe` gets every
+/// event sent after it. Sending without subscribers is not an error
+pub struct BroadcastPostEventSink {
+    sender: broadcast::Sender<PostEvent>,
+}
+
+impl BroadcastPostEventSink {
+    pub fn new() -> Self {
+        Self {
+            sender: broadcast::channel(POST_EVENTS_CAPACITY).0,
+        }
+    }
+
+    fn send(&self, event: PostEvent) {
+        // Ошибка означает лишь отсутствие подписчиков
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for BroadcastPostEventSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PostEventSink for BroadcastPostEventSink {
+    async fn on_created(&self, post: &Post) {
+        self.send(PostEvent::Created(post.clone()));
+    }
+
+    async fn on_updated(&self, post: &Post) {
+        self.send(PostEvent::Updated(post.clone()));
+    }
+
+    async fn on_deleted(&self, post: &Post) {
+        self.send(PostEvent::Deleted(post.clone()));
+    }
+
+    fn subscribe(&self) -> Option<broadcast::Receiver<PostEvent>> {
+        Some(self.sender.subscribe())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::domain::{Comment, DomainError};
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+
+#[async_trait]
+pub trait CommentRepository: Send + Sync {
+    /// Пост, удаленный одновременно с добавлением комментария, дает `PostNotFound`
+    async fn create(
+        &self,
+        post_id: i64,
+        author_id: i64,
+        content: &str,
+    ) -> Result<Comment, DomainError>;
+    /// Комментарии поста от старых к новым и их общее число
+    async fn list_by_post(
+        &self,
+        post_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Comment>, i64), DomainError>;
+}
+
+pub struct PostgresCommentRepository {
+    pool: PgPool,
+}
+
+impl PostgresCommentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn comment_from_row(row: &PgRow) -> Result<Comment, DomainError> {
+    Ok(Comment {
+        id: row.try_get("id")?,
+        post_id: row.try_get("post_id")?,
+        author_id: row.try_get("author_id")?,
+        content: row.try_get("content")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+#[async_trait]
+impl CommentRepository for PostgresCommentRepository {
+    async fn create(
+        &self,
+        post_id: i64,
+        author_id: i64,
+        content: &str,
+    ) -> Result<Comment, DomainError> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO comments (post_id, author_id, content)
+            VALUES ($1, $2, $3)
+            RETURNING id, post_id, author_id, content, created_at
+            "#,
+        )
+        .bind(post_id)
+        .bind(author_id)
+        .bind(content)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                DomainError::PostNotFound
+            }
+            _ => {
+                tracing::error!("Failed to create comment: {}", e);
+                DomainError::database(e)
+            }
+        })?;
+
+        comment_from_row(&row)
+    }
+
+    async fn list_by_post(
+        &self,
+        post_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Comment>, i64), DomainError> {
+        let count_row = sqlx::query("SELECT COUNT(*) as count FROM comments WHERE post_id = $1")
+            .bind(post_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        let total: i64 = count_row.try_get("count")?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, post_id, author_id, content, created_at
+            FROM comments
+            WHERE post_id = $1
+            ORDER BY created_at ASC, id ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(post_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(DomainError::database)?;
+
+        let comments = rows
+            .iter()
+            .map(comment_from_row)
+            .collect::<Result<Vec<Comment>, DomainError>>()?;
+
+        Ok((comments, total))
+    }
+}
@@ -0,0 +1,298 @@
+//! Хранилище постов в памяти для тестов сервисов и обработчиков без PostgreSQL
+
+use crate::data::post_repository::PostRepository;
+use crate::domain::post::{AuthorSummary, CreatePostRequest, PostFilter, UpdatePostRequest};
+use crate::domain::{DomainError, Post};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct State {
+    next_id: i64,
+    posts: BTreeMap<i64, Post>,
+    likes: HashSet<(i64, i64)>,
+    idempotency_keys: HashMap<(i64, String), (i64, Instant)>,
+}
+
+impl State {
+    fn insert(&mut self, author_id: i64, req: &CreatePostRequest) -> Post {
+        self.next_id += 1;
+        let now = Utc::now();
+        let mut tags = req.tags.clone();
+        tags.sort();
+        let post = Post {
+            id: self.next_id,
+            title: req.title.clone(),
+            content: req.content.clone(),
+            author_id,
+            tags,
+            likes_count: 0,
+            content_format: req.content_format,
+            published: req.published,
+            published_at: req.published.then(|| req.published_at.unwrap_or(now)),
+            created_at: now,
+            updated_at: now,
+        };
+        self.posts.insert(post.id, post.clone());
+        post
+    }
+
+    fn find(&self, id: i64) -> Result<Post, DomainError> {
+        let mut post = self
+            .posts
+            .get(&id)
+            .cloned()
+            .ok_or(DomainError::PostNotFound)?;
+        post.likes_count = self.likes.iter().filter(|(p, _)| *p == id).count() as i64;
+        Ok(post)
+    }
+
+    fn filtered(&self, filter: &PostFilter) -> Vec<Post> {
+        let search = filter.search.as_ref().map(|s| s.to_lowercase());
+        let mut posts: Vec<Post> = self
+            .posts
+            .keys()
+            .filter_map(|id| self.find(*id).ok())
+            .filter(|p| p.is_public() || filter.drafts_visible_to == Some(p.author_id))
+            .filter(|p| filter.author_id.is_none_or(|author| p.author_id == author))
+            .filter(|p| filter.tag.as_ref().is_none_or(|tag| p.tags.contains(tag)))
+            .filter(|p| {
+                search.as_ref().is_none_or(|s| {
+                    p.title.to_lowercase().contains(s) || p.content.to_lowercase().contains(s)
+                })
+            })
+            .collect();
+        // Новые первыми; другие сортировки тестам не нужны
+        posts.reverse();
+        posts
+    }
+}
+
+/// `PostRepository` поверх `Mutex<State>`: без пользователей, транзакций и keyset-пагинации
+#[derive(Default)]
+pub struct InMemoryPostRepository {
+    state: Mutex<State>,
+}
+
+impl InMemoryPostRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+fn page(posts: Vec<Post>, limit: i64, offset: i64) -> (Vec<Post>, i64) {
+    let total = posts.len() as i64;
+    let posts = posts
+        .into_iter()
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect();
+    (posts, total)
+}
+
+#[async_trait]
+impl PostRepository for InMemoryPostRepository {
+    async fn create(&self, author_id: i64, req: CreatePostRequest) -> Result<Post, DomainError> {
+        Ok(self.state().insert(author_id, &req))
+    }
+
+    async fn create_idempotent(
+        &self,
+        author_id: i64,
+        req: CreatePostRequest,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<(Post, bool), DomainError> {
+        if let Some(post) = self.find_idempotent(author_id, key, ttl).await? {
+            return Ok((post, false));
+        }
+
+        let mut state = self.state();
+        let post = state.insert(author_id, &req);
+        state
+            .idempotency_keys
+            .insert((author_id, key.to_string()), (post.id, Instant::now()));
+        Ok((post, true))
+    }
+
+    async fn find_idempotent(
+        &self,
+        author_id: i64,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<Option<Post>, DomainError> {
+        let state = self.state();
+        match state.idempotency_keys.get(&(author_id, key.to_string())) {
+            Some((post_id, created)) if created.elapsed() < ttl => Ok(Some(state.find(*post_id)?)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn create_many(
+        &self,
+        author_id: i64,
+        reqs: Vec<CreatePostRequest>,
+    ) -> Result<Vec<Post>, DomainError> {
+        let mut state = self.state();
+        Ok(reqs
+            .iter()
+            .map(|req| state.insert(author_id, req))
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Post, DomainError> {
+        self.state().find(id)
+    }
+
+    async fn find_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, DomainError> {
+        let state = self.state();
+        Ok(ids.iter().filter_map(|id| state.find(*id).ok()).collect())
+    }
+
+    async fn update(&self, id: i64, req: UpdatePostRequest) -> Result<Post, DomainError> {
+        let mut state = self.state();
+        let post = state.posts.get_mut(&id).ok_or(DomainError::PostNotFound)?;
+        if req
+            .expected_updated_at
+            .is_some_and(|expected| post.updated_at > expected)
+        {
+            return Err(DomainError::Conflict(
+                "Post was modified since it was loaded".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        if let Some(title) = req.title {
+            post.title = title;
+        }
+        if let Some(content) = req.content {
+            post.content = content;
+        }
+        if let Some(mut tags) = req.tags {
+            tags.sort();
+            post.tags = tags;
+        }
+        if let Some(content_format) = req.content_format {
+            post.content_format = content_format;
+        }
+        if let Some(published) = req.published {
+            post.published = published;
+        }
+        post.published_at = post.published.then(|| post.published_at.unwrap_or(now));
+        post.updated_at = now;
+        state.find(id)
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), DomainError> {
+        let mut state = self.state();
+        state.posts.remove(&id).ok_or(DomainError::PostNotFound)?;
+        state.likes.retain(|(post_id, _)| *post_id != id);
+        Ok(())
+    }
+
+    async fn delete_many(&self, ids: &[i64]) -> Result<u64, DomainError> {
+        let mut state = self.state();
+        if ids.iter().any(|id| !state.posts.contains_key(id)) {
+            return Err(DomainError::PostNotFound);
+        }
+        for id in ids {
+            state.posts.remove(id);
+            state.likes.retain(|(post_id, _)| post_id != id);
+        }
+        Ok(ids.len() as u64)
+    }
+
+    async fn delete_by_author(&self, author_id: i64) -> Result<u64, DomainError> {
+        let mut state = self.state();
+        let before = state.posts.len();
+        state.posts.retain(|_, post| post.author_id != author_id);
+        Ok((before - state.posts.len()) as u64)
+    }
+
+    async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &PostFilter,
+    ) -> Result<(Vec<Post>, i64), DomainError> {
+        Ok(page(self.state().filtered(filter), limit, offset))
+    }
+
+    async fn count(&self, filter: &PostFilter) -> Result<i64, DomainError> {
+        Ok(self.state().filtered(filter).len() as i64)
+    }
+
+    async fn find_by_author(&self, author_id: i64) -> Result<Vec<Post>, DomainError> {
+        let state = self.state();
+        let mut posts: Vec<Post> = state
+            .posts
+            .values()
+            .filter(|post| post.author_id == author_id)
+            .filter_map(|post| state.find(post.id).ok())
+            .collect();
+        posts.reverse();
+        Ok(posts)
+    }
+
+    async fn count_by_author(&self, author_id: i64) -> Result<i64, DomainError> {
+        Ok(self.find_by_author(author_id).await?.len() as i64)
+    }
+
+    async fn list_for_dashboard(
+        &self,
+        author_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64), DomainError> {
+        let mut posts = self.find_by_author(author_id).await?;
+        posts.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(b.id.cmp(&a.id)));
+        Ok(page(posts, limit, offset))
+    }
+
+    async fn like(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        let mut state = self.state();
+        state.find(post_id)?;
+        state.likes.insert((post_id, user_id));
+        Ok(state.find(post_id)?.likes_count)
+    }
+
+    async fn unlike(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        let mut state = self.state();
+        state.find(post_id)?;
+        state.likes.remove(&(post_id, user_id));
+        Ok(state.find(post_id)?.likes_count)
+    }
+
+    async fn find_authors(&self, author_ids: &[i64]) -> Result<Vec<AuthorSummary>, DomainError> {
+        Ok(author_ids
+            .iter()
+            .map(|id| AuthorSummary {
+                id: *id,
+                username: format!("user{}", id),
+                avatar_url: None,
+            })
+            .collect())
+    }
+
+    async fn rename_tag(&self, old_name: &str, new_name: &str) -> Result<Vec<i64>, DomainError> {
+        let mut renamed = Vec::new();
+        for post in self.state().posts.values_mut() {
+            if let Some(index) = post.tags.iter().position(|tag| tag == old_name) {
+                post.tags.remove(index);
+                if !post.tags.iter().any(|tag| tag == new_name) {
+                    post.tags.push(new_name.to_string());
+                    post.tags.sort();
+                }
+                renamed.push(post.id);
+            }
+        }
+        Ok(renamed)
+    }
+}
@@ -1,2 +1,6 @@
+pub mod comment_repository;
+#[cfg(test)]
+pub mod memory;
 pub mod post_repository;
+pub mod slow_query;
 pub mod user_repository;
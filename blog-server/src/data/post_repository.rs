@@ -1,16 +1,77 @@
-use crate::domain::post::{CreatePostRequest, UpdatePostRequest};
+use crate::domain::post::{
+    AuthorSummary, ContentFormat, CreatePostRequest, PostCursor, PostFilter, PostSort,
+    UpdatePostRequest,
+};
 use crate::domain::{DomainError, Post};
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
+use std::time::Duration;
 
 #[async_trait]
 pub trait PostRepository: Send + Sync {
     async fn create(&self, author_id: i64, req: CreatePostRequest) -> Result<Post, DomainError>;
+    /// Создает пост с ключом идемпотентности; если автор уже использовал этот ключ
+    /// не раньше чем `ttl` назад, возвращает созданный тогда пост.
+    /// Второе значение - `true`, если пост создан этим вызовом
+    async fn create_idempotent(
+        &self,
+        author_id: i64,
+        req: CreatePostRequest,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<(Post, bool), DomainError>;
+    /// Пост, созданный автором с этим ключом не раньше чем `ttl` назад, если он есть
+    async fn find_idempotent(
+        &self,
+        author_id: i64,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<Option<Post>, DomainError>;
+    /// Создает все посты в одной транзакции: либо все, либо ни одного.
+    /// Посты возвращаются в порядке запросов
+    async fn create_many(
+        &self,
+        author_id: i64,
+        reqs: Vec<CreatePostRequest>,
+    ) -> Result<Vec<Post>, DomainError>;
     async fn find_by_id(&self, id: i64) -> Result<Post, DomainError>;
+    /// Посты с указанными id в произвольном порядке; отсутствующие id пропускаются
+    async fn find_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, DomainError>;
     async fn update(&self, id: i64, req: UpdatePostRequest) -> Result<Post, DomainError>;
     async fn delete(&self, id: i64) -> Result<(), DomainError>;
-    async fn list(&self, limit: i64, offset: i64) -> Result<(Vec<Post>, i64), DomainError>; // i64 для пагинации
+    /// Удаляет все посты одной транзакцией: если хотя бы одного уже нет, не удаляется
+    /// ни один (`PostNotFound`). Возвращает число удаленных
+    async fn delete_many(&self, ids: &[i64]) -> Result<u64, DomainError>;
+    /// Удаляет все посты автора, возвращает число удаленных
+    async fn delete_by_author(&self, author_id: i64) -> Result<u64, DomainError>;
+    async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &PostFilter,
+    ) -> Result<(Vec<Post>, i64), DomainError>; // i64 для пагинации; filter.after включает keyset-режим
+    /// Размер отфильтрованного списка без выборки самих постов (курсор и сортировка не влияют)
+    async fn count(&self, filter: &PostFilter) -> Result<i64, DomainError>;
     async fn find_by_author(&self, author_id: i64) -> Result<Vec<Post>, DomainError>;
+    /// Число постов автора, включая черновики и отложенные
+    async fn count_by_author(&self, author_id: i64) -> Result<i64, DomainError>;
+    /// Все посты автора независимо от статуса, последние измененные первыми
+    async fn list_for_dashboard(
+        &self,
+        author_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64), DomainError>;
+    /// Ставит лайк (повторный лайк игнорируется), возвращает актуальное число лайков
+    async fn like(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError>;
+    /// Снимает лайк (отсутствующий лайк игнорируется), возвращает актуальное число лайков
+    async fn unlike(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError>;
+    /// Публичные профили авторов с указанными id; отсутствующие id пропускаются
+    async fn find_authors(&self, author_ids: &[i64]) -> Result<Vec<AuthorSummary>, DomainError>;
+    /// Переименовывает тег во всех постах одной транзакцией; пост, у которого уже есть
+    /// новый тег, сохраняет одну его копию. Возвращает id постов со старым тегом
+    async fn rename_tag(&self, old_name: &str, new_name: &str) -> Result<Vec<i64>, DomainError>;
 }
 
 pub struct PostgresPostRepository {
@@ -21,102 +82,414 @@ impl PostgresPostRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    async fn count_likes(&self, post_id: i64) -> Result<i64, DomainError> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM post_likes WHERE post_id = $1")
+            .bind(post_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    async fn find_idempotent_post_id(
+        &self,
+        author_id: i64,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<Option<i64>, DomainError> {
+        let row = sqlx::query(
+            r#"
+            SELECT post_id FROM post_idempotency_keys
+            WHERE user_id = $1 AND idempotency_key = $2
+              AND created_at > NOW() - make_interval(secs => $3)
+            "#,
+        )
+        .bind(author_id)
+        .bind(key)
+        .bind(ttl.as_secs_f64())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(DomainError::database)?;
+
+        row.map(|row| row.try_get("post_id"))
+            .transpose()
+            .map_err(DomainError::from)
+    }
+}
+
+// Строка с колонками поста, тегами (TAGS_COLUMN) и числом лайков (LIKES_COLUMN)
+fn post_from_row(row: &PgRow) -> Result<Post, DomainError> {
+    Ok(Post {
+        id: row.try_get("id")?,
+        title: row.try_get("title")?,
+        content: row.try_get("content")?,
+        author_id: row.try_get("author_id")?,
+        tags: row.try_get("tags")?,
+        likes_count: row.try_get("likes_count")?,
+        content_format: row.try_get::<String, _>("content_format")?.parse()?,
+        published: row.try_get("published")?,
+        published_at: row.try_get("published_at")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+// Вставляет пост вместе с тегами в рамках транзакции
+async fn insert_post(
+    tx: &mut Transaction<'_, Postgres>,
+    author_id: i64,
+    req: &CreatePostRequest,
+) -> Result<Post, DomainError> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO posts (title, content, author_id, content_format, published, published_at, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, CASE WHEN $5 THEN COALESCE($6, NOW()) END, NOW(), NOW())
+        RETURNING id, title, content, author_id, content_format, published, published_at, created_at, updated_at,
+            ARRAY[]::VARCHAR[] AS tags, 0::BIGINT AS likes_count
+        "#,
+    )
+    .bind(&req.title)
+    .bind(&req.content)
+    .bind(author_id)
+    .bind(req.content_format.as_str())
+    .bind(req.published)
+    .bind(req.published_at)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create post: {}", e);
+        DomainError::database(e)
+    })?;
+
+    let mut post = post_from_row(&row)?;
+
+    replace_tags(tx, post.id, &req.tags).await?;
+
+    post.tags = req.tags.clone();
+    post.tags.sort();
+
+    Ok(post)
+}
+
+// ORDER BY только из фиксированных вариантов - пользовательский ввод в SQL не попадает.
+// id как второй ключ делает пагинацию стабильной при одинаковых значениях
+fn order_by(sort: PostSort) -> &'static str {
+    match sort {
+        PostSort::CreatedDesc => "p.created_at DESC, p.id DESC",
+        PostSort::CreatedAsc => "p.created_at ASC, p.id ASC",
+        PostSort::TitleAsc => "LOWER(p.title) ASC, p.id ASC",
+        PostSort::UpdatedDesc => "p.updated_at DESC, p.id DESC",
+    }
+}
+
+// Добавляет условия фильтрации к запросу по таблице posts (алиас p) с JOIN users (алиас u)
+fn push_filter(builder: &mut QueryBuilder<'_, Postgres>, filter: &PostFilter) {
+    builder.push(" WHERE (p.published AND p.published_at <= NOW()");
+    if let Some(viewer_id) = filter.drafts_visible_to {
+        builder.push(" OR p.author_id = ").push_bind(viewer_id);
+    }
+    builder.push(")");
+
+    if let Some(author_id) = filter.author_id {
+        builder.push(" AND p.author_id = ").push_bind(author_id);
+    }
+    if let Some(username) = &filter.author_username {
+        builder
+            .push(" AND LOWER(u.username) = LOWER(")
+            .push_bind(username.clone())
+            .push(")");
+    }
+    if let Some(search) = &filter.search {
+        let pattern = format!("%{}%", escape_like(search));
+        builder
+            .push(" AND (p.title ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR p.content ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+    if let Some(tag) = &filter.tag {
+        builder
+            .push(
+                " AND EXISTS (SELECT 1 FROM post_tags pt JOIN tags t ON t.id = pt.tag_id \
+                 WHERE pt.post_id = p.id AND t.name = ",
+            )
+            .push_bind(tag.clone())
+            .push(")");
+    }
+    if let Some(created_after) = filter.created_after {
+        builder
+            .push(" AND p.created_at >= ")
+            .push_bind(created_after);
+    }
+    if let Some(created_before) = filter.created_before {
+        builder
+            .push(" AND p.created_at <= ")
+            .push_bind(created_before);
+    }
+}
+
+// Keyset-пагинация: только строки строго после курсора в порядке сортировки по (created_at, id)
+fn push_after(builder: &mut QueryBuilder<'_, Postgres>, sort: PostSort, cursor: PostCursor) {
+    let op = match sort {
+        PostSort::CreatedAsc => " > ",
+        _ => " < ",
+    };
+    builder
+        .push(" AND (p.created_at, p.id)")
+        .push(op)
+        .push("(")
+        .push_bind(cursor.created_at)
+        .push(", ")
+        .push_bind(cursor.id)
+        .push(")");
+}
+
+// Подзапрос, возвращающий теги поста (алиас p) в виде отсортированного массива
+const TAGS_COLUMN: &str =
+    "ARRAY(SELECT t.name FROM post_tags pt JOIN tags t ON t.id = pt.tag_id WHERE pt.post_id = p.id ORDER BY t.name) AS tags";
+
+// Подзапрос с количеством лайков поста (алиас p)
+const LIKES_COLUMN: &str =
+    "(SELECT COUNT(*) FROM post_likes pl WHERE pl.post_id = p.id) AS likes_count";
+
+// Заменяет набор тегов поста, создавая отсутствующие теги
+async fn replace_tags(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: i64,
+    tags: &[String],
+) -> Result<(), DomainError> {
+    sqlx::query("DELETE FROM post_tags WHERE post_id = $1")
+        .bind(post_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(DomainError::database)?;
+
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO tags (name)
+        SELECT UNNEST($1::varchar[])
+        ON CONFLICT (name) DO NOTHING
+        "#,
+    )
+    .bind(tags)
+    .execute(&mut **tx)
+    .await
+    .map_err(DomainError::database)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO post_tags (post_id, tag_id)
+        SELECT $1, id FROM tags WHERE name = ANY($2)
+        "#,
+    )
+    .bind(post_id)
+    .bind(tags)
+    .execute(&mut **tx)
+    .await
+    .map_err(DomainError::database)?;
+
+    Ok(())
+}
+
+// Экранирует спецсимволы LIKE, чтобы поиск был буквальным
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
 
 #[async_trait]
 impl PostRepository for PostgresPostRepository {
     async fn create(&self, author_id: i64, req: CreatePostRequest) -> Result<Post, DomainError> {
-        let row = sqlx::query(
+        let mut tx = self.pool.begin().await.map_err(DomainError::database)?;
+        let post = insert_post(&mut tx, author_id, &req).await?;
+        tx.commit().await.map_err(DomainError::database)?;
+
+        Ok(post)
+    }
+
+    async fn create_idempotent(
+        &self,
+        author_id: i64,
+        req: CreatePostRequest,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<(Post, bool), DomainError> {
+        if let Some(post_id) = self.find_idempotent_post_id(author_id, key, ttl).await? {
+            return Ok((self.find_by_id(post_id).await?, false));
+        }
+
+        let mut tx = self.pool.begin().await.map_err(DomainError::database)?;
+
+        // Просроченные ключи автора больше не нужны, заодно освобождаем текущий
+        sqlx::query(
             r#"
-            INSERT INTO posts (title, content, author_id, created_at, updated_at)
-            VALUES ($1, $2, $3, NOW(), NOW())
-            RETURNING id, title, content, author_id, created_at, updated_at
+            DELETE FROM post_idempotency_keys
+            WHERE user_id = $1 AND created_at <= NOW() - make_interval(secs => $2)
             "#,
         )
-        .bind(&req.title)
-        .bind(&req.content)
         .bind(author_id)
-        .fetch_one(&self.pool)
+        .bind(ttl.as_secs_f64())
+        .execute(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to create post: {}", e);
-            DomainError::DatabaseError(e.to_string())
-        })?;
-
-        let post = Post {
-            id: row.try_get("id")?,
-            title: row.try_get("title")?,
-            content: row.try_get("content")?,
-            author_id: row.try_get("author_id")?,
-            created_at: row.try_get("created_at")?,
-            updated_at: row.try_get("updated_at")?,
-        };
+        .map_err(DomainError::database)?;
 
-        Ok(post)
-    }
+        let post = insert_post(&mut tx, author_id, &req).await?;
 
-    async fn find_by_id(&self, id: i64) -> Result<Post, DomainError> {
-        let row = sqlx::query(
+        let stored = sqlx::query(
             r#"
-            SELECT id, title, content, author_id, created_at, updated_at
-            FROM posts
-            WHERE id = $1
+            INSERT INTO post_idempotency_keys (user_id, idempotency_key, post_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, idempotency_key) DO NOTHING
             "#,
         )
+        .bind(author_id)
+        .bind(key)
+        .bind(post.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(DomainError::database)?
+        .rows_affected();
+
+        if stored == 0 {
+            // Параллельный запрос с тем же ключом успел первым - откатываем свою вставку
+            tx.rollback().await.map_err(DomainError::database)?;
+            let post_id = self
+                .find_idempotent_post_id(author_id, key, ttl)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::InternalError("Idempotency key lookup failed".to_string())
+                })?;
+            return Ok((self.find_by_id(post_id).await?, false));
+        }
+
+        tx.commit().await.map_err(DomainError::database)?;
+
+        Ok((post, true))
+    }
+
+    async fn find_idempotent(
+        &self,
+        author_id: i64,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<Option<Post>, DomainError> {
+        match self.find_idempotent_post_id(author_id, key, ttl).await? {
+            Some(post_id) => Ok(Some(self.find_by_id(post_id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn create_many(
+        &self,
+        author_id: i64,
+        reqs: Vec<CreatePostRequest>,
+    ) -> Result<Vec<Post>, DomainError> {
+        // Ошибка любой вставки откатывает транзакцию при drop
+        let mut tx = self.pool.begin().await.map_err(DomainError::database)?;
+        let mut posts = Vec::with_capacity(reqs.len());
+        for req in &reqs {
+            posts.push(insert_post(&mut tx, author_id, req).await?);
+        }
+        tx.commit().await.map_err(DomainError::database)?;
+
+        Ok(posts)
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Post, DomainError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.content_format, p.published, p.published_at, p.created_at, p.updated_at, {TAGS_COLUMN}, {LIKES_COLUMN}
+            FROM posts p
+            WHERE p.id = $1
+            "#
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(DomainError::database)?;
 
         match row {
-            Some(row) => {
-                let post = Post {
-                    id: row.try_get("id")?,
-                    title: row.try_get("title")?,
-                    content: row.try_get("content")?,
-                    author_id: row.try_get("author_id")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
-                };
-                Ok(post)
-            }
+            Some(row) => post_from_row(&row),
             None => Err(DomainError::PostNotFound),
         }
     }
 
+    async fn find_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.content_format, p.published, p.published_at, p.created_at, p.updated_at, {TAGS_COLUMN}, {LIKES_COLUMN}
+            FROM posts p
+            WHERE p.id = ANY($1)
+            "#
+        ))
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(DomainError::database)?;
+
+        rows.iter().map(post_from_row).collect()
+    }
+
     async fn update(&self, id: i64, req: UpdatePostRequest) -> Result<Post, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(DomainError::database)?;
+
         let row = sqlx::query(
             r#"
             UPDATE posts
             SET
                 title = COALESCE($1, title),
                 content = COALESCE($2, content),
+                published = COALESCE($3, published),
+                published_at = CASE
+                    WHEN COALESCE($3, published) THEN COALESCE(published_at, NOW())
+                END,
+                content_format = COALESCE($4, content_format),
                 updated_at = NOW()
-            WHERE id = $3
-            RETURNING id, title, content, author_id, created_at, updated_at
+            WHERE id = $5
+              AND ($6::timestamptz IS NULL OR updated_at <= $6)
+            RETURNING id
             "#,
         )
         .bind(req.title)
         .bind(req.content)
+        .bind(req.published)
+        .bind(req.content_format.map(ContentFormat::as_str))
         .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(req.expected_updated_at)
+        .fetch_optional(&mut *tx)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(DomainError::database)?;
 
-        match row {
-            Some(row) => {
-                let post = Post {
-                    id: row.try_get("id")?,
-                    title: row.try_get("title")?,
-                    content: row.try_get("content")?,
-                    author_id: row.try_get("author_id")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
-                };
-                Ok(post)
-            }
-            None => Err(DomainError::PostNotFound),
+        if row.is_none() {
+            // Ни одной строки: поста нет или его изменили после того, как клиент его прочитал
+            let row = sqlx::query("SELECT EXISTS(SELECT 1 FROM posts WHERE id = $1) AS exists")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(DomainError::database)?;
+            return Err(if row.try_get("exists")? {
+                DomainError::Conflict("Post was modified since it was loaded".to_string())
+            } else {
+                DomainError::PostNotFound
+            });
+        }
+
+        if let Some(tags) = &req.tags {
+            replace_tags(&mut tx, id, tags).await?;
         }
+        tx.commit().await.map_err(DomainError::database)?;
+
+        // Перечитываем пост, чтобы вернуть актуальный набор тегов
+        self.find_by_id(id).await
     }
 
     async fn delete(&self, id: i64) -> Result<(), DomainError> {
@@ -129,7 +502,7 @@ impl PostRepository for PostgresPostRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(DomainError::database)?;
 
         if result.rows_affected() == 0 {
             Err(DomainError::PostNotFound)
@@ -138,75 +511,419 @@ impl PostRepository for PostgresPostRepository {
         }
     }
 
-    async fn list(&self, limit: i64, offset: i64) -> Result<(Vec<Post>, i64), DomainError> {
-        // Get total count
-        let count_row = sqlx::query("SELECT COUNT(*) as count FROM posts")
+    async fn delete_many(&self, ids: &[i64]) -> Result<u64, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(DomainError::database)?;
+        let result = sqlx::query("DELETE FROM posts WHERE id = ANY($1)")
+            .bind(ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(DomainError::database)?;
+
+        // Пост удалили параллельно после проверки прав - откатываем остальные
+        if result.rows_affected() != ids.len() as u64 {
+            tx.rollback().await.map_err(DomainError::database)?;
+            return Err(DomainError::PostNotFound);
+        }
+        tx.commit().await.map_err(DomainError::database)?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_by_author(&self, author_id: i64) -> Result<u64, DomainError> {
+        // Теги, лайки и ключи идемпотентности удаляются каскадно
+        let result = sqlx::query("DELETE FROM posts WHERE author_id = $1")
+            .bind(author_id)
+            .execute(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn count(&self, filter: &PostFilter) -> Result<i64, DomainError> {
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT COUNT(*) as count FROM posts p JOIN users u ON u.id = p.author_id",
+        );
+        push_filter(&mut query, filter);
+
+        let row = query
+            .build()
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+            .map_err(DomainError::database)?;
 
-        let total: i64 = count_row.try_get("count")?;
+        Ok(row.try_get("count")?)
+    }
+
+    async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &PostFilter,
+    ) -> Result<(Vec<Post>, i64), DomainError> {
+        // Get total count for the filtered set
+        let total = self.count(filter).await?;
 
         // Get paginated posts
-        let rows = sqlx::query(
+        let mut query = QueryBuilder::<Postgres>::new(format!(
             r#"
-            SELECT id, title, content, author_id, created_at, updated_at
-            FROM posts
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
-            "#,
-        )
+            SELECT p.id, p.title, p.content, p.author_id, p.content_format, p.published, p.published_at, p.created_at, p.updated_at, {TAGS_COLUMN}, {LIKES_COLUMN}
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            "#
+        ));
+        push_filter(&mut query, filter);
+        if let Some(cursor) = filter.after {
+            push_after(&mut query, filter.sort, cursor);
+        }
+        query
+            .push(" ORDER BY ")
+            .push(order_by(filter.sort))
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        let posts = rows
+            .iter()
+            .map(post_from_row)
+            .collect::<Result<Vec<Post>, DomainError>>()?;
+
+        Ok((posts, total))
+    }
+
+    async fn count_by_author(&self, author_id: i64) -> Result<i64, DomainError> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM posts WHERE author_id = $1")
+            .bind(author_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    async fn find_by_author(&self, author_id: i64) -> Result<Vec<Post>, DomainError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.content_format, p.published, p.published_at, p.created_at, p.updated_at, {TAGS_COLUMN}, {LIKES_COLUMN}
+            FROM posts p
+            WHERE p.author_id = $1
+            ORDER BY p.created_at DESC
+            "#
+        ))
+        .bind(author_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(DomainError::database)?;
+
+        let posts = rows
+            .iter()
+            .map(post_from_row)
+            .collect::<Result<Vec<Post>, DomainError>>()?;
+
+        Ok(posts)
+    }
+
+    async fn list_for_dashboard(
+        &self,
+        author_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64), DomainError> {
+        let count_row = sqlx::query("SELECT COUNT(*) as count FROM posts WHERE author_id = $1")
+            .bind(author_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        let total: i64 = count_row.try_get("count")?;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.content_format, p.published, p.published_at, p.created_at, p.updated_at, {TAGS_COLUMN}, {LIKES_COLUMN}
+            FROM posts p
+            WHERE p.author_id = $1
+            ORDER BY p.updated_at DESC
+            LIMIT $2 OFFSET $3
+            "#
+        ))
+        .bind(author_id)
         .bind(limit)
         .bind(offset)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(DomainError::database)?;
 
         let posts = rows
-            .into_iter()
-            .map(|row| {
-                Ok(Post {
-                    id: row.try_get("id")?,
-                    title: row.try_get("title")?,
-                    content: row.try_get("content")?,
-                    author_id: row.try_get("author_id")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
-                })
-            })
+            .iter()
+            .map(post_from_row)
             .collect::<Result<Vec<Post>, DomainError>>()?;
 
         Ok((posts, total))
     }
 
-    async fn find_by_author(&self, author_id: i64) -> Result<Vec<Post>, DomainError> {
-        let rows = sqlx::query(
+    async fn like(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        sqlx::query(
             r#"
-            SELECT id, title, content, author_id, created_at, updated_at
-            FROM posts
-            WHERE author_id = $1
-            ORDER BY created_at DESC
+            INSERT INTO post_likes (post_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (post_id, user_id) DO NOTHING
             "#,
         )
-        .bind(author_id)
-        .fetch_all(&self.pool)
+        .bind(post_id)
+        .bind(user_id)
+        .execute(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(DomainError::database)?;
 
-        let posts = rows
-            .into_iter()
+        self.count_likes(post_id).await
+    }
+
+    async fn unlike(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        sqlx::query(
+            r#"
+            DELETE FROM post_likes
+            WHERE post_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(DomainError::database)?;
+
+        self.count_likes(post_id).await
+    }
+
+    async fn find_authors(&self, author_ids: &[i64]) -> Result<Vec<AuthorSummary>, DomainError> {
+        let rows = sqlx::query("SELECT id, username, avatar_url FROM users WHERE id = ANY($1)")
+            .bind(author_ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        rows.into_iter()
             .map(|row| {
-                Ok(Post {
+                Ok(AuthorSummary {
                     id: row.try_get("id")?,
-                    title: row.try_get("title")?,
-                    content: row.try_get("content")?,
-                    author_id: row.try_get("author_id")?,
-                    created_at: row.try_get("created_at")?,
-                    updated_at: row.try_get("updated_at")?,
+                    username: row.try_get("username")?,
+                    avatar_url: row.try_get("avatar_url")?,
                 })
             })
-            .collect::<Result<Vec<Post>, DomainError>>()?;
+            .collect()
+    }
 
-        Ok(posts)
+    async fn rename_tag(&self, old_name: &str, new_name: &str) -> Result<Vec<i64>, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(DomainError::database)?;
+
+        // Блокировка строки тега не дает двум переименованиям одного тега пересечься
+        let old_id: Option<i64> = sqlx::query("SELECT id FROM tags WHERE name = $1 FOR UPDATE")
+            .bind(old_name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DomainError::database)?
+            .map(|row| row.try_get("id"))
+            .transpose()?;
+        let Some(old_id) = old_id else {
+            return Ok(Vec::new());
+        };
+
+        let post_ids: Vec<i64> =
+            sqlx::query("SELECT post_id FROM post_tags WHERE tag_id = $1 ORDER BY post_id")
+                .bind(old_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(DomainError::database)?
+                .iter()
+                .map(|row| row.try_get("post_id"))
+                .collect::<Result<_, _>>()?;
+
+        let new_id: Option<i64> = sqlx::query("SELECT id FROM tags WHERE name = $1 FOR UPDATE")
+            .bind(new_name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DomainError::database)?
+            .map(|row| row.try_get("id"))
+            .transpose()?;
+
+        match new_id {
+            // Нового тега еще нет - достаточно сменить имя
+            None => {
+                sqlx::query("UPDATE tags SET name = $2 WHERE id = $1")
+                    .bind(old_id)
+                    .bind(new_name)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(DomainError::database)?;
+            }
+            // Слияние: посты получают существующий тег (без дублей), старый тег
+            // удаляется вместе со своими связями (ON DELETE CASCADE)
+            Some(new_id) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO post_tags (post_id, tag_id)
+                    SELECT post_id, $2 FROM post_tags WHERE tag_id = $1
+                    ON CONFLICT (post_id, tag_id) DO NOTHING
+                    "#,
+                )
+                .bind(old_id)
+                .bind(new_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(DomainError::database)?;
+
+                sqlx::query("DELETE FROM tags WHERE id = $1")
+                    .bind(old_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(DomainError::database)?;
+            }
+        }
+
+        tx.commit().await.map_err(DomainError::database)?;
+
+        Ok(post_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::test_pool;
+
+    // Тесты делят одну базу: имена пользователей и тегов уникальны для каждого запуска
+    fn unique(prefix: &str) -> String {
+        format!(
+            "{}-{}",
+            prefix,
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        )
+    }
+
+    async fn create_author(pool: &PgPool) -> i64 {
+        let username = unique("author");
+        sqlx::query(
+            "INSERT INTO users (username, email, password_hash) VALUES ($1, $2, 'x') RETURNING id",
+        )
+        .bind(&username)
+        .bind(format!("{}@example.com", username))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .get("id")
+    }
+
+    fn new_post(tags: &[&str]) -> CreatePostRequest {
+        CreatePostRequest {
+            title: "Title".to_string(),
+            content: "Content".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            content_format: ContentFormat::Plain,
+            published: true,
+            published_at: None,
+        }
+    }
+
+    async fn tag_exists(pool: &PgPool, name: &str) -> bool {
+        sqlx::query("SELECT EXISTS(SELECT 1 FROM tags WHERE name = $1) AS exists")
+            .bind(name)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            .get("exists")
+    }
+
+    #[tokio::test]
+    async fn rename_tag_merges_into_existing_tag() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let repo = PostgresPostRepository::new(pool.clone());
+        let author = create_author(&pool).await;
+        let (old, new, other) = (unique("javascirpt"), unique("javascript"), unique("web"));
+
+        let both = repo.create(author, new_post(&[&old, &new])).await.unwrap();
+        let only_old = repo
+            .create(author, new_post(&[&old, &other]))
+            .await
+            .unwrap();
+        let only_new = repo.create(author, new_post(&[&new])).await.unwrap();
+
+        let renamed = repo.rename_tag(&old, &new).await.unwrap();
+        assert_eq!(renamed, vec![both.id, only_old.id]);
+
+        let mut expected = vec![new.clone(), other.clone()];
+        expected.sort();
+        assert_eq!(
+            repo.find_by_id(both.id).await.unwrap().tags,
+            vec![new.clone()]
+        );
+        assert_eq!(repo.find_by_id(only_old.id).await.unwrap().tags, expected);
+        assert_eq!(
+            repo.find_by_id(only_new.id).await.unwrap().tags,
+            vec![new.clone()]
+        );
+        assert!(!tag_exists(&pool, &old).await);
+
+        repo.delete_by_author(author).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rename_tag_to_unused_name_renames_in_place() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let repo = PostgresPostRepository::new(pool.clone());
+        let author = create_author(&pool).await;
+        let (old, new) = (unique("rsut"), unique("rust"));
+
+        let post = repo.create(author, new_post(&[&old])).await.unwrap();
+
+        assert_eq!(repo.rename_tag(&old, &new).await.unwrap(), vec![post.id]);
+        assert_eq!(
+            repo.find_by_id(post.id).await.unwrap().tags,
+            vec![new.clone()]
+        );
+        assert!(!tag_exists(&pool, &old).await);
+
+        // Неизвестный тег - ничего не меняется
+        assert!(repo.rename_tag(&old, &new).await.unwrap().is_empty());
+
+        repo.delete_by_author(author).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn find_idempotent_returns_post_created_with_the_key() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let repo = PostgresPostRepository::new(pool.clone());
+        let author = create_author(&pool).await;
+        let ttl = Duration::from_secs(60);
+
+        assert!(repo
+            .find_idempotent(author, "key-1", ttl)
+            .await
+            .unwrap()
+            .is_none());
+        let (post, created) = repo
+            .create_idempotent(author, new_post(&[]), "key-1", ttl)
+            .await
+            .unwrap();
+        assert!(created);
+
+        let found = repo.find_idempotent(author, "key-1", ttl).await.unwrap();
+        assert_eq!(found.map(|p| p.id), Some(post.id));
+        assert!(repo
+            .find_idempotent(author, "key-2", ttl)
+            .await
+            .unwrap()
+            .is_none());
     }
 }
@@ -0,0 +1,254 @@
+use crate::data::comment_repository::CommentRepository;
+use crate::data::post_repository::PostRepository;
+use crate::data::user_repository::UserRepository;
+use crate::domain::post::{AuthorSummary, CreatePostRequest, PostFilter, UpdatePostRequest};
+use crate::domain::user::{RegisterUserRequest, UpdateUserRequest};
+use crate::domain::{Comment, DomainError, Post, User};
+use async_trait::async_trait;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Repository wrapper that warns about calls slower than `threshold`.
+/// Only installed when `SLOW_QUERY_MS` is set, so without it queries are not timed at all
+pub struct SlowQueryLog<R> {
+    inner: R,
+    threshold: Duration,
+}
+
+impl<R> SlowQueryLog<R> {
+    pub fn new(inner: R, threshold: Duration) -> Self {
+        Self { inner, threshold }
+    }
+
+    async fn timed<T>(&self, name: &'static str, query: impl Future<Output = T>) -> T {
+        let started = Instant::now();
+        let result = query.await;
+        let elapsed = started.elapsed();
+
+        if elapsed > self.threshold {
+            tracing::warn!(
+                "Slow query {}: {} ms (threshold {} ms)",
+                name,
+                elapsed.as_millis(),
+                self.threshold.as_millis()
+            );
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl<R: PostRepository> PostRepository for SlowQueryLog<R> {
+    async fn create(&self, author_id: i64, req: CreatePostRequest) -> Result<Post, DomainError> {
+        self.timed("posts.create", self.inner.create(author_id, req))
+            .await
+    }
+
+    async fn create_idempotent(
+        &self,
+        author_id: i64,
+        req: CreatePostRequest,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<(Post, bool), DomainError> {
+        self.timed(
+            "posts.create_idempotent",
+            self.inner.create_idempotent(author_id, req, key, ttl),
+        )
+        .await
+    }
+
+    async fn find_idempotent(
+        &self,
+        author_id: i64,
+        key: &str,
+        ttl: Duration,
+    ) -> Result<Option<Post>, DomainError> {
+        self.timed(
+            "posts.find_idempotent",
+            self.inner.find_idempotent(author_id, key, ttl),
+        )
+        .await
+    }
+
+    async fn create_many(
+        &self,
+        author_id: i64,
+        reqs: Vec<CreatePostRequest>,
+    ) -> Result<Vec<Post>, DomainError> {
+        self.timed("posts.create_many", self.inner.create_many(author_id, reqs))
+            .await
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Post, DomainError> {
+        self.timed("posts.find_by_id", self.inner.find_by_id(id))
+            .await
+    }
+
+    async fn find_by_ids(&self, ids: &[i64]) -> Result<Vec<Post>, DomainError> {
+        self.timed("posts.find_by_ids", self.inner.find_by_ids(ids))
+            .await
+    }
+
+    async fn update(&self, id: i64, req: UpdatePostRequest) -> Result<Post, DomainError> {
+        self.timed("posts.update", self.inner.update(id, req)).await
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), DomainError> {
+        self.timed("posts.delete", self.inner.delete(id)).await
+    }
+
+    async fn delete_many(&self, ids: &[i64]) -> Result<u64, DomainError> {
+        self.timed("posts.delete_many", self.inner.delete_many(ids))
+            .await
+    }
+
+    async fn delete_by_author(&self, author_id: i64) -> Result<u64, DomainError> {
+        self.timed(
+            "posts.delete_by_author",
+            self.inner.delete_by_author(author_id),
+        )
+        .await
+    }
+
+    async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &PostFilter,
+    ) -> Result<(Vec<Post>, i64), DomainError> {
+        self.timed("posts.list", self.inner.list(limit, offset, filter))
+            .await
+    }
+
+    async fn count(&self, filter: &PostFilter) -> Result<i64, DomainError> {
+        self.timed("posts.count", self.inner.count(filter)).await
+    }
+
+    async fn find_by_author(&self, author_id: i64) -> Result<Vec<Post>, DomainError> {
+        self.timed("posts.find_by_author", self.inner.find_by_author(author_id))
+            .await
+    }
+
+    async fn count_by_author(&self, author_id: i64) -> Result<i64, DomainError> {
+        self.timed(
+            "posts.count_by_author",
+            self.inner.count_by_author(author_id),
+        )
+        .await
+    }
+
+    async fn list_for_dashboard(
+        &self,
+        author_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64), DomainError> {
+        self.timed(
+            "posts.list_for_dashboard",
+            self.inner.list_for_dashboard(author_id, limit, offset),
+        )
+        .await
+    }
+
+    async fn like(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        self.timed("posts.like", self.inner.like(post_id, user_id))
+            .await
+    }
+
+    async fn unlike(&self, post_id: i64, user_id: i64) -> Result<i64, DomainError> {
+        self.timed("posts.unlike", self.inner.unlike(post_id, user_id))
+            .await
+    }
+
+    async fn find_authors(&self, author_ids: &[i64]) -> Result<Vec<AuthorSummary>, DomainError> {
+        self.timed("posts.find_authors", self.inner.find_authors(author_ids))
+            .await
+    }
+
+    async fn rename_tag(&self, old_name: &str, new_name: &str) -> Result<Vec<i64>, DomainError> {
+        self.timed(
+            "posts.rename_tag",
+            self.inner.rename_tag(old_name, new_name),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<R: UserRepository> UserRepository for SlowQueryLog<R> {
+    async fn create(
+        &self,
+        req: RegisterUserRequest,
+        password_hash: String,
+    ) -> Result<User, DomainError> {
+        self.timed("users.create", self.inner.create(req, password_hash))
+            .await
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<User, DomainError> {
+        self.timed(
+            "users.find_by_username",
+            self.inner.find_by_username(username),
+        )
+        .await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<User, DomainError> {
+        self.timed("users.find_by_email", self.inner.find_by_email(email))
+            .await
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<User, DomainError> {
+        self.timed("users.find_by_id", self.inner.find_by_id(id))
+            .await
+    }
+
+    async fn update_profile(&self, id: i64, req: UpdateUserRequest) -> Result<User, DomainError> {
+        self.timed("users.update_profile", self.inner.update_profile(id, req))
+            .await
+    }
+
+    async fn update_password(&self, id: i64, password_hash: &str) -> Result<(), DomainError> {
+        self.timed(
+            "users.update_password",
+            self.inner.update_password(id, password_hash),
+        )
+        .await
+    }
+
+    async fn touch_last_login(&self, id: i64) -> Result<(), DomainError> {
+        self.timed("users.touch_last_login", self.inner.touch_last_login(id))
+            .await
+    }
+}
+
+#[async_trait]
+impl<R: CommentRepository> CommentRepository for SlowQueryLog<R> {
+    async fn create(
+        &self,
+        post_id: i64,
+        author_id: i64,
+        content: &str,
+    ) -> Result<Comment, DomainError> {
+        self.timed(
+            "comments.create",
+            self.inner.create(post_id, author_id, content),
+        )
+        .await
+    }
+
+    async fn list_by_post(
+        &self,
+        post_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Comment>, i64), DomainError> {
+        self.timed(
+            "comments.list_by_post",
+            self.inner.list_by_post(post_id, limit, offset),
+        )
+        .await
+    }
+}
@@ -1,6 +1,7 @@
-use crate::domain::user::RegisterUserRequest;
+use crate::domain::user::{RegisterUserRequest, UpdateUserRequest};
 use crate::domain::{DomainError, User};
 use async_trait::async_trait;
+use sqlx::postgres::PgRow;
 use sqlx::{PgPool, Row};
 
 #[async_trait]
@@ -10,10 +11,59 @@ pub trait UserRepository: Send + Sync {
         req: RegisterUserRequest,
         password_hash: String,
     ) -> Result<User, DomainError>;
+    /// Case-insensitive: "Alice" finds "alice"
     async fn find_by_username(&self, username: &str) -> Result<User, DomainError>;
+    /// Case-insensitive, like `find_by_username`
     async fn find_by_email(&self, email: &str) -> Result<User, DomainError>;
-    #[allow(dead_code)]
     async fn find_by_id(&self, id: i64) -> Result<User, DomainError>;
+    /// Apply profile changes; a taken email yields `UserAlreadyExists`
+    async fn update_profile(&self, id: i64, req: UpdateUserRequest) -> Result<User, DomainError>;
+    async fn update_password(&self, id: i64, password_hash: &str) -> Result<(), DomainError>;
+    /// Set `last_login_at` to now
+    async fn touch_last_login(&self, id: i64) -> Result<(), DomainError>;
+}
+
+const USER_COLUMNS: &str =
+    "id, username, email, password_hash, full_name, bio, avatar_url, created_at, role, last_login_at";
+
+fn user_from_row(row: &PgRow) -> Result<User, DomainError> {
+    Ok(User {
+        id: row.try_get("id")?,
+        username: row.try_get("username")?,
+        email: row.try_get("email")?,
+        password_hash: row.try_get("password_hash")?,
+        full_name: row.try_get("full_name")?,
+        bio: row.try_get("bio")?,
+        avatar_url: row.try_get("avatar_url")?,
+        created_at: row.try_get("created_at")?,
+        role: row.try_get::<String, _>("role")?.parse()?,
+        last_login_at: row.try_get("last_login_at")?,
+    })
+}
+
+/// SQLSTATE нарушения уникальности
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// Уникальные ограничения таблицы users: сами колонки и их версии без учета регистра
+const USER_UNIQUE_CONSTRAINTS: &[&str] = &[
+    "users_username_key",
+    "users_email_key",
+    "idx_users_username_lower",
+    "idx_users_email_lower",
+];
+
+// Нарушение уникальности (username / email) -> UserAlreadyExists
+fn map_write_error(e: sqlx::Error) -> DomainError {
+    if let sqlx::Error::Database(db) = &e {
+        if db.code().as_deref() == Some(UNIQUE_VIOLATION)
+            && db
+                .constraint()
+                .is_some_and(|name| USER_UNIQUE_CONSTRAINTS.contains(&name))
+        {
+            return DomainError::UserAlreadyExists;
+        }
+    }
+    DomainError::database(e)
 }
 
 pub struct PostgresUserRepository {
@@ -33,13 +83,14 @@ impl UserRepository for PostgresUserRepository {
         req: RegisterUserRequest,
         password_hash: String,
     ) -> Result<User, DomainError> {
-        let row = sqlx::query(
+        let row = sqlx::query(&format!(
             r#"
             INSERT INTO users (username, email, password_hash, created_at)
             VALUES ($1, $2, $3, NOW())
-            RETURNING id, username, email, password_hash, created_at
+            RETURNING {}
             "#,
-        )
+            USER_COLUMNS
+        ))
         .bind(&req.username)
         .bind(&req.email)
         .bind(&password_hash)
@@ -47,105 +98,182 @@ impl UserRepository for PostgresUserRepository {
         .await
         .map_err(|e| {
             tracing::error!("Failed to create user: {}", e);
-            if e.to_string().contains("duplicate key") {
-                DomainError::UserAlreadyExists
-            } else {
-                DomainError::DatabaseError(e.to_string())
-            }
+            map_write_error(e)
         })?;
 
-        let user = User {
-            id: row.try_get("id")?,
-            username: row.try_get("username")?,
-            email: row.try_get("email")?,
-            password_hash: row.try_get("password_hash")?,
-            created_at: row.try_get("created_at")?,
-        };
-
-        Ok(user)
+        user_from_row(&row)
     }
 
     async fn find_by_username(&self, username: &str) -> Result<User, DomainError> {
-        let row = sqlx::query(
-            r#"
-            SELECT id, username, email, password_hash, created_at
-            FROM users
-            WHERE username = $1
-            "#,
-        )
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM users WHERE LOWER(username) = LOWER($1)",
+            USER_COLUMNS
+        ))
         .bind(username)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(DomainError::database)?;
 
         match row {
-            Some(row) => {
-                let user = User {
-                    id: row.try_get("id")?,
-                    username: row.try_get("username")?,
-                    email: row.try_get("email")?,
-                    password_hash: row.try_get("password_hash")?,
-                    created_at: row.try_get("created_at")?,
-                };
-                Ok(user)
-            }
+            Some(row) => user_from_row(&row),
             None => Err(DomainError::UserNotFound),
         }
     }
 
     async fn find_by_email(&self, email: &str) -> Result<User, DomainError> {
-        let row = sqlx::query(
-            r#"
-            SELECT id, username, email, password_hash, created_at
-            FROM users
-            WHERE email = $1
-            "#,
-        )
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM users WHERE LOWER(email) = LOWER($1)",
+            USER_COLUMNS
+        ))
         .bind(email)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(DomainError::database)?;
 
         match row {
-            Some(row) => {
-                let user = User {
-                    id: row.try_get("id")?,
-                    username: row.try_get("username")?,
-                    email: row.try_get("email")?,
-                    password_hash: row.try_get("password_hash")?,
-                    created_at: row.try_get("created_at")?,
-                };
-                Ok(user)
-            }
+            Some(row) => user_from_row(&row),
             None => Err(DomainError::UserNotFound),
         }
     }
 
     async fn find_by_id(&self, id: i64) -> Result<User, DomainError> {
-        let row = sqlx::query(
+        let row = sqlx::query(&format!("SELECT {} FROM users WHERE id = $1", USER_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        match row {
+            Some(row) => user_from_row(&row),
+            None => Err(DomainError::UserNotFound),
+        }
+    }
+
+    async fn update_profile(&self, id: i64, req: UpdateUserRequest) -> Result<User, DomainError> {
+        // NULL - оставить как есть, пустая строка - очистить поле
+        let row = sqlx::query(&format!(
             r#"
-            SELECT id, username, email, password_hash, created_at
-            FROM users
+            UPDATE users
+            SET email = COALESCE($2, email),
+                full_name = CASE WHEN $3::TEXT IS NULL THEN full_name ELSE NULLIF($3, '') END,
+                bio = CASE WHEN $4::TEXT IS NULL THEN bio ELSE NULLIF($4, '') END,
+                avatar_url = CASE WHEN $5::TEXT IS NULL THEN avatar_url ELSE NULLIF($5, '') END
             WHERE id = $1
+            RETURNING {}
             "#,
-        )
+            USER_COLUMNS
+        ))
         .bind(id)
+        .bind(&req.email)
+        .bind(&req.full_name)
+        .bind(&req.bio)
+        .bind(&req.avatar_url)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(|e| {
+            tracing::error!("Failed to update profile of user {}: {}", id, e);
+            map_write_error(e)
+        })?;
 
         match row {
-            Some(row) => {
-                let user = User {
-                    id: row.try_get("id")?,
-                    username: row.try_get("username")?,
-                    email: row.try_get("email")?,
-                    password_hash: row.try_get("password_hash")?,
-                    created_at: row.try_get("created_at")?,
-                };
-                Ok(user)
-            }
+            Some(row) => user_from_row(&row),
             None => Err(DomainError::UserNotFound),
         }
     }
+
+    async fn update_password(&self, id: i64, password_hash: &str) -> Result<(), DomainError> {
+        let result = sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+            .bind(id)
+            .bind(password_hash)
+            .execute(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::UserNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn touch_last_login(&self, id: i64) -> Result<(), DomainError> {
+        sqlx::query("UPDATE users SET last_login_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(DomainError::database)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::test_pool;
+
+    // Тесты делят одну базу: имена уникальны для каждого запуска
+    fn unique(prefix: &str) -> String {
+        format!(
+            "{}{}",
+            prefix,
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        )
+    }
+
+    fn register(username: &str, email: &str) -> RegisterUserRequest {
+        RegisterUserRequest {
+            username: username.to_string(),
+            email: email.to_string(),
+            password: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_rejects_username_differing_only_in_case() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let repo = PostgresUserRepository::new(pool);
+        let name = unique("Alice");
+
+        repo.create(
+            register(&name, &format!("{}@example.com", name)),
+            "x".into(),
+        )
+        .await
+        .unwrap();
+        let err = repo
+            .create(
+                register(&name.to_lowercase(), &format!("other-{}@example.com", name)),
+                "x".into(),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DomainError::UserAlreadyExists), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn create_rejects_email_differing_only_in_case() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let repo = PostgresUserRepository::new(pool);
+        let name = unique("bob");
+        let email = format!("{}@example.com", name);
+
+        repo.create(register(&name, &email), "x".into())
+            .await
+            .unwrap();
+        let err = repo
+            .create(
+                register(&format!("{}-2", name), &email.to_uppercase()),
+                "x".into(),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DomainError::UserAlreadyExists), "{:?}", err);
+    }
 }
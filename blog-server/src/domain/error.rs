@@ -20,34 +20,139 @@ pub enum DomainError {
     #[error("Database error: {0}")]
     DatabaseError(String),
 
+    /// No free connection within the pool acquire timeout, or the connection
+    /// to Postgres is lost; the request can be retried later
+    #[error("Database is temporarily unavailable")]
+    DatabaseUnavailable,
+
     #[error("Validation error: {0}")]
     ValidationError(String),
 
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("Token expired")]
+    TokenExpired,
+
+    #[error("Invalid token: {0}")]
+    TokenInvalid(String),
+
     #[error("Internal server error: {0}")]
     InternalError(String),
+
+    /// The resource changed since the client read it
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Too many requests, retry after {0} seconds")]
+    RateLimited(u64),
 }
 
 impl DomainError {
+    /// Map a sqlx error; an exhausted pool or a lost connection becomes
+    /// `DatabaseUnavailable` (503), query errors stay `DatabaseError` (500)
+    pub fn database(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::PoolTimedOut => {
+                tracing::warn!("Timed out waiting for a database connection");
+                Self::DatabaseUnavailable
+            }
+            _ if is_connection_error(&err) => {
+                tracing::error!("Database connection lost: {}", err);
+                Self::DatabaseUnavailable
+            }
+            _ => Self::DatabaseError(err.to_string()),
+        }
+    }
+
+    /// Point a validation error at an item of a batch request (`posts[3]: ...`);
+    /// other errors are returned as is
+    pub fn in_batch(self, field: &str, index: usize) -> Self {
+        match self {
+            Self::ValidationError(msg) => {
+                Self::ValidationError(format!("{}[{}]: {}", field, index, msg))
+            }
+            other => other,
+        }
+    }
+
+    /// Stable machine-readable code sent to clients along with the message
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UserNotFound => "USER_NOT_FOUND",
+            Self::UserAlreadyExists => "USER_ALREADY_EXISTS",
+            Self::InvalidCredentials => "INVALID_CREDENTIALS",
+            Self::PostNotFound => "POST_NOT_FOUND",
+            Self::Forbidden => "FORBIDDEN",
+            Self::ValidationError(_) => "VALIDATION_ERROR",
+            Self::Unauthorized(_) => "UNAUTHORIZED",
+            Self::TokenExpired => "TOKEN_EXPIRED",
+            Self::TokenInvalid(_) => "TOKEN_INVALID",
+            Self::Conflict(_) => "CONFLICT",
+            Self::RateLimited(_) => "RATE_LIMITED",
+            Self::DatabaseUnavailable => "SERVICE_UNAVAILABLE",
+            // Детали внутренних ошибок клиенту не раскрываем
+            Self::DatabaseError(_) | Self::InternalError(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Name of the variant, used as the `variant` label of the error metrics
+    pub fn variant(&self) -> &'static str {
+        match self {
+            Self::UserNotFound => "UserNotFound",
+            Self::UserAlreadyExists => "UserAlreadyExists",
+            Self::InvalidCredentials => "InvalidCredentials",
+            Self::PostNotFound => "PostNotFound",
+            Self::Forbidden => "Forbidden",
+            Self::DatabaseError(_) => "DatabaseError",
+            Self::DatabaseUnavailable => "DatabaseUnavailable",
+            Self::ValidationError(_) => "ValidationError",
+            Self::Unauthorized(_) => "Unauthorized",
+            Self::TokenExpired => "TokenExpired",
+            Self::TokenInvalid(_) => "TokenInvalid",
+            Self::InternalError(_) => "InternalError",
+            Self::Conflict(_) => "Conflict",
+            Self::RateLimited(_) => "RateLimited",
+        }
+    }
+
     pub fn to_status_code(&self) -> u16 {
         match self {
             Self::UserNotFound | Self::PostNotFound => 404,
-            Self::UserAlreadyExists => 409,
-            Self::InvalidCredentials | Self::Unauthorized(_) => 401,
+            Self::UserAlreadyExists | Self::Conflict(_) => 409,
+            Self::InvalidCredentials
+            | Self::Unauthorized(_)
+            | Self::TokenExpired
+            | Self::TokenInvalid(_) => 401,
             Self::Forbidden => 403,
             Self::ValidationError(_) => 400,
+            Self::RateLimited(_) => 429,
+            Self::DatabaseUnavailable => 503,
             Self::DatabaseError(_) | Self::InternalError(_) => 500,
         }
     }
 }
 
+// Ошибка соединения, а не запроса: сеть/TLS, закрытый пул или Postgres,
+// который завершается или еще не принимает подключения (SQLSTATE 08xxx, 57P01-57P03)
+fn is_connection_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_)
+        | sqlx::Error::Tls(_)
+        | sqlx::Error::PoolClosed
+        | sqlx::Error::WorkerCrashed => true,
+        sqlx::Error::Database(db_err) => db_err.code().is_some_and(|code| {
+            code.starts_with("08") || matches!(&*code, "57P01" | "57P02" | "57P03")
+        }),
+        _ => false,
+    }
+}
+
 impl From<sqlx::Error> for DomainError {
     fn from(err: sqlx::Error) -> Self {
         match err {
             sqlx::Error::RowNotFound => Self::UserNotFound,
-            _ => Self::DatabaseError(err.to_string()),
+            _ => Self::database(err),
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::domain::DomainError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -7,30 +8,384 @@ pub struct Post {
     pub title: String,
     pub content: String,
     pub author_id: i64,
+    pub tags: Vec<String>,
+    pub likes_count: i64,
+    pub content_format: ContentFormat,
+    pub published: bool,
+    pub published_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// How clients should render the post content
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    // Посты, созданные до появления форматов, - обычный текст
+    #[default]
+    Plain,
+    Markdown,
+}
+
+impl ContentFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Markdown => "markdown",
+        }
+    }
+}
+
+impl std::str::FromStr for ContentFormat {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "plain" => Ok(Self::Plain),
+            "markdown" => Ok(Self::Markdown),
+            other => Err(DomainError::ValidationError(format!(
+                "Unknown content format '{}', expected one of: plain, markdown",
+                other
+            ))),
+        }
+    }
+}
+
+fn default_published() -> bool {
+    true
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreatePostRequest {
     pub title: String,
     pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub content_format: ContentFormat,
+    /// `false` сохраняет пост как черновик
+    #[serde(default = "default_published")]
+    pub published: bool,
+    /// Отложенная публикация; по умолчанию — момент создания
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdatePostRequest {
     pub title: Option<String>,
     pub content: Option<String>,
+    /// `None` оставляет теги без изменений, `Some(vec![])` удаляет все теги
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub content_format: Option<ContentFormat>,
+    #[serde(default)]
+    pub published: Option<bool>,
+    /// `updated_at` of the version the client edited; a newer post yields `Conflict`
+    #[serde(default)]
+    pub expected_updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize)]
+/// Order of the post list; the default shows the newest posts first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PostSort {
+    #[default]
+    CreatedDesc,
+    CreatedAsc,
+    TitleAsc,
+    UpdatedDesc,
+}
+
+impl std::str::FromStr for PostSort {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "created_desc" | "newest" => Ok(Self::CreatedDesc),
+            "created_asc" | "oldest" => Ok(Self::CreatedAsc),
+            "title_asc" | "title" => Ok(Self::TitleAsc),
+            "updated_desc" | "updated" => Ok(Self::UpdatedDesc),
+            other => Err(DomainError::ValidationError(format!(
+                "Unknown sort '{}', expected one of: created_desc, created_asc, title_asc, updated_desc",
+                other
+            ))),
+        }
+    }
+}
+
+impl PostSort {
+    /// Keyset (cursor) pagination is possible only for orders by (created_at, id)
+    pub fn supports_cursor(self) -> bool {
+        matches!(self, Self::CreatedDesc | Self::CreatedAsc)
+    }
+}
+
+/// Position in a list ordered by (created_at, id), used for keyset pagination.
+/// Clients see it as an opaque string: `<created_at in microseconds>_<id>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: i64,
+}
+
+impl PostCursor {
+    pub fn after(post: &Post) -> Self {
+        Self {
+            created_at: post.created_at,
+            id: post.id,
+        }
+    }
+}
+
+impl std::fmt::Display for PostCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+}
+
+impl std::str::FromStr for PostCursor {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DomainError::ValidationError("Invalid cursor".to_string());
+
+        let (micros, id) = s.trim().split_once('_').ok_or_else(invalid)?;
+        let created_at = micros
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let id = id.parse().map_err(|_| invalid())?;
+
+        Ok(Self { created_at, id })
+    }
+}
+
+/// Optional filters applied when listing posts
+#[derive(Debug, Clone, Default)]
+pub struct PostFilter {
+    pub author_id: Option<i64>,
+    pub author_username: Option<String>,
+    /// Case-insensitive substring match on title or content
+    pub search: Option<String>,
+    /// Exact (normalized) tag name
+    pub tag: Option<String>,
+    /// Author whose drafts and scheduled posts are listed along with published ones
+    pub drafts_visible_to: Option<i64>,
+    pub sort: PostSort,
+    /// Keyset pagination: only posts after this position in `sort` order (replaces offset)
+    pub after: Option<PostCursor>,
+    /// Only posts created at or after this moment
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only posts created at or before this moment
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// Parses an RFC3339 bound of the `created_at` range; empty string means "not set"
+pub fn parse_created_bound(
+    name: &str,
+    value: Option<&str>,
+) -> Result<Option<DateTime<Utc>>, DomainError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(value) => DateTime::parse_from_rfc3339(value)
+            .map(|dt| Some(dt.with_timezone(&Utc)))
+            .map_err(|_| {
+                DomainError::ValidationError(format!(
+                    "Invalid '{}' date, expected RFC3339 (e.g. 2025-01-31T00:00:00Z)",
+                    name
+                ))
+            }),
+        None => Ok(None),
+    }
+}
+
+/// Page size of list requests that don't specify one
+pub const DEFAULT_PAGE_SIZE: i64 = 10;
+/// Largest page size any list request accepts
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// Pagination rules shared by HTTP and gRPC: a missing limit means
+/// `DEFAULT_PAGE_SIZE`, a missing offset means 0; a limit outside
+/// 1..=`MAX_PAGE_SIZE` or a negative offset is rejected, never clamped
+pub fn pagination(limit: Option<i64>, offset: Option<i64>) -> Result<(i64, i64), DomainError> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let offset = offset.unwrap_or(0);
+
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(DomainError::ValidationError(format!(
+            "Limit must be between 1 and {}",
+            MAX_PAGE_SIZE
+        )));
+    }
+    if offset < 0 {
+        return Err(DomainError::ValidationError(
+            "Offset cannot be negative".to_string(),
+        ));
+    }
+    Ok((limit, offset))
+}
+
+/// Offset of a 1-based page number (gRPC); page 0 is the first page, as if unset
+pub fn page_offset(page: i64, limit: i64) -> Result<i64, DomainError> {
+    if page < 0 {
+        return Err(DomainError::ValidationError(
+            "Page cannot be negative".to_string(),
+        ));
+    }
+    Ok((page.max(1) - 1) * limit)
+}
+
+/// One page of the post list
+#[derive(Debug)]
+pub struct PostPage {
+    pub posts: Vec<PostResponse>,
+    /// Size of the whole filtered list
+    pub total: i64,
+    /// Cursor of the next page; `None` on the last page or when the sort does not support cursors
+    pub next_cursor: Option<String>,
+}
+
+impl PostPage {
+    /// Drop the full content of every post, leaving the excerpt (`?fields=summary`)
+    pub fn into_summary(mut self) -> Self {
+        for post in &mut self.posts {
+            post.content = String::new();
+        }
+        self
+    }
+}
+
+/// Outcome of one item of a best-effort batch request (`best_effort: true`)
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchItemResult {
+    /// Position of the item in the request
+    pub index: usize,
+    pub ok: bool,
+    /// Post created, found or deleted for this item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchItemResult {
+    pub fn success(index: usize, id: i64) -> Self {
+        Self {
+            index,
+            ok: true,
+            id: Some(id),
+            error: None,
+        }
+    }
+
+    /// Failed item; `id` is known for lookups and deletes, not for failed creates
+    pub fn failure(index: usize, id: Option<i64>, err: &DomainError) -> Self {
+        Self {
+            index,
+            ok: false,
+            id,
+            error: Some(err.to_string()),
+        }
+    }
+}
+
+/// Beginning of the content for feeds: whitespace collapsed, cut on a word
+/// boundary to at most `max_chars` characters, with "..." when something was cut
+pub fn excerpt(content: &str, max_chars: usize) -> String {
+    let mut excerpt = String::new();
+    let mut length = 0;
+
+    for word in content.split_whitespace() {
+        let word_length = word.chars().count();
+        let separator = usize::from(length > 0);
+        if length + separator + word_length > max_chars {
+            // Одно слово длиннее лимита режется посимвольно
+            if length == 0 {
+                excerpt.extend(word.chars().take(max_chars));
+            }
+            excerpt.push_str("...");
+            return excerpt;
+        }
+        if separator > 0 {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+        length += separator + word_length;
+    }
+
+    excerpt
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct PostResponse {
     pub id: i64,
     pub title: String,
+    /// Пустой только в списках с `?fields=summary` - у сохраненных постов контент не пустой
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub content: String,
+    /// Set in list results only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    /// Content length in characters, so feeds can show size without the full text
+    pub content_length: i64,
     pub author_id: i64,
+    pub tags: Vec<String>,
+    pub likes_count: i64,
+    pub content_format: ContentFormat,
+    pub published: bool,
+    pub published_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// The post was changed after creation (`updated_at != created_at`), for an "edited" badge
+    pub edited: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permalink: Option<String>,
+    /// Embedded only on request (`?include=author`) to avoid the extra lookup on hot paths
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<AuthorSummary>,
+}
+
+/// Public part of the author profile embedded into a post
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorSummary {
+    pub id: i64,
+    pub username: String,
+    pub avatar_url: Option<String>,
+}
+
+/// Publication state of a post as shown on the author's dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    Draft,
+    Published,
+    Scheduled,
+}
+
+impl PostStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Published => "published",
+            Self::Scheduled => "scheduled",
+        }
+    }
+}
+
+/// Post entry of the author's dashboard
+#[derive(Debug, Serialize)]
+pub struct DashboardPost {
+    #[serde(flatten)]
+    pub post: PostResponse,
+    pub status: PostStatus,
+}
+
+impl PostResponse {
+    /// Visible to everyone; drafts and scheduled posts are seen only by their author
+    pub fn is_public(&self) -> bool {
+        self.published && !matches!(self.published_at, Some(at) if at > Utc::now())
+    }
 }
 
 impl From<Post> for PostResponse {
@@ -38,15 +393,38 @@ impl From<Post> for PostResponse {
         Self {
             id: post.id,
             title: post.title,
+            content_length: post.content.chars().count() as i64,
             content: post.content,
+            excerpt: None,
             author_id: post.author_id,
+            tags: post.tags,
+            likes_count: post.likes_count,
+            content_format: post.content_format,
+            published: post.published,
+            published_at: post.published_at,
+            edited: post.updated_at != post.created_at,
             created_at: post.created_at,
             updated_at: post.updated_at,
+            permalink: None,
+            author: None,
         }
     }
 }
 
 impl Post {
+    pub fn status(&self) -> PostStatus {
+        match self.published_at {
+            _ if !self.published => PostStatus::Draft,
+            Some(at) if at > Utc::now() => PostStatus::Scheduled,
+            _ => PostStatus::Published,
+        }
+    }
+
+    /// Visible to everyone (published and not scheduled for the future)
+    pub fn is_public(&self) -> bool {
+        self.status() == PostStatus::Published
+    }
+
     #[allow(dead_code)]
     pub fn new(title: String, content: String, author_id: i64) -> Self {
         let now = Utc::now();
@@ -55,8 +433,53 @@ impl Post {
             title,
             content,
             author_id,
+            tags: Vec::new(),
+            likes_count: 0,
+            content_format: ContentFormat::Plain,
+            published: true,
+            published_at: Some(now),
             created_at: now,
             updated_at: now,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pagination_defaults_to_first_page() {
+        assert_eq!(pagination(None, None).unwrap(), (DEFAULT_PAGE_SIZE, 0));
+    }
+
+    #[test]
+    fn pagination_rejects_out_of_range_limit_and_negative_offset() {
+        assert!(pagination(Some(0), None).is_err());
+        assert!(pagination(Some(MAX_PAGE_SIZE + 1), None).is_err());
+        assert!(pagination(None, Some(-1)).is_err());
+        assert_eq!(
+            pagination(Some(MAX_PAGE_SIZE), None).unwrap(),
+            (MAX_PAGE_SIZE, 0)
+        );
+    }
+
+    #[test]
+    fn pagination_accepts_offset_past_the_end() {
+        // Сколько постов всего, здесь неизвестно: смещение за концом списка дает пустую страницу
+        assert_eq!(pagination(Some(10), Some(1_000)).unwrap(), (10, 1_000));
+    }
+
+    #[test]
+    fn page_offset_treats_page_zero_as_first_page() {
+        assert_eq!(page_offset(0, 10).unwrap(), 0);
+        assert_eq!(page_offset(1, 10).unwrap(), 0);
+        assert_eq!(page_offset(3, 10).unwrap(), 20);
+        assert!(page_offset(-1, 10).is_err());
+    }
+
+    #[test]
+    fn page_offset_past_the_end_is_not_an_error() {
+        assert_eq!(page_offset(100, 25).unwrap(), 2_475);
+    }
+}
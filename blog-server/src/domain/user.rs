@@ -1,13 +1,50 @@
+use crate::domain::DomainError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Privilege level of a user; admins may update and delete any post
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    // Токены, выпущенные до появления ролей, не содержат роли
+    #[default]
+    User,
+    Admin,
+}
+
+impl Role {
+    pub fn is_admin(self) -> bool {
+        self == Self::Admin
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "user" => Ok(Self::User),
+            "admin" => Ok(Self::Admin),
+            other => Err(DomainError::ValidationError(format!(
+                "Unknown role '{}', expected one of: user, admin",
+                other
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: i64,
     pub username: String,
     pub email: String,
     pub password_hash: String,
+    pub full_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub role: Role,
+    pub last_login_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -17,18 +54,51 @@ pub struct RegisterUserRequest {
     pub password: String,
 }
 
+/// Login credentials; `email` is used only when `username` is empty
 #[derive(Debug, Deserialize)]
 pub struct LoginUserRequest {
+    #[serde(default)]
     pub username: String,
+    #[serde(default)]
+    pub email: Option<String>,
     pub password: String,
 }
 
+/// Profile changes; `None` leaves a field unchanged,
+/// an empty string clears full_name, bio or avatar_url
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateUserRequest {
+    pub email: Option<String>,
+    pub full_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct UserResponse {
     pub id: i64,
     pub username: String,
+    /// Пустой в публичном профиле чужого пользователя
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub email: String,
+    pub full_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Заполняется только в собственном профиле (`/api/protected/me`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_login_at: Option<DateTime<Utc>>,
+    // Роль не входит в профиль: клиенту отдается только is_admin в ответе логина
+    #[serde(skip)]
+    pub role: Role,
+}
+
+impl UserResponse {
+    /// Profile as seen by other users: everything except the email
+    pub fn into_public(mut self) -> Self {
+        self.email.clear();
+        self
+    }
 }
 
 impl From<User> for UserResponse {
@@ -37,7 +107,12 @@ impl From<User> for UserResponse {
             id: user.id,
             username: user.username,
             email: user.email,
+            full_name: user.full_name,
+            bio: user.bio,
+            avatar_url: user.avatar_url,
             created_at: user.created_at,
+            last_login_at: None,
+            role: user.role,
         }
     }
 }
@@ -1,24 +1,148 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use sqlx::migrate::MigrateError;
 use sqlx::{postgres::PgPoolOptions, PgPool};
+use std::time::Duration;
+
+/// Snapshot of the connection pool state for diagnostics
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DbStats {
+    /// Open connections, idle and in use
+    pub size: u32,
+    pub idle: usize,
+    pub in_use: u32,
+    pub max_connections: u32,
+}
+
+/// Read the current pool state. Cheap and safe to call at any time,
+/// including before any connection has been opened (all zeros then)
+pub fn pool_stats(pool: &PgPool) -> DbStats {
+    let size = pool.size();
+    let idle = pool.num_idle();
+
+    DbStats {
+        size,
+        idle,
+        in_use: size.saturating_sub(idle as u32),
+        max_connections: pool.options().get_max_connections(),
+    }
+}
+
+/// How long the health check waits for the database to answer
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Check that the database answers a trivial query. Bounded by its own short
+/// timeout so the health check doesn't hang for the whole acquire timeout
+pub async fn ping(pool: &PgPool) -> bool {
+    match tokio::time::timeout(PING_TIMEOUT, sqlx::query("SELECT 1").execute(pool)).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(err)) => {
+            tracing::warn!("Database health check failed: {}", err);
+            false
+        }
+        Err(_) => {
+            tracing::warn!(
+                "Database health check timed out after {}s",
+                PING_TIMEOUT.as_secs()
+            );
+            false
+        }
+    }
+}
+
+// Значения по умолчанию совпадают с умолчаниями sqlx
+const DEFAULT_MAX_CONNECTIONS: u32 = 5;
+const DEFAULT_MIN_CONNECTIONS: u32 = 0;
+const DEFAULT_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 600;
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
 
 pub async fn create_pool(database_url: &str) -> Result<PgPool> {
-    let max_connections = std::env::var("DATABASE_MAX_CONNECTIONS")
-        .unwrap_or_else(|_| "5".to_string())
-        .parse()
-        .unwrap_or(5);
+    let max_connections = env_or("DATABASE_MAX_CONNECTIONS", DEFAULT_MAX_CONNECTIONS);
+    let min_connections =
+        env_or("DATABASE_MIN_CONNECTIONS", DEFAULT_MIN_CONNECTIONS).min(max_connections);
+    let acquire_timeout = env_or(
+        "DATABASE_ACQUIRE_TIMEOUT_SECS",
+        DEFAULT_ACQUIRE_TIMEOUT_SECS,
+    );
+    // 0 - не закрывать простаивающие соединения
+    let idle_timeout = env_or("DATABASE_IDLE_TIMEOUT_SECS", DEFAULT_IDLE_TIMEOUT_SECS);
 
     let pool = PgPoolOptions::new()
         .max_connections(max_connections)
+        .min_connections(min_connections)
+        .acquire_timeout(Duration::from_secs(acquire_timeout))
+        .idle_timeout((idle_timeout > 0).then(|| Duration::from_secs(idle_timeout)))
         .connect(database_url)
         .await?;
 
-    tracing::info!("Database connection pool created");
+    tracing::info!(
+        "Database connection pool created (max={}, min={}, acquire_timeout={}s, idle_timeout={}s)",
+        max_connections,
+        min_connections,
+        acquire_timeout,
+        idle_timeout
+    );
     Ok(pool)
 }
 
+/// Pool for tests against a real Postgres: `DATABASE_URL` (also read from `.env`)
+/// with migrations applied. `None` when the variable is not set - such tests are skipped
+#[cfg(test)]
+pub async fn test_pool() -> Option<PgPool> {
+    dotenvy::dotenv().ok();
+    let Ok(database_url) = std::env::var("DATABASE_URL") else {
+        eprintln!("DATABASE_URL is not set, skipping database test");
+        return None;
+    };
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .expect("Failed to connect to DATABASE_URL");
+    run_migrations(&pool)
+        .await
+        .expect("Failed to run migrations");
+    Some(pool)
+}
+
 pub async fn run_migrations(pool: &PgPool) -> Result<()> {
-    sqlx::migrate!("./migrations").run(pool).await?;
+    sqlx::migrate!("./migrations")
+        .run(pool)
+        .await
+        .map_err(explain_migration_error)?;
 
     tracing::info!("Database migrations completed");
     Ok(())
 }
+
+// Превращает ошибку миграции в сообщение с подсказкой, как исправить состояние БД
+fn explain_migration_error(err: MigrateError) -> anyhow::Error {
+    let hint = match &err {
+        MigrateError::VersionMismatch(version) => format!(
+            "migration {version} has a changed checksum since it was applied; \
+             revert the edit to the migration file or create a new migration instead"
+        ),
+        MigrateError::Dirty(version) => format!(
+            "migration {version} is partially applied; fix the database state manually \
+             and remove its row from the _sqlx_migrations table"
+        ),
+        MigrateError::VersionMissing(version) => format!(
+            "migration {version} was applied to this database but its file is missing; \
+             restore the file or point DATABASE_URL at the right database"
+        ),
+        MigrateError::ExecuteMigration(_, version) => {
+            format!("migration {version} failed to execute; fix the SQL and restart the server")
+        }
+        _ => return anyhow!(err).context("Failed to run database migrations"),
+    };
+
+    anyhow!("{hint} ({err})")
+}
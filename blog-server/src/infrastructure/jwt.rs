@@ -1,13 +1,62 @@
+use crate::domain::user::Role;
 use crate::domain::DomainError;
 use chrono::{Duration, Utc};
+use jsonwebtoken::errors::ErrorKind;
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 
+/// Kind of token: short-lived access token or long-lived refresh token
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    // Токены, выпущенные до появления refresh-токенов, не содержат типа
+    #[default]
+    Access,
+    Refresh,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub user_id: i64,
     pub username: String,
     pub exp: usize,
+    #[serde(default, rename = "typ")]
+    pub token_type: TokenType,
+    #[serde(default)]
+    pub role: Role,
+}
+
+/// Default lifetime of access tokens in seconds (24 hours)
+pub const DEFAULT_ACCESS_TOKEN_TTL_SECONDS: i64 = 24 * 60 * 60;
+/// Default lifetime of refresh tokens
+pub const DEFAULT_REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+/// Longest bearer token accepted; real tokens are a few hundred bytes
+pub const MAX_TOKEN_LENGTH: usize = 4096;
+
+/// Token from an Authorization value, shared by HTTP and gRPC: surrounding
+/// whitespace and an optional "Bearer " prefix (any case) are removed.
+/// An empty or over-long token is rejected before any decoding
+pub fn bearer_token(value: &str) -> Result<&str, DomainError> {
+    let value = value.trim();
+    let token = match value.split_once(char::is_whitespace) {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => token.trim_start(),
+        // Схема без токена
+        None if value.eq_ignore_ascii_case("bearer") => "",
+        _ => value,
+    };
+
+    if token.is_empty() {
+        return Err(DomainError::Unauthorized(
+            "Missing authorization token".to_string(),
+        ));
+    }
+    if token.len() > MAX_TOKEN_LENGTH {
+        return Err(DomainError::TokenInvalid(format!(
+            "token is longer than {} bytes",
+            MAX_TOKEN_LENGTH
+        )));
+    }
+    Ok(token)
 }
 
 pub struct JwtService {
@@ -15,10 +64,12 @@ pub struct JwtService {
     decoding_key: DecodingKey,
     #[allow(dead_code)]
     secret_length: usize,
+    access_token_ttl: Duration,
+    refresh_ttl: Duration,
 }
 
 impl JwtService {
-    pub fn new(secret: &str) -> Result<Self, DomainError> {
+    pub fn new(secret: &str, access_token_ttl: Duration) -> Result<Self, DomainError> {
         tracing::debug!(
             "Initializing JwtService with secret length: {}",
             secret.len()
@@ -35,18 +86,70 @@ impl JwtService {
             encoding_key: EncodingKey::from_secret(secret.as_bytes()),
             decoding_key: DecodingKey::from_secret(secret.as_bytes()),
             secret_length: secret.len(),
+            access_token_ttl,
+            refresh_ttl: Duration::days(DEFAULT_REFRESH_TOKEN_TTL_DAYS),
         })
     }
 
-    pub fn generate_token(&self, user_id: i64, username: String) -> Result<String, DomainError> {
+    /// Lifetime of issued access tokens
+    pub fn access_token_ttl(&self) -> Duration {
+        self.access_token_ttl
+    }
+
+    /// Override the lifetime of refresh tokens
+    pub fn with_refresh_ttl(mut self, refresh_ttl: Duration) -> Self {
+        self.refresh_ttl = refresh_ttl;
+        self
+    }
+
+    pub fn generate_token(
+        &self,
+        user_id: i64,
+        username: String,
+        role: Role,
+    ) -> Result<String, DomainError> {
         tracing::debug!(
             "Generating token for user_id: {}, username: {}",
             user_id,
             username
         );
 
+        self.encode_claims(
+            user_id,
+            username,
+            role,
+            TokenType::Access,
+            self.access_token_ttl,
+        )
+    }
+
+    pub fn generate_refresh_token(
+        &self,
+        user_id: i64,
+        username: String,
+    ) -> Result<String, DomainError> {
+        tracing::debug!("Generating refresh token for user_id: {}", user_id);
+
+        // Роль в refresh-токене не нужна: при обновлении она перечитывается из БД
+        self.encode_claims(
+            user_id,
+            username,
+            Role::default(),
+            TokenType::Refresh,
+            self.refresh_ttl,
+        )
+    }
+
+    fn encode_claims(
+        &self,
+        user_id: i64,
+        username: String,
+        role: Role,
+        token_type: TokenType,
+        ttl: Duration,
+    ) -> Result<String, DomainError> {
         let expiration = Utc::now()
-            .checked_add_signed(Duration::hours(24))
+            .checked_add_signed(ttl)
             .expect("valid timestamp")
             .timestamp() as usize;
 
@@ -54,6 +157,8 @@ impl JwtService {
             user_id,
             username,
             exp: expiration,
+            token_type,
+            role,
         };
 
         match encode(&Header::default(), &claims, &self.encoding_key) {
@@ -71,15 +176,46 @@ impl JwtService {
         }
     }
 
+    /// Verify an access token and return its user_id. Refresh tokens are rejected.
+    /// Fails with `TokenExpired` for an expired token and `TokenInvalid` otherwise.
     pub fn verify_token(&self, token: &str) -> Result<i64, DomainError> {
+        self.verify_token_with_role(token)
+            .map(|(user_id, _)| user_id)
+    }
+
+    /// Like `verify_token`, but also returns the role the token was issued with
+    pub fn verify_token_with_role(&self, token: &str) -> Result<(i64, Role), DomainError> {
+        self.decode_claims(token, TokenType::Access)
+            .map(|claims| (claims.user_id, claims.role))
+    }
+
+    /// Verify a refresh token and return its user_id. Access tokens are rejected.
+    pub fn verify_refresh_token(&self, token: &str) -> Result<i64, DomainError> {
+        self.decode_claims(token, TokenType::Refresh)
+            .map(|claims| claims.user_id)
+    }
+
+    fn decode_claims(&self, token: &str, expected: TokenType) -> Result<Claims, DomainError> {
         match decode::<Claims>(token, &self.decoding_key, &Validation::default()) {
-            Ok(token_data) => {
+            Ok(token_data) if token_data.claims.token_type == expected => {
                 tracing::debug!("Token verified for user_id: {}", token_data.claims.user_id);
-                Ok(token_data.claims.user_id)
+                Ok(token_data.claims)
+            }
+            Ok(token_data) => {
+                tracing::warn!(
+                    "Token type mismatch: expected {:?}, got {:?}",
+                    expected,
+                    token_data.claims.token_type
+                );
+                Err(DomainError::TokenInvalid("invalid token type".to_string()))
+            }
+            Err(e) if matches!(e.kind(), ErrorKind::ExpiredSignature) => {
+                tracing::debug!("Token expired");
+                Err(DomainError::TokenExpired)
             }
             Err(e) => {
                 tracing::error!("Token verification failed: {}", e);
-                Err(DomainError::Unauthorized(format!("Invalid token: {}", e)))
+                Err(DomainError::TokenInvalid(e.to_string()))
             }
         }
     }
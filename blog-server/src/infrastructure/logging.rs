@@ -1,12 +1,33 @@
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+/// Output format of log lines, chosen by `LOG_FORMAT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    /// Human-readable lines (default)
+    Pretty,
+    /// One JSON object per event, for log collectors such as Loki
+    Json,
+}
 
 pub fn init_logging() {
+    // Неизвестное значение не мешает запуску: логируем как обычно и предупреждаем ниже
+    let log_format = std::env::var("LOG_FORMAT").unwrap_or_default();
+    let (format, unknown_format) = match log_format.trim().to_ascii_lowercase().as_str() {
+        "json" => (LogFormat::Json, false),
+        "" | "pretty" => (LogFormat::Pretty, false),
+        _ => (LogFormat::Pretty, true),
+    };
+
     let fmt_layer = fmt::layer()
         .with_target(true)
         .with_thread_ids(true)
         .with_thread_names(true)
         .with_file(true)
         .with_line_number(true);
+    let fmt_layer = match format {
+        LogFormat::Pretty => fmt_layer.boxed(),
+        LogFormat::Json => fmt_layer.json().boxed(),
+    };
 
     let filter_layer = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info,blog_server=debug"));
@@ -16,5 +37,11 @@ pub fn init_logging() {
         .with(fmt_layer)
         .init();
 
-    tracing::info!("Logging initialized");
+    if unknown_format {
+        tracing::warn!(
+            "Unknown LOG_FORMAT '{}', expected pretty or json; using pretty",
+            log_format
+        );
+    }
+    tracing::info!("Logging initialized ({:?} format)", format);
 }
@@ -0,0 +1,111 @@
+use prometheus::{
+    exponential_buckets, histogram_opts, opts, Encoder, HistogramVec, IntCounterVec, Registry,
+    TextEncoder,
+};
+use std::time::Duration;
+
+/// Prometheus metrics shared by the HTTP and gRPC servers; `transport` label is `http` or `grpc`
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    errors: IntCounterVec,
+    latency: HistogramVec,
+    request_size: HistogramVec,
+    response_size: HistogramVec,
+}
+
+impl Metrics {
+    pub fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new();
+
+        let requests = IntCounterVec::new(
+            opts!("blog_requests_total", "Handled requests"),
+            &["transport", "endpoint", "status"],
+        )?;
+        let errors = IntCounterVec::new(
+            opts!("blog_errors_total", "Requests failed with a DomainError"),
+            &["transport", "variant"],
+        )?;
+        let latency = HistogramVec::new(
+            histogram_opts!("blog_request_duration_seconds", "Request latency"),
+            &["transport", "endpoint"],
+        )?;
+        // 64 байта .. 1 МБ (предел JSON-тела по умолчанию)
+        let size_buckets = exponential_buckets(64.0, 4.0, 8)?;
+        let request_size = HistogramVec::new(
+            histogram_opts!(
+                "blog_http_request_size_bytes",
+                "HTTP request body size (Content-Length)",
+                size_buckets.clone()
+            ),
+            &["endpoint"],
+        )?;
+        let response_size = HistogramVec::new(
+            histogram_opts!(
+                "blog_http_response_size_bytes",
+                "HTTP response body size before compression",
+                size_buckets
+            ),
+            &["endpoint"],
+        )?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(latency.clone()))?;
+        registry.register(Box::new(request_size.clone()))?;
+        registry.register(Box::new(response_size.clone()))?;
+
+        Ok(Self {
+            registry,
+            requests,
+            errors,
+            latency,
+            request_size,
+            response_size,
+        })
+    }
+
+    /// Count a finished request and record its latency
+    pub fn observe_request(
+        &self,
+        transport: &str,
+        endpoint: &str,
+        status: &str,
+        elapsed: Duration,
+    ) {
+        self.requests
+            .with_label_values(&[transport, endpoint, status])
+            .inc();
+        self.latency
+            .with_label_values(&[transport, endpoint])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Count a request that failed with the given `DomainError` variant
+    pub fn observe_error(&self, transport: &str, variant: &str) {
+        self.errors.with_label_values(&[transport, variant]).inc();
+    }
+
+    /// Body sizes of an HTTP request; unknown sizes (streaming bodies) are skipped
+    pub fn observe_http_sizes(&self, endpoint: &str, request: Option<u64>, response: Option<u64>) {
+        if let Some(size) = request {
+            self.request_size
+                .with_label_values(&[endpoint])
+                .observe(size as f64);
+        }
+        if let Some(size) = response {
+            self.response_size
+                .with_label_values(&[endpoint])
+                .observe(size as f64);
+        }
+    }
+
+    /// All metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(err) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!("Failed to encode metrics: {}", err);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
@@ -1,3 +1,5 @@
 pub mod database;
 pub mod jwt;
 pub mod logging;
+pub mod metrics;
+pub mod rate_limit;
@@ -0,0 +1,40 @@
+use crate::domain::DomainError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Fixed-window rate limiter keyed by an arbitrary client identifier (usually the peer IP)
+pub struct RateLimiter {
+    max_requests: u32,
+    window: Duration,
+    hits: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        Self {
+            max_requests,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register a hit for `key`, failing with `RateLimited` once the window budget is spent
+    pub fn check(&self, key: &str) -> Result<(), DomainError> {
+        let now = Instant::now();
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Drop expired windows so the map doesn't grow unbounded
+        hits.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+
+        let (started, count) = hits.entry(key.to_string()).or_insert((now, 0));
+        if *count >= self.max_requests {
+            let retry_after = self.window.saturating_sub(now.duration_since(*started));
+            tracing::warn!("Rate limit exceeded for {}", key);
+            return Err(DomainError::RateLimited(retry_after.as_secs().max(1)));
+        }
+
+        *count += 1;
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
+use anyhow::Context;
 use dotenvy::dotenv;
+use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::{JoinError, JoinHandle};
+use tonic::transport::{Identity, ServerTlsConfig};
 
 mod application;
 mod data;
@@ -11,14 +16,37 @@ pub mod proto {
     tonic::include_proto!("blog");
 }
 
-use application::{auth_service::AuthService, blog_service::BlogService};
-use data::{post_repository::PostgresPostRepository, user_repository::PostgresUserRepository};
+use application::{
+    auth_service::{Argon2Config, AuthService, DEFAULT_MIN_PASSWORD_LENGTH},
+    blog_service::{
+        BlogService, DEFAULT_EXCERPT_LENGTH, DEFAULT_IDEMPOTENCY_TTL_SECONDS,
+        DEFAULT_MAX_CONTENT_LENGTH, DEFAULT_MAX_TITLE_LENGTH,
+    },
+    comment_service::CommentService,
+    post_events::BroadcastPostEventSink,
+};
+use data::{
+    comment_repository::{CommentRepository, PostgresCommentRepository},
+    post_repository::{PostRepository, PostgresPostRepository},
+    slow_query::SlowQueryLog,
+    user_repository::{PostgresUserRepository, UserRepository},
+};
 use infrastructure::{
     database::{create_pool, run_migrations},
-    jwt::JwtService,
+    jwt::{JwtService, DEFAULT_ACCESS_TOKEN_TTL_SECONDS, DEFAULT_REFRESH_TOKEN_TTL_DAYS},
     logging::init_logging,
+    metrics::Metrics,
+    rate_limit::RateLimiter,
+};
+use presentation::{
+    caching::{CachePolicy, DEFAULT_PUBLIC_MAX_AGE_SECONDS},
+    grpc_service::BlogGrpcService,
+    http_handlers,
+    json::JsonNaming,
+    metrics::{metrics_middleware, GrpcMetricsLayer},
+    middleware::{admin_middleware, jwt_middleware},
+    request_id::{request_id_middleware, GrpcRequestIdLayer, REQUEST_ID_HEADER},
 };
-use presentation::{grpc_service::BlogGrpcService, http_handlers, middleware::jwt_middleware};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -33,18 +61,75 @@ async fn main() -> anyhow::Result<()> {
     let jwt_secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
     let http_port = std::env::var("HTTP_PORT").unwrap_or_else(|_| "3000".to_string());
     let grpc_port = std::env::var("GRPC_PORT").unwrap_or_else(|_| "50051".to_string());
+    let json_naming = JsonNaming::from_flag(
+        std::env::var("JSON_CAMEL_CASE")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false),
+    );
+    let public_base_url = std::env::var("PUBLIC_BASE_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty());
+    let cache_policy = CachePolicy::new(
+        std::env::var("PUBLIC_CACHE_MAX_AGE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_PUBLIC_MAX_AGE_SECONDS),
+    );
+    let availability_rate_limit: u32 = std::env::var("AVAILABILITY_RATE_LIMIT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30);
+    // Предел тела JSON-запроса в байтах; больший запрос отклоняется до разбора (413)
+    let json_payload_limit: usize = std::env::var("MAX_JSON_PAYLOAD_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(http_handlers::DEFAULT_JSON_PAYLOAD_LIMIT);
+
+    // Отладочные эндпоинты (/api/debug/*) выключены по умолчанию
+    let debug_endpoints = std::env::var("DEBUG_ENDPOINTS")
+        .map(|v| matches!(v.trim(), "1" | "true"))
+        .unwrap_or(false);
+    // Метрики Prometheus (GET /metrics) выключены по умолчанию
+    let metrics_enabled = std::env::var("ENABLE_METRICS")
+        .map(|v| matches!(v.trim(), "1" | "true"))
+        .unwrap_or(false);
+    // Сжатие ответов (gzip и др. для HTTP, gzip для gRPC) включено по умолчанию
+    let response_compression = std::env::var("RESPONSE_COMPRESSION")
+        .map(|v| !matches!(v.trim(), "0" | "false"))
+        .unwrap_or(true);
+    // Любой из серверов можно выключить (ENABLE_HTTP=false / ENABLE_GRPC=false), по умолчанию работают оба
+    let enable_http = std::env::var("ENABLE_HTTP")
+        .map(|v| !matches!(v.trim(), "0" | "false"))
+        .unwrap_or(true);
+    let enable_grpc = std::env::var("ENABLE_GRPC")
+        .map(|v| !matches!(v.trim(), "0" | "false"))
+        .unwrap_or(true);
+    if !enable_http && !enable_grpc {
+        tracing::error!("Both ENABLE_HTTP and ENABLE_GRPC are false, nothing to serve");
+        anyhow::bail!("Both HTTP and gRPC servers are disabled");
+    }
 
-    // Получаем разрешенные CORS домены из .env
-    let cors_allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:8000,http://127.0.0.1:8000".to_string());
+    // Получаем разрешенные CORS домены, методы и заголовки из .env
+    let cors = load_cors_settings()?;
+
+    // TLS для gRPC включается, только если заданы оба файла
+    let grpc_tls = if enable_grpc { load_grpc_tls()? } else { None };
 
     let http_addr = format!("0.0.0.0:{}", http_port);
     let grpc_addr = format!("0.0.0.0:{}", grpc_port);
 
     tracing::info!("Starting blog server...");
-    tracing::info!("HTTP server will listen on {}", http_addr);
-    tracing::info!("gRPC server will listen on {}", grpc_addr);
-    tracing::info!("CORS allowed origins: {}", cors_allowed_origins);
+    if enable_http {
+        tracing::info!("HTTP server will listen on {}", http_addr);
+    } else {
+        tracing::info!("HTTP server disabled (ENABLE_HTTP=false)");
+    }
+    if enable_grpc {
+        tracing::info!("gRPC server will listen on {}", grpc_addr);
+    } else {
+        tracing::info!("gRPC server disabled (ENABLE_GRPC=false)");
+    }
+    tracing::info!("CORS allowed origins: {}", cors.allowed_origins);
 
     // Initialize database connection pool
     tracing::info!("Connecting to database...");
@@ -59,68 +144,212 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Initializing services...");
 
     // JWT service
-    let jwt_service = Arc::new(JwtService::new(&jwt_secret)?);
-
-    // Repositories
-    let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostgresPostRepository::new(pool.clone()));
+    let access_ttl_seconds: i64 = std::env::var("JWT_TTL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&v| v > 0)
+        .unwrap_or(DEFAULT_ACCESS_TOKEN_TTL_SECONDS);
+    let refresh_ttl_days: i64 = std::env::var("JWT_REFRESH_TTL_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_REFRESH_TOKEN_TTL_DAYS);
+    let jwt_service = Arc::new(
+        JwtService::new(&jwt_secret, chrono::Duration::seconds(access_ttl_seconds))?
+            .with_refresh_ttl(chrono::Duration::days(refresh_ttl_days)),
+    );
+
+    // Repositories; с SLOW_QUERY_MS медленные запросы логируются как warn
+    let slow_query_threshold = std::env::var("SLOW_QUERY_MS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .map(Duration::from_millis);
+    let user_repo: Arc<dyn UserRepository + Send + Sync> = match slow_query_threshold {
+        Some(threshold) => Arc::new(SlowQueryLog::new(
+            PostgresUserRepository::new(pool.clone()),
+            threshold,
+        )),
+        None => Arc::new(PostgresUserRepository::new(pool.clone())),
+    };
+    let post_repo: Arc<dyn PostRepository + Send + Sync> = match slow_query_threshold {
+        Some(threshold) => {
+            tracing::info!("Slow query logging enabled: threshold {:?}", threshold);
+            Arc::new(SlowQueryLog::new(
+                PostgresPostRepository::new(pool.clone()),
+                threshold,
+            ))
+        }
+        None => Arc::new(PostgresPostRepository::new(pool.clone())),
+    };
+    let comment_repo: Arc<dyn CommentRepository + Send + Sync> = match slow_query_threshold {
+        Some(threshold) => Arc::new(SlowQueryLog::new(
+            PostgresCommentRepository::new(pool.clone()),
+            threshold,
+        )),
+        None => Arc::new(PostgresCommentRepository::new(pool.clone())),
+    };
 
     // Application services
-    let auth_service = Arc::new(AuthService::new(user_repo.clone(), jwt_service.clone()));
-
-    let blog_service = Arc::new(BlogService::new(post_repo.clone()));
+    let min_password_length: usize = std::env::var("MIN_PASSWORD_LENGTH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MIN_PASSWORD_LENGTH);
+    // Стоимость хеширования паролей: ARGON2_PROFILE=fast для тестов, отдельные
+    // ARGON2_* переопределяют параметры профиля
+    let argon2_profile = match std::env::var("ARGON2_PROFILE").as_deref() {
+        Ok("fast") => {
+            tracing::warn!("ARGON2_PROFILE=fast: password hashes are cheap to brute-force");
+            Argon2Config::fast()
+        }
+        _ => Argon2Config::default(),
+    };
+    let argon2_config = Argon2Config {
+        memory_kib: std::env::var("ARGON2_MEMORY_KIB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(argon2_profile.memory_kib),
+        iterations: std::env::var("ARGON2_ITERATIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(argon2_profile.iterations),
+        parallelism: std::env::var("ARGON2_PARALLELISM")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(argon2_profile.parallelism),
+    };
+    let auth_service = Arc::new(
+        AuthService::new(user_repo.clone(), jwt_service.clone())
+            .with_min_password_length(min_password_length)
+            .with_argon2(argon2_config)?,
+    );
+
+    let idempotency_ttl_seconds: u64 = std::env::var("IDEMPOTENCY_TTL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_IDEMPOTENCY_TTL_SECONDS);
+    let max_title_length: usize = std::env::var("MAX_TITLE_LENGTH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_TITLE_LENGTH);
+    let max_content_length: usize = std::env::var("MAX_CONTENT_LENGTH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_CONTENT_LENGTH);
+    let excerpt_length: usize = std::env::var("EXCERPT_LENGTH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_EXCERPT_LENGTH);
+    // Сколько постов может быть у обычного пользователя (0 - без ограничения)
+    let max_posts_per_user: u64 = std::env::var("MAX_POSTS_PER_USER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let blog_service = Arc::new(
+        BlogService::new(post_repo.clone(), public_base_url)
+            .with_idempotency_ttl(Duration::from_secs(idempotency_ttl_seconds))
+            .with_max_title_length(max_title_length)
+            .with_max_content_length(max_content_length)
+            .with_excerpt_length(excerpt_length)
+            .with_max_posts_per_user(max_posts_per_user)
+            // Broadcast-канал питает потоки новых постов (SSE и gRPC StreamPosts)
+            .with_event_sink(Arc::new(BroadcastPostEventSink::new())),
+    );
+
+    let comment_service = Arc::new(CommentService::new(comment_repo, post_repo.clone()));
+
+    // Rate limiter for the username/email availability check (requests per minute per IP)
+    let availability_limiter = Arc::new(RateLimiter::new(
+        availability_rate_limit,
+        Duration::from_secs(60),
+    ));
+
+    let metrics = if metrics_enabled {
+        if enable_http {
+            tracing::info!("Prometheus metrics enabled at GET /metrics");
+        } else {
+            tracing::warn!(
+                "ENABLE_METRICS=true, but GET /metrics is served only by the HTTP server"
+            );
+        }
+        Some(Arc::new(Metrics::new()?))
+    } else {
+        None
+    };
 
     tracing::info!("Services initialized successfully");
 
     // Clone services for HTTP and gRPC servers
     let auth_service_http = auth_service.clone();
     let blog_service_http = blog_service.clone();
+    let comment_service_http = comment_service.clone();
     let jwt_service_http = jwt_service.clone();
+    let availability_limiter_http = availability_limiter.clone();
+    let pool_http = pool.clone();
+    let metrics_http = metrics.clone();
 
     let auth_service_grpc = auth_service.clone();
     let blog_service_grpc = blog_service.clone();
+    let comment_service_grpc = comment_service.clone();
     let jwt_service_grpc = jwt_service.clone();
+    let availability_limiter_grpc = availability_limiter.clone();
 
     // Start HTTP server (actix-web)
-    tracing::info!("Starting HTTP server...");
-    let http_server = tokio::spawn(async move {
-        if let Err(e) = run_http_server(
-            http_addr,
-            auth_service_http,
-            blog_service_http,
-            jwt_service_http,
-            cors_allowed_origins,
-        )
-        .await
-        {
-            tracing::error!("HTTP server error: {}", e);
-        }
+    let http_server = enable_http.then(|| {
+        tracing::info!("Starting HTTP server...");
+        tokio::spawn(async move {
+            if let Err(e) = run_http_server(
+                http_addr,
+                auth_service_http,
+                blog_service_http,
+                comment_service_http,
+                jwt_service_http,
+                availability_limiter_http,
+                json_naming,
+                cache_policy,
+                json_payload_limit,
+                response_compression,
+                cors,
+                pool_http,
+                debug_endpoints,
+                metrics_http,
+            )
+            .await
+            {
+                tracing::error!("HTTP server error: {}", e);
+            }
+        })
     });
 
     // Start gRPC server (tonic)
-    tracing::info!("Starting gRPC server...");
-    let grpc_server = tokio::spawn(async move {
-        if let Err(e) = run_grpc_server(
-            grpc_addr,
-            auth_service_grpc,
-            blog_service_grpc,
-            jwt_service_grpc,
-        )
-        .await
-        {
-            tracing::error!("gRPC server error: {}", e);
-        }
+    let grpc_server = enable_grpc.then(|| {
+        tracing::info!("Starting gRPC server...");
+        tokio::spawn(async move {
+            if let Err(e) = run_grpc_server(
+                grpc_addr,
+                auth_service_grpc,
+                blog_service_grpc,
+                comment_service_grpc,
+                jwt_service_grpc,
+                availability_limiter_grpc,
+                grpc_tls,
+                response_compression,
+                metrics,
+            )
+            .await
+            {
+                tracing::error!("gRPC server error: {}", e);
+            }
+        })
     });
 
-    // Wait for both servers to complete (they shouldn't, unless there's an error)
+    // Wait for the running servers to complete (they shouldn't, unless there's an error)
     tokio::select! {
-        result = http_server => {
+        result = join_server(http_server) => {
             match result {
                 Ok(_) => tracing::info!("HTTP server stopped"),
                 Err(e) => tracing::error!("HTTP server task failed: {}", e),
             }
         }
-        result = grpc_server => {
+        result = join_server(grpc_server) => {
             match result {
                 Ok(_) => tracing::info!("gRPC server stopped"),
                 Err(e) => tracing::error!("gRPC server task failed: {}", e),
@@ -132,25 +361,166 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Задача выключенного сервера никогда не завершается, чтобы select! ждал только запущенные
+async fn join_server(task: Option<JoinHandle<()>>) -> Result<(), JoinError> {
+    match task {
+        Some(task) => task.await,
+        None => std::future::pending().await,
+    }
+}
+
+// Сертификат и ключ (PEM) для gRPC из GRPC_TLS_CERT / GRPC_TLS_KEY; без них - plaintext
+fn load_grpc_tls() -> anyhow::Result<Option<ServerTlsConfig>> {
+    let cert_path = std::env::var("GRPC_TLS_CERT")
+        .ok()
+        .filter(|p| !p.trim().is_empty());
+    let key_path = std::env::var("GRPC_TLS_KEY")
+        .ok()
+        .filter(|p| !p.trim().is_empty());
+
+    match (cert_path, key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            let cert = std::fs::read_to_string(&cert_path)
+                .with_context(|| format!("Failed to read GRPC_TLS_CERT {}", cert_path))?;
+            let key = std::fs::read_to_string(&key_path)
+                .with_context(|| format!("Failed to read GRPC_TLS_KEY {}", key_path))?;
+            tracing::info!("gRPC TLS enabled with certificate {}", cert_path);
+            Ok(Some(
+                ServerTlsConfig::new().identity(Identity::from_pem(cert, key)),
+            ))
+        }
+        (None, None) => Ok(None),
+        _ => anyhow::bail!("GRPC_TLS_CERT and GRPC_TLS_KEY must be set together"),
+    }
+}
+
+/// CORS settings of the HTTP server, read once at startup
+#[derive(Debug, Clone)]
+struct CorsSettings {
+    /// Comma-separated origins; `*` allows any origin (development only)
+    allowed_origins: String,
+    allowed_methods: Vec<actix_web::http::Method>,
+    allowed_headers: Vec<actix_web::http::header::HeaderName>,
+    allow_credentials: bool,
+}
+
+// CORS_ALLOWED_ORIGINS / CORS_ALLOWED_METHODS / CORS_ALLOWED_HEADERS (списки через запятую)
+// и CORS_ALLOW_CREDENTIALS; неверный метод или заголовок - ошибка запуска
+fn load_cors_settings() -> anyhow::Result<CorsSettings> {
+    use actix_web::http::{header, header::HeaderName, Method};
+
+    fn list(name: &str) -> Option<Vec<String>> {
+        std::env::var(name)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+    }
+
+    let allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
+        .unwrap_or_else(|_| "http://localhost:8000,http://127.0.0.1:8000".to_string());
+
+    let allowed_methods = match list("CORS_ALLOWED_METHODS") {
+        Some(methods) => methods
+            .iter()
+            .map(|m| {
+                Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                    .with_context(|| format!("Invalid method '{}' in CORS_ALLOWED_METHODS", m))
+            })
+            .collect::<anyhow::Result<_>>()?,
+        None => vec![
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::DELETE,
+            Method::OPTIONS,
+        ],
+    };
+
+    // По умолчанию - все заголовки, которые понимает сервер
+    let allowed_headers = match list("CORS_ALLOWED_HEADERS") {
+        Some(headers) => headers
+            .iter()
+            .map(|h| {
+                HeaderName::from_bytes(h.as_bytes())
+                    .with_context(|| format!("Invalid header '{}' in CORS_ALLOWED_HEADERS", h))
+            })
+            .collect::<anyhow::Result<_>>()?,
+        None => vec![
+            header::AUTHORIZATION,
+            header::ACCEPT,
+            header::CONTENT_TYPE,
+            header::IF_NONE_MATCH,
+            header::IF_UNMODIFIED_SINCE,
+            HeaderName::from_static("accept-version"),
+            HeaderName::from_static("idempotency-key"),
+            HeaderName::from_static(REQUEST_ID_HEADER),
+        ],
+    };
+
+    let allow_credentials = std::env::var("CORS_ALLOW_CREDENTIALS")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    if allowed_origins.split(',').any(|o| o.trim() == "*") {
+        tracing::warn!(
+            "CORS_ALLOWED_ORIGINS contains '*': any website may call this API, use it only in development"
+        );
+        if allow_credentials {
+            tracing::warn!(
+                "CORS allows credentials for any origin: never enable this combination in production"
+            );
+        }
+    }
+
+    Ok(CorsSettings {
+        allowed_origins,
+        allowed_methods,
+        allowed_headers,
+        allow_credentials,
+    })
+}
+
 /// Configure CORS for the HTTP server with allowed origins from .env
-fn configure_cors(allowed_origins: &str) -> actix_cors::Cors {
+fn configure_cors(settings: &CorsSettings) -> actix_cors::Cors {
     use actix_cors::Cors;
     use actix_web::http::header;
 
-    tracing::info!("Configuring CORS with allowed origins: {}", allowed_origins);
+    tracing::info!(
+        "Configuring CORS with allowed origins: {}",
+        settings.allowed_origins
+    );
 
-    let origins: Vec<&str> = allowed_origins.split(',').map(|s| s.trim()).collect();
+    let origins: Vec<&str> = settings
+        .allowed_origins
+        .split(',')
+        .map(|s| s.trim())
+        .collect();
 
     let mut cors = Cors::default()
-        .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
-        .allowed_headers(vec![
+        .allowed_methods(settings.allowed_methods.clone())
+        .allowed_headers(settings.allowed_headers.clone())
+        .expose_headers(vec![
             header::AUTHORIZATION,
-            header::ACCEPT,
-            header::CONTENT_TYPE,
+            header::ETAG,
+            header::RETRY_AFTER,
+            header::HeaderName::from_static(REQUEST_ID_HEADER),
         ])
-        .expose_headers(vec![header::AUTHORIZATION])
         .max_age(3600);
 
+    if settings.allow_credentials {
+        cors = cors.supports_credentials();
+    }
+
+    // "*" - любой домен (Origin запроса отражается в ответе); только для разработки
+    if origins.contains(&"*") {
+        return cors.allow_any_origin();
+    }
+
     // Добавляем каждый разрешенный домен
     for origin in origins {
         if !origin.is_empty() {
@@ -162,46 +532,148 @@ fn configure_cors(allowed_origins: &str) -> actix_cors::Cors {
     cors
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_http_server(
     addr: String,
     auth_service: Arc<AuthService>,
     blog_service: Arc<BlogService>,
+    comment_service: Arc<CommentService>,
     jwt_service: Arc<JwtService>,
-    cors_allowed_origins: String,
+    availability_limiter: Arc<RateLimiter>,
+    json_naming: JsonNaming,
+    cache_policy: CachePolicy,
+    json_payload_limit: usize,
+    compression: bool,
+    cors: CorsSettings,
+    pool: PgPool,
+    debug_endpoints: bool,
+    metrics: Option<Arc<Metrics>>,
 ) -> anyhow::Result<()> {
-    use actix_web::{middleware::Logger, web, App, HttpServer};
+    use actix_web::{
+        middleware::{from_fn, Compress, Condition, DefaultHeaders, Logger},
+        web, App, HttpServer,
+    };
     use actix_web_httpauth::middleware::HttpAuthentication;
 
     tracing::info!("Configuring HTTP server...");
 
-    let auth_middleware = HttpAuthentication::bearer(jwt_middleware);
+    let auth_middleware = HttpAuthentication::with_fn(jwt_middleware);
+    let admin_auth_middleware = HttpAuthentication::with_fn(admin_middleware);
 
     let server = HttpServer::new(move || {
         App::new()
-            .wrap(Logger::default())
-            .wrap(configure_cors(&cors_allowed_origins))
+            // Внутри Logger, чтобы в access-логе был итоговый X-Request-Id
+            .wrap(from_fn(request_id_middleware))
+            // До сжатия: размер ответа в метриках - исходный
+            .wrap(Condition::new(
+                metrics.is_some(),
+                from_fn(metrics_middleware),
+            ))
+            // Кодировка выбирается по Accept-Encoding; %b в логе - размер после сжатия
+            .wrap(Condition::new(compression, Compress::default()))
+            .wrap(Logger::new(
+                r#"%a "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T request_id=%{x-request-id}o"#,
+            ))
+            .wrap(configure_cors(&cors))
             .app_data(web::Data::new(auth_service.clone()))
             .app_data(web::Data::new(blog_service.clone()))
+            .app_data(web::Data::new(comment_service.clone()))
             .app_data(web::Data::new(jwt_service.clone()))
+            .app_data(web::Data::new(availability_limiter.clone()))
+            .app_data(web::Data::new(json_naming))
+            .app_data(web::Data::new(cache_policy))
+            .app_data(web::Data::new(pool.clone()))
+            .app_data(
+                web::JsonConfig::default()
+                    .limit(json_payload_limit)
+                    .error_handler(http_handlers::json_error_handler),
+            )
+            .route("/api/health", web::get().to(http_handlers::health))
+            // Diagnostics, only with DEBUG_ENDPOINTS=true
+            .configure(|cfg| {
+                if debug_endpoints {
+                    cfg.route("/api/debug/pool", web::get().to(http_handlers::pool_stats));
+                }
+            })
+            // Prometheus scrape endpoint, only with ENABLE_METRICS=true
+            .configure(|cfg| {
+                if let Some(metrics) = &metrics {
+                    cfg.app_data(web::Data::new(metrics.clone()))
+                        .route("/metrics", web::get().to(http_handlers::metrics));
+                }
+            })
             // Public routes - authentication
             .service(
                 web::scope("/api/auth")
                     .route("/register", web::post().to(http_handlers::register))
-                    .route("/login", web::post().to(http_handlers::login)),
+                    .route("/login", web::post().to(http_handlers::login))
+                    .route("/validate", web::post().to(http_handlers::validate_token))
+                    .route("/refresh", web::post().to(http_handlers::refresh_token))
+                    .route(
+                        "/available",
+                        web::get().to(http_handlers::check_availability),
+                    ),
             )
             // Public routes - posts (read-only)
             .service(
                 web::scope("/api/posts")
                     .route("", web::get().to(http_handlers::list_posts))
-                    .route("/{id}", web::get().to(http_handlers::get_post)),
+                    .route("/count", web::get().to(http_handlers::count_posts))
+                    .route("/batch", web::post().to(http_handlers::get_posts_batch))
+                    .route("/stream", web::get().to(http_handlers::stream_posts))
+                    .route("/{id}", web::get().to(http_handlers::get_post))
+                    .route(
+                        "/{id}/comments",
+                        web::get().to(http_handlers::list_comments),
+                    ),
+            )
+            // Public routes - user profiles (email only for the owner's own token)
+            .service(
+                web::scope("/api/users").route("/{id}", web::get().to(http_handlers::get_user)),
             )
             // Protected routes - posts (write operations)
             .service(
                 web::scope("/api/protected/posts")
                     .wrap(auth_middleware.clone())
+                    .wrap(DefaultHeaders::new().add(CachePolicy::no_store()))
                     .route("", web::post().to(http_handlers::create_post))
+                    .route("/batch", web::post().to(http_handlers::create_posts_batch))
+                    .route(
+                        "/batch/delete",
+                        web::post().to(http_handlers::delete_posts_batch),
+                    )
+                    .route("", web::delete().to(http_handlers::delete_my_posts))
+                    .route("/dashboard", web::get().to(http_handlers::dashboard))
                     .route("/{id}", web::put().to(http_handlers::update_post))
-                    .route("/{id}", web::delete().to(http_handlers::delete_post)),
+                    .route("/{id}", web::delete().to(http_handlers::delete_post))
+                    .route("/{id}/like", web::post().to(http_handlers::like_post))
+                    .route("/{id}/like", web::delete().to(http_handlers::unlike_post))
+                    .route("/{id}/comments", web::post().to(http_handlers::add_comment)),
+            )
+            // Protected routes - current user profile
+            .service(
+                web::scope("/api/protected/me")
+                    .wrap(auth_middleware.clone())
+                    .wrap(DefaultHeaders::new().add(CachePolicy::no_store()))
+                    .route("", web::get().to(http_handlers::current_user))
+                    .route("", web::put().to(http_handlers::update_profile)),
+            )
+            .service(
+                web::scope("/api/protected/change-password")
+                    .wrap(auth_middleware.clone())
+                    .wrap(DefaultHeaders::new().add(CachePolicy::no_store()))
+                    .route("", web::post().to(http_handlers::change_password)),
+            )
+            // Admin routes - moderation, role=admin only
+            .service(
+                web::scope("/api/admin")
+                    .wrap(admin_auth_middleware.clone())
+                    .wrap(DefaultHeaders::new().add(CachePolicy::no_store()))
+                    .route(
+                        "/users/{id}/posts",
+                        web::delete().to(http_handlers::delete_user_posts),
+                    )
+                    .route("/tags/rename", web::post().to(http_handlers::rename_tag)),
             )
     })
     .bind(&addr)?
@@ -214,27 +686,58 @@ async fn run_http_server(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_grpc_server(
     addr: String,
     auth_service: Arc<AuthService>,
     blog_service: Arc<BlogService>,
+    comment_service: Arc<CommentService>,
     jwt_service: Arc<JwtService>,
+    availability_limiter: Arc<RateLimiter>,
+    tls: Option<ServerTlsConfig>,
+    compression: bool,
+    metrics: Option<Arc<Metrics>>,
 ) -> anyhow::Result<()> {
+    use crate::proto::{
+        auth_service_server::AuthServiceServer, post_service_server::PostServiceServer,
+    };
+    use tonic::codec::CompressionEncoding;
     use tonic::transport::Server;
 
-    let grpc_service = BlogGrpcService::new(auth_service, blog_service, jwt_service);
+    let grpc_service = BlogGrpcService::new(
+        auth_service,
+        blog_service,
+        comment_service,
+        jwt_service,
+        availability_limiter,
+    );
 
     let addr = addr.parse()?;
 
-    tracing::info!("gRPC server running on {}", addr);
+    let mut builder = Server::builder();
+    if let Some(tls) = tls {
+        builder = builder.tls_config(tls)?;
+        tracing::info!("gRPC server running on {} (TLS)", addr);
+    } else {
+        tracing::info!("gRPC server running on {}", addr);
+    }
+
+    // Сжатые запросы принимаются всегда; ответы сжимаются, только если клиент
+    // объявил gzip в grpc-accept-encoding
+    let mut auth_server =
+        AuthServiceServer::new(grpc_service.clone()).accept_compressed(CompressionEncoding::Gzip);
+    let mut post_server =
+        PostServiceServer::new(grpc_service).accept_compressed(CompressionEncoding::Gzip);
+    if compression {
+        auth_server = auth_server.send_compressed(CompressionEncoding::Gzip);
+        post_server = post_server.send_compressed(CompressionEncoding::Gzip);
+    }
 
-    Server::builder()
-        .add_service(crate::proto::auth_service_server::AuthServiceServer::new(
-            grpc_service.clone(),
-        ))
-        .add_service(crate::proto::post_service_server::PostServiceServer::new(
-            grpc_service,
-        ))
+    builder
+        .layer(GrpcRequestIdLayer)
+        .layer(GrpcMetricsLayer::new(metrics))
+        .add_service(auth_server)
+        .add_service(post_server)
         .serve(addr)
         .await?;
 
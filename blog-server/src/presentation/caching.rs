@@ -0,0 +1,75 @@
+use actix_web::http::header::{
+    CacheControl, CacheDirective, EntityTag, IfNoneMatch, IfUnmodifiedSince,
+};
+use actix_web::{HttpMessage, HttpRequest};
+use chrono::{DateTime, Duration, Utc};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::SystemTime;
+
+/// Default max-age for public read endpoints
+pub const DEFAULT_PUBLIC_MAX_AGE_SECONDS: u32 = 60;
+
+/// Cache-Control policy for HTTP responses
+#[derive(Debug, Clone, Copy)]
+pub struct CachePolicy {
+    public_max_age: u32,
+}
+
+impl CachePolicy {
+    pub fn new(public_max_age: u32) -> Self {
+        Self { public_max_age }
+    }
+
+    /// Directive for successful public reads, cacheable by browsers and CDNs
+    pub fn public(self) -> CacheControl {
+        CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(self.public_max_age),
+        ])
+    }
+
+    /// Directive for responses meant for one viewer (e.g. the author's draft):
+    /// the browser may keep them, shared caches must not
+    pub fn private() -> CacheControl {
+        CacheControl(vec![CacheDirective::Private])
+    }
+
+    /// Directive for authenticated responses that must never be stored
+    pub fn no_store() -> CacheControl {
+        CacheControl(vec![CacheDirective::NoStore])
+    }
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_PUBLIC_MAX_AGE_SECONDS)
+    }
+}
+
+/// Weak ETag of a JSON response body. The tag covers the whole representation,
+/// so the version, naming style and embedded author all change it
+pub fn etag(body: &serde_json::Value) -> EntityTag {
+    // DefaultHasher детерминирован в пределах одной сборки; после обновления сервера
+    // теги могут смениться, что стоит клиенту лишь одного полного ответа
+    let mut hasher = DefaultHasher::new();
+    body.to_string().hash(&mut hasher);
+    EntityTag::new_weak(format!("{:016x}", hasher.finish()))
+}
+
+/// Whether `If-None-Match` of the request matches `etag`, so 304 can be sent instead of the body
+pub fn is_not_modified(req: &HttpRequest, etag: &EntityTag) -> bool {
+    match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        None => false,
+    }
+}
+
+/// Latest `updated_at` the request accepts, taken from `If-Unmodified-Since`;
+/// an invalid date is ignored like a missing header
+pub fn unmodified_since(req: &HttpRequest) -> Option<DateTime<Utc>> {
+    let IfUnmodifiedSince(date) = req.get_header::<IfUnmodifiedSince>()?;
+    // HTTP-дата точна до секунды, а updated_at - до микросекунды:
+    // изменение внутри той же секунды считается "не позже" заголовка
+    Some(DateTime::<Utc>::from(SystemTime::from(date)) + Duration::microseconds(999_999))
+}
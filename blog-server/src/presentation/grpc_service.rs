@@ -1,28 +1,74 @@
-use crate::application::{AuthService, BlogService};
+use crate::application::{AuthService, BlogService, CommentService};
+use crate::domain::comment::CreateCommentRequest as DomainCreateCommentRequest;
 use crate::domain::post::{
-    CreatePostRequest as DomainCreatePostRequest, UpdatePostRequest as DomainUpdatePostRequest,
+    page_offset, pagination, parse_created_bound, BatchItemResult as DomainBatchItemResult,
+    ContentFormat, CreatePostRequest as DomainCreatePostRequest, PostFilter, PostSort,
+    UpdatePostRequest as DomainUpdatePostRequest,
 };
 use crate::domain::user::{
-    LoginUserRequest as DomainLoginRequest, RegisterUserRequest as DomainRegisterRequest,
+    LoginUserRequest as DomainLoginRequest, RegisterUserRequest as DomainRegisterRequest, Role,
+    UpdateUserRequest as DomainUpdateUserRequest,
 };
-use crate::infrastructure::jwt::JwtService;
+use crate::infrastructure::jwt::{bearer_token, JwtService};
+use crate::infrastructure::rate_limit::RateLimiter;
+use crate::presentation::metrics;
 use crate::proto::*;
+use futures::stream::{BoxStream, StreamExt};
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
-// Вспомогательная функция для извлечения user_id из JWT
+// Сообщение Status::unauthenticated для истекшего токена (клиент отличает его от невалидного)
+const TOKEN_EXPIRED_MESSAGE: &str = "Token expired";
+
+// Вспомогательная функция для извлечения user_id и роли из JWT
+#[allow(clippy::result_large_err)]
+fn extract_auth_from_token(token: &str, jwt_service: &JwtService) -> Result<(i64, Role), Status> {
+    // Префикс "Bearer " необязателен; слишком длинный токен отклоняется до декодирования
+    let token = bearer_token(token).map_err(map_domain_error)?;
+
+    jwt_service.verify_token_with_role(token).map_err(|err| {
+        metrics::record_error(&err);
+        match err {
+            crate::domain::DomainError::TokenExpired => {
+                Status::unauthenticated(TOKEN_EXPIRED_MESSAGE)
+            }
+            _ => Status::unauthenticated("Invalid token"),
+        }
+    })
+}
+
 #[allow(clippy::result_large_err)]
 fn extract_user_id_from_token(token: &str, jwt_service: &JwtService) -> Result<i64, Status> {
-    // Remove "Bearer " prefix if present
-    let token = token.strip_prefix("Bearer ").unwrap_or(token);
+    extract_auth_from_token(token, jwt_service).map(|(user_id, _)| user_id)
+}
+
+// user_id администратора; для остальных ролей - PermissionDenied
+#[allow(clippy::result_large_err)]
+fn require_admin<T>(request: &Request<T>, jwt_service: &JwtService) -> Result<i64, Status> {
+    let token = request
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
 
-    jwt_service
-        .verify_token(token)
-        .map_err(|_| Status::unauthenticated("Invalid or expired token"))
+    match extract_auth_from_token(token, jwt_service)? {
+        (user_id, role) if role.is_admin() => Ok(user_id),
+        _ => Err(Status::permission_denied("Admin role required")),
+    }
+}
+
+// user_id из необязательного токена (для публичных методов); невалидный токен игнорируется
+fn optional_user_id<T>(request: &Request<T>, jwt_service: &JwtService) -> Option<i64> {
+    request
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|token| extract_user_id_from_token(token, jwt_service).ok())
 }
 
 // Преобразование доменных ошибок в gRPC статусы
 fn map_domain_error(err: crate::domain::DomainError) -> Status {
+    metrics::record_error(&err);
     match err {
         crate::domain::DomainError::UserNotFound => Status::not_found("User not found"),
         crate::domain::DomainError::PostNotFound => Status::not_found("Post not found"),
@@ -35,23 +81,95 @@ fn map_domain_error(err: crate::domain::DomainError) -> Status {
         crate::domain::DomainError::Forbidden => Status::permission_denied("Forbidden"),
         crate::domain::DomainError::ValidationError(msg) => Status::invalid_argument(msg),
         crate::domain::DomainError::Unauthorized(msg) => Status::unauthenticated(msg),
+        crate::domain::DomainError::TokenExpired => Status::unauthenticated(TOKEN_EXPIRED_MESSAGE),
+        err @ crate::domain::DomainError::TokenInvalid(_) => {
+            Status::unauthenticated(err.to_string())
+        }
         crate::domain::DomainError::DatabaseError(msg) => {
             Status::internal(format!("Database error: {}", msg))
         }
+        err @ crate::domain::DomainError::DatabaseUnavailable => {
+            Status::unavailable(err.to_string())
+        }
         crate::domain::DomainError::InternalError(msg) => Status::internal(msg),
+        crate::domain::DomainError::Conflict(msg) => Status::aborted(msg),
+        // Задержка дублируется в метаданных, как заголовок Retry-After в HTTP
+        err @ crate::domain::DomainError::RateLimited(retry_after) => {
+            let mut status = Status::resource_exhausted(err.to_string());
+            status
+                .metadata_mut()
+                .insert("retry-after", retry_after.into());
+            status
+        }
     }
 }
 
+// page/page_size/offset запроса -> limit/offset по тем же правилам, что и у HTTP;
+// page_size, не переданный клиентом, означает размер страницы по умолчанию,
+// переданный offset важнее page
+#[allow(clippy::result_large_err)]
+fn page_limits(
+    page: i32,
+    page_size: Option<i32>,
+    offset: Option<i64>,
+) -> Result<(i64, i64), Status> {
+    let (limit, _) = pagination(page_size.map(i64::from), offset).map_err(map_domain_error)?;
+    let offset = match offset {
+        Some(offset) => offset,
+        None => page_offset(page.into(), limit).map_err(map_domain_error)?,
+    };
+    Ok((limit, offset))
+}
+
+// Черновики в списке видны только их автору и только при явном published_only = false;
+// неустановленное поле означает true, как отсутствие параметра у HTTP
+fn drafts_visible_to(viewer_id: Option<i64>, published_only: Option<bool>) -> Option<i64> {
+    viewer_id.filter(|_| published_only == Some(false))
+}
+
+// Поле page ответа: страница (с 1), на которую попадает первый элемент. При offset,
+// не кратном limit, ответ с нее не начинается (offset=3, limit=2 возвращает элементы
+// 4-5, а page = 2 - страница из элементов 3-4), поэтому клиенты, листающие по offset,
+// на page не опираются
+fn page_number(offset: i64, limit: i64) -> i32 {
+    (offset / limit + 1) as i32
+}
+
 // Преобразование доменного User в protobuf User
 fn user_to_proto(user: crate::domain::user::UserResponse) -> User {
     User {
         id: user.id,
         username: user.username,
         email: user.email,
-        bio: "".to_string(),
-        avatar_url: "".to_string(),
+        bio: user.bio.unwrap_or_default(),
+        avatar_url: user.avatar_url.unwrap_or_default(),
         created_at: user.created_at.to_rfc3339(),
         updated_at: user.created_at.to_rfc3339(),
+        full_name: user.full_name.unwrap_or_default(),
+        last_login_at: user
+            .last_login_at
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default(),
+    }
+}
+
+// Преобразование доменного Comment в protobuf Comment
+fn comment_to_proto(comment: crate::domain::Comment) -> Comment {
+    Comment {
+        id: comment.id,
+        post_id: comment.post_id,
+        author_id: comment.author_id,
+        content: comment.content,
+        created_at: comment.created_at.to_rfc3339(),
+    }
+}
+
+fn batch_result_to_proto(result: DomainBatchItemResult) -> BatchItemResult {
+    BatchItemResult {
+        index: result.index as u32,
+        ok: result.ok,
+        id: result.id,
+        error: result.error,
     }
 }
 
@@ -62,34 +180,74 @@ fn post_to_proto(post: crate::domain::post::PostResponse) -> Post {
         title: post.title,
         content: post.content,
         author_id: post.author_id,
-        author: None,
-        tags: vec![],
-        likes_count: 0,
+        author: post.author.map(|author| User {
+            id: author.id,
+            username: author.username,
+            avatar_url: author.avatar_url.unwrap_or_default(),
+            ..User::default()
+        }),
+        tags: post.tags,
+        likes_count: post.likes_count as i32,
         views_count: 0,
         created_at: post.created_at.to_rfc3339(),
         updated_at: post.updated_at.to_rfc3339(),
-        published: true,
-        published_at: post.created_at.to_rfc3339(),
+        published: post.published,
+        published_at: post
+            .published_at
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_default(),
+        permalink: post.permalink.unwrap_or_default(),
+        content_length: post.content_length,
+        content_format: post.content_format.as_str().to_string(),
+        excerpt: post.excerpt.unwrap_or_default(),
+        edited: post.edited,
     }
 }
 
+// Доменный запрос из protobuf; пустой content_format - формат по умолчанию,
+// неустановленный published - true, как у HTTP
+fn create_request_from_proto(
+    req: CreatePostRequest,
+) -> Result<DomainCreatePostRequest, crate::domain::DomainError> {
+    let content_format = if req.content_format.trim().is_empty() {
+        ContentFormat::default()
+    } else {
+        req.content_format.parse()?
+    };
+
+    Ok(DomainCreatePostRequest {
+        title: req.title,
+        content: req.content,
+        tags: req.tags,
+        content_format,
+        published: req.published.unwrap_or(true),
+        published_at: parse_created_bound("published_at", Some(&req.published_at))?,
+    })
+}
+
 #[derive(Clone)]
 pub struct BlogGrpcService {
     auth_service: Arc<AuthService>,
     blog_service: Arc<BlogService>,
+    comment_service: Arc<CommentService>,
     jwt_service: Arc<JwtService>,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 impl BlogGrpcService {
     pub fn new(
         auth_service: Arc<AuthService>,
         blog_service: Arc<BlogService>,
+        comment_service: Arc<CommentService>,
         jwt_service: Arc<JwtService>,
+        rate_limiter: Arc<RateLimiter>,
     ) -> Self {
         Self {
             auth_service,
             blog_service,
+            comment_service,
             jwt_service,
+            rate_limiter,
         }
     }
 }
@@ -127,25 +285,20 @@ impl auth_service_server::AuthService for BlogGrpcService {
     ) -> Result<Response<LoginResponse>, Status> {
         let req = request.into_inner();
 
-        let login_req = if !req.username.is_empty() {
-            DomainLoginRequest {
-                username: req.username,
-                password: req.password,
-            }
-        } else {
-            DomainLoginRequest {
-                username: req.email,
-                password: req.password,
-            }
+        let login_req = DomainLoginRequest {
+            username: req.username,
+            email: Some(req.email),
+            password: req.password,
         };
 
         match self.auth_service.login(login_req).await {
-            Ok((token, user)) => {
+            Ok((tokens, user)) => {
                 let response = LoginResponse {
-                    token,
-                    refresh_token: "".to_string(),
+                    token: tokens.access_token,
+                    refresh_token: tokens.refresh_token,
+                    is_admin: user.role.is_admin(),
                     user: Some(user_to_proto(user)),
-                    expires_in: 86400,
+                    expires_in: self.jwt_service.access_token_ttl().num_seconds() as i32,
                 };
                 Ok(Response::new(response))
             }
@@ -153,6 +306,22 @@ impl auth_service_server::AuthService for BlogGrpcService {
         }
     }
 
+    async fn refresh_token(
+        &self,
+        request: Request<RefreshTokenRequest>,
+    ) -> Result<Response<RefreshTokenResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.auth_service.refresh(&req.refresh_token).await {
+            Ok((token, user)) => Ok(Response::new(RefreshTokenResponse {
+                token,
+                user: Some(user_to_proto(user)),
+                expires_in: self.jwt_service.access_token_ttl().num_seconds() as i32,
+            })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
     async fn logout(
         &self,
         _request: Request<LogoutRequest>,
@@ -163,13 +332,123 @@ impl auth_service_server::AuthService for BlogGrpcService {
         }))
     }
 
+    async fn check_availability(
+        &self,
+        request: Request<CheckAvailabilityRequest>,
+    ) -> Result<Response<CheckAvailabilityResponse>, Status> {
+        let client_ip = request
+            .remote_addr()
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        self.rate_limiter
+            .check(&client_ip)
+            .map_err(map_domain_error)?;
+
+        let req = request.into_inner();
+
+        match self
+            .auth_service
+            .check_availability(Some(&req.username), Some(&req.email))
+            .await
+        {
+            Ok(available) => Ok(Response::new(CheckAvailabilityResponse { available })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn change_password(
+        &self,
+        request: Request<ChangePasswordRequest>,
+    ) -> Result<Response<ChangePasswordResponse>, Status> {
+        // Извлекаем токен из метаданных
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        let req = request.into_inner();
+
+        match self
+            .auth_service
+            .change_password(user_id, &req.old_password, &req.new_password)
+            .await
+        {
+            Ok(()) => Ok(Response::new(ChangePasswordResponse {
+                success: true,
+                message: "Password changed".to_string(),
+            })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn get_current_user(
+        &self,
+        request: Request<GetCurrentUserRequest>,
+    ) -> Result<Response<User>, Status> {
+        // Извлекаем токен из метаданных
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        match self.auth_service.current_user(user_id).await {
+            Ok(user) => Ok(Response::new(user_to_proto(user))),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn get_user(&self, request: Request<GetUserRequest>) -> Result<Response<User>, Status> {
+        let viewer_id = optional_user_id(&request, &self.jwt_service);
+        let req = request.into_inner();
+
+        match self.auth_service.get_user(req.id, viewer_id).await {
+            Ok(user) => Ok(Response::new(user_to_proto(user))),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn update_profile(
+        &self,
+        request: Request<UpdateProfileRequest>,
+    ) -> Result<Response<User>, Status> {
+        // Извлекаем токен из метаданных
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        let req = request.into_inner();
+        let update_req = DomainUpdateUserRequest {
+            email: req.email,
+            full_name: req.full_name,
+            bio: req.bio,
+            avatar_url: req.avatar_url,
+        };
+
+        match self.auth_service.update_profile(user_id, update_req).await {
+            Ok(user) => Ok(Response::new(user_to_proto(user))),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
     async fn validate_token(
         &self,
         request: Request<ValidateTokenRequest>,
     ) -> Result<Response<ValidateTokenResponse>, Status> {
         let req = request.into_inner();
 
-        match self.jwt_service.verify_token(&req.token) {
+        // Префикс "Bearer " и длина проверяются так же, как у HTTP
+        match bearer_token(&req.token).and_then(|t| self.jwt_service.verify_token(t)) {
             Ok(user_id) => {
                 let response = ValidateTokenResponse {
                     valid: true,
@@ -189,6 +468,8 @@ impl auth_service_server::AuthService for BlogGrpcService {
 
 #[tonic::async_trait]
 impl post_service_server::PostService for BlogGrpcService {
+    type StreamPostsStream = BoxStream<'static, Result<Post, Status>>;
+
     async fn create_post(
         &self,
         request: Request<CreatePostRequest>,
@@ -200,27 +481,143 @@ impl post_service_server::PostService for BlogGrpcService {
             .and_then(|v| v.to_str().ok())
             .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
 
-        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+        let (user_id, role) = extract_auth_from_token(token, &self.jwt_service)?;
 
-        let req = request.into_inner();
-
-        // Создаем доменный запрос из protobuf
-        let create_req = DomainCreatePostRequest {
-            title: req.title,
-            content: req.content,
+        // Необязательный ключ идемпотентности делает повтор запроса безопасным
+        let idempotency_key = match request.metadata().get("idempotency-key") {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .map_err(|_| Status::invalid_argument("Invalid idempotency key"))?
+                    .to_string(),
+            ),
+            None => None,
         };
 
-        match self.blog_service.create_post(user_id, create_req).await {
+        let create_req =
+            create_request_from_proto(request.into_inner()).map_err(map_domain_error)?;
+
+        match self
+            .blog_service
+            .create_post(user_id, role, create_req, idempotency_key.as_deref())
+            .await
+        {
             Ok(post) => Ok(Response::new(post_to_proto(post))),
             Err(err) => Err(map_domain_error(err)),
         }
     }
 
     async fn get_post(&self, request: Request<GetPostRequest>) -> Result<Response<Post>, Status> {
+        let viewer_id = optional_user_id(&request, &self.jwt_service);
         let req = request.into_inner();
 
-        match self.blog_service.get_post(req.id).await {
-            Ok(post) => Ok(Response::new(post_to_proto(post))),
+        let mut post = self
+            .blog_service
+            .get_post(req.id, viewer_id)
+            .await
+            .map_err(map_domain_error)?;
+        if req.include_author {
+            self.blog_service
+                .include_authors(std::slice::from_mut(&mut post))
+                .await
+                .map_err(map_domain_error)?;
+        }
+
+        Ok(Response::new(post_to_proto(post)))
+    }
+
+    async fn create_posts(
+        &self,
+        request: Request<CreatePostsRequest>,
+    ) -> Result<Response<CreatePostsResponse>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let (user_id, role) = extract_auth_from_token(token, &self.jwt_service)?;
+        let req = request.into_inner();
+
+        if req.best_effort {
+            // Элемент, который не разобрался из protobuf, сразу получает ошибку;
+            // индексы остальных сдвигаются обратно к позициям в запросе
+            let mut positions = Vec::new();
+            let mut create_reqs = Vec::new();
+            let mut failed = Vec::new();
+            for (index, post) in req.posts.into_iter().enumerate() {
+                match create_request_from_proto(post) {
+                    Ok(create_req) => {
+                        positions.push(index);
+                        create_reqs.push(create_req);
+                    }
+                    Err(err) => failed.push(DomainBatchItemResult::failure(index, None, &err)),
+                }
+            }
+
+            let (posts, mut results) = self
+                .blog_service
+                .create_posts_best_effort(user_id, role, create_reqs)
+                .await
+                .map_err(map_domain_error)?;
+            for result in &mut results {
+                result.index = positions[result.index];
+            }
+            results.extend(failed);
+            results.sort_by_key(|result| result.index);
+
+            return Ok(Response::new(CreatePostsResponse {
+                posts: posts.into_iter().map(post_to_proto).collect(),
+                results: results.into_iter().map(batch_result_to_proto).collect(),
+            }));
+        }
+
+        let create_reqs = req
+            .posts
+            .into_iter()
+            .enumerate()
+            .map(|(index, req)| {
+                create_request_from_proto(req).map_err(|err| err.in_batch("posts", index))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(map_domain_error)?;
+
+        match self
+            .blog_service
+            .create_posts(user_id, role, create_reqs)
+            .await
+        {
+            Ok(posts) => Ok(Response::new(CreatePostsResponse {
+                posts: posts.into_iter().map(post_to_proto).collect(),
+                results: Vec::new(),
+            })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn get_posts(
+        &self,
+        request: Request<GetPostsRequest>,
+    ) -> Result<Response<GetPostsResponse>, Status> {
+        let viewer_id = optional_user_id(&request, &self.jwt_service);
+        let req = request.into_inner();
+
+        let result = if req.best_effort {
+            self.blog_service
+                .get_posts_best_effort(req.ids, viewer_id)
+                .await
+        } else {
+            self.blog_service
+                .get_posts(req.ids, viewer_id)
+                .await
+                .map(|posts| (posts, Vec::new()))
+        };
+
+        match result {
+            Ok((posts, results)) => Ok(Response::new(GetPostsResponse {
+                posts: posts.into_iter().map(post_to_proto).collect(),
+                results: results.into_iter().map(batch_result_to_proto).collect(),
+            })),
             Err(err) => Err(map_domain_error(err)),
         }
     }
@@ -236,20 +633,35 @@ impl post_service_server::PostService for BlogGrpcService {
             .and_then(|v| v.to_str().ok())
             .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
 
-        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+        let (user_id, role) = extract_auth_from_token(token, &self.jwt_service)?;
 
         let req = request.into_inner();
 
         // Создаем доменный запрос из protobuf
-        // В protobuf UpdatePostRequest поля опциональные
+        // В protobuf UpdatePostRequest поля опциональные;
+        // repeated tags не отличает "не передано" от пустого списка,
+        // поэтому пустой список оставляет теги без изменений
         let update_req = DomainUpdatePostRequest {
             title: req.title,
             content: req.content,
+            tags: Some(req.tags).filter(|t| !t.is_empty()),
+            content_format: req
+                .content_format
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(map_domain_error)?,
+            published: req.published,
+            expected_updated_at: parse_created_bound(
+                "expected_updated_at",
+                req.expected_updated_at.as_deref(),
+            )
+            .map_err(map_domain_error)?,
         };
 
         match self
             .blog_service
-            .update_post(req.id, user_id, update_req)
+            .update_post(req.id, user_id, role, update_req)
             .await
         {
             Ok(post) => Ok(Response::new(post_to_proto(post))),
@@ -268,11 +680,11 @@ impl post_service_server::PostService for BlogGrpcService {
             .and_then(|v| v.to_str().ok())
             .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
 
-        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+        let (user_id, role) = extract_auth_from_token(token, &self.jwt_service)?;
 
         let req = request.into_inner();
 
-        match self.blog_service.delete_post(req.id, user_id).await {
+        match self.blog_service.delete_post(req.id, user_id, role).await {
             Ok(()) => Ok(Response::new(DeletePostResponse {
                 success: true,
                 message: format!("Post {} deleted", req.id),
@@ -281,31 +693,349 @@ impl post_service_server::PostService for BlogGrpcService {
         }
     }
 
+    async fn delete_posts(
+        &self,
+        request: Request<DeletePostsRequest>,
+    ) -> Result<Response<DeletePostsResponse>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let (user_id, role) = extract_auth_from_token(token, &self.jwt_service)?;
+        let req = request.into_inner();
+
+        let response = if req.best_effort {
+            let results = self
+                .blog_service
+                .delete_posts_best_effort(req.ids, user_id, role)
+                .await
+                .map_err(map_domain_error)?;
+            DeletePostsResponse {
+                deleted: results.iter().filter(|result| result.ok).count() as u64,
+                results: results.into_iter().map(batch_result_to_proto).collect(),
+            }
+        } else {
+            let deleted = self
+                .blog_service
+                .delete_posts(req.ids, user_id, role)
+                .await
+                .map_err(map_domain_error)?;
+            DeletePostsResponse {
+                deleted,
+                results: Vec::new(),
+            }
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn delete_my_posts(
+        &self,
+        request: Request<DeleteMyPostsRequest>,
+    ) -> Result<Response<DeleteMyPostsResponse>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        match self.blog_service.delete_posts_by_author(user_id).await {
+            Ok(deleted) => Ok(Response::new(DeleteMyPostsResponse { deleted })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn delete_user_posts(
+        &self,
+        request: Request<DeleteUserPostsRequest>,
+    ) -> Result<Response<DeleteMyPostsResponse>, Status> {
+        let admin_id = require_admin(&request, &self.jwt_service)?;
+
+        let req = request.into_inner();
+        tracing::info!(
+            "Admin {} deleting all posts of user_id={}",
+            admin_id,
+            req.user_id
+        );
+
+        match self.blog_service.delete_posts_by_author(req.user_id).await {
+            Ok(deleted) => Ok(Response::new(DeleteMyPostsResponse { deleted })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn rename_tag(
+        &self,
+        request: Request<RenameTagRequest>,
+    ) -> Result<Response<RenameTagResponse>, Status> {
+        let admin_id = require_admin(&request, &self.jwt_service)?;
+
+        let req = request.into_inner();
+        tracing::info!(
+            "Admin {} renaming tag '{}' to '{}'",
+            admin_id,
+            req.old_name,
+            req.new_name
+        );
+
+        match self
+            .blog_service
+            .rename_tag(&req.old_name, &req.new_name)
+            .await
+        {
+            Ok(renamed) => Ok(Response::new(RenameTagResponse { renamed })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
     async fn list_posts(
         &self,
         request: Request<ListPostsRequest>,
     ) -> Result<Response<ListPostsResponse>, Status> {
+        let viewer_id = optional_user_id(&request, &self.jwt_service);
         let req = request.into_inner();
 
-        let limit = if req.page_size > 0 && req.page_size <= 100 {
-            req.page_size as i64
+        let (limit, offset) = page_limits(req.page, req.page_size, req.offset)?;
+
+        let sort = if req.sort.trim().is_empty() {
+            PostSort::default()
         } else {
-            10
+            req.sort.parse().map_err(map_domain_error)?
         };
 
-        let offset = if req.page > 0 {
-            ((req.page - 1) * limit as i32) as i64
+        let filter = PostFilter {
+            author_id: (req.author_id > 0).then_some(req.author_id),
+            author_username: Some(req.author_username.clone()).filter(|u| !u.is_empty()),
+            search: Some(req.search_query.clone()),
+            tag: Some(req.tag.clone()).filter(|t| !t.is_empty()),
+            drafts_visible_to: drafts_visible_to(viewer_id, req.published_only),
+            sort,
+            after: None,
+            created_after: parse_created_bound("created_after", Some(&req.created_after))
+                .map_err(map_domain_error)?,
+            created_before: parse_created_bound("created_before", Some(&req.created_before))
+                .map_err(map_domain_error)?,
+        };
+
+        let result = match self.blog_service.list_posts(limit, offset, filter).await {
+            Ok(mut page) if req.include_author => self
+                .blog_service
+                .include_authors(&mut page.posts)
+                .await
+                .map(|()| page),
+            other => other,
+        }
+        .map(|page| {
+            if req.summary {
+                page.into_summary()
+            } else {
+                page
+            }
+        });
+
+        match result {
+            Ok(page) => {
+                let total = page.total;
+                let response = ListPostsResponse {
+                    posts: page.posts.into_iter().map(post_to_proto).collect(),
+                    total_count: total as i32,
+                    page: page_number(offset, limit),
+                    page_size: limit as i32,
+                    total_pages: ((total + limit - 1) / limit) as i32,
+                    next_cursor: page.next_cursor.unwrap_or_default(),
+                };
+                Ok(Response::new(response))
+            }
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn list_posts_cursor(
+        &self,
+        request: Request<ListPostsCursorRequest>,
+    ) -> Result<Response<ListPostsCursorResponse>, Status> {
+        let viewer_id = optional_user_id(&request, &self.jwt_service);
+        let req = request.into_inner();
+
+        let (limit, _) = pagination(req.limit.map(i64::from), None).map_err(map_domain_error)?;
+
+        let sort = if req.sort.trim().is_empty() {
+            PostSort::default()
         } else {
-            0
+            req.sort.parse().map_err(map_domain_error)?
+        };
+
+        let after = if req.cursor.trim().is_empty() {
+            None
+        } else {
+            Some(req.cursor.parse().map_err(map_domain_error)?)
+        };
+
+        let filter = PostFilter {
+            author_id: (req.author_id > 0).then_some(req.author_id),
+            author_username: Some(req.author_username).filter(|u| !u.is_empty()),
+            search: Some(req.search_query),
+            tag: Some(req.tag).filter(|t| !t.is_empty()),
+            drafts_visible_to: drafts_visible_to(viewer_id, req.published_only),
+            sort,
+            after,
+            created_after: parse_created_bound("created_after", Some(&req.created_after))
+                .map_err(map_domain_error)?,
+            created_before: parse_created_bound("created_before", Some(&req.created_before))
+                .map_err(map_domain_error)?,
+        };
+
+        let result = match self.blog_service.list_posts(limit, 0, filter).await {
+            Ok(mut page) if req.include_author => self
+                .blog_service
+                .include_authors(&mut page.posts)
+                .await
+                .map(|()| page),
+            other => other,
+        }
+        .map(|page| {
+            if req.summary {
+                page.into_summary()
+            } else {
+                page
+            }
+        });
+
+        match result {
+            Ok(page) => Ok(Response::new(ListPostsCursorResponse {
+                posts: page.posts.into_iter().map(post_to_proto).collect(),
+                next_cursor: page.next_cursor.unwrap_or_default(),
+                total_count: page.total as i32,
+            })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn count_posts(
+        &self,
+        request: Request<CountPostsRequest>,
+    ) -> Result<Response<CountPostsResponse>, Status> {
+        let viewer_id = optional_user_id(&request, &self.jwt_service);
+        let req = request.into_inner();
+
+        let filter = PostFilter {
+            author_id: (req.author_id > 0).then_some(req.author_id),
+            author_username: Some(req.author_username).filter(|u| !u.is_empty()),
+            search: Some(req.search_query),
+            tag: Some(req.tag).filter(|t| !t.is_empty()),
+            drafts_visible_to: drafts_visible_to(viewer_id, req.published_only),
+            sort: PostSort::default(),
+            after: None,
+            created_after: parse_created_bound("created_after", Some(&req.created_after))
+                .map_err(map_domain_error)?,
+            created_before: parse_created_bound("created_before", Some(&req.created_before))
+                .map_err(map_domain_error)?,
         };
 
-        match self.blog_service.list_posts(limit, offset).await {
+        match self.blog_service.count_posts(filter).await {
+            Ok(count) => Ok(Response::new(CountPostsResponse { count })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn stream_posts(
+        &self,
+        _request: Request<StreamPostsRequest>,
+    ) -> Result<Response<Self::StreamPostsStream>, Status> {
+        tracing::info!("New gRPC post subscriber");
+
+        let posts = self
+            .blog_service
+            .subscribe_posts()
+            .map(post_to_proto)
+            .map(Ok)
+            .boxed();
+
+        Ok(Response::new(posts))
+    }
+
+    async fn like_post(
+        &self,
+        request: Request<LikePostRequest>,
+    ) -> Result<Response<LikePostResponse>, Status> {
+        // Извлекаем токен из метаданных
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        let req = request.into_inner();
+
+        match self.blog_service.like_post(req.post_id, user_id).await {
+            Ok(likes_count) => Ok(Response::new(LikePostResponse {
+                post_id: req.post_id,
+                likes_count,
+            })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn unlike_post(
+        &self,
+        request: Request<LikePostRequest>,
+    ) -> Result<Response<LikePostResponse>, Status> {
+        // Извлекаем токен из метаданных
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        let req = request.into_inner();
+
+        match self.blog_service.unlike_post(req.post_id, user_id).await {
+            Ok(likes_count) => Ok(Response::new(LikePostResponse {
+                post_id: req.post_id,
+                likes_count,
+            })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn dashboard(
+        &self,
+        request: Request<DashboardRequest>,
+    ) -> Result<Response<DashboardResponse>, Status> {
+        // Извлекаем токен из метаданных
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        let req = request.into_inner();
+
+        let (limit, offset) = page_limits(req.page, req.page_size, req.offset)?;
+
+        match self.blog_service.dashboard(user_id, limit, offset).await {
             Ok((posts, total)) => {
-                let response = ListPostsResponse {
-                    posts: posts.into_iter().map(post_to_proto).collect(),
+                let response = DashboardResponse {
+                    posts: posts
+                        .into_iter()
+                        .map(|entry| crate::proto::DashboardPost {
+                            status: entry.status.as_str().to_string(),
+                            post: Some(post_to_proto(entry.post)),
+                        })
+                        .collect(),
                     total_count: total as i32,
-                    page: req.page,
-                    page_size: req.page_size,
+                    page: page_number(offset, limit),
+                    page_size: limit as i32,
                     total_pages: ((total + limit - 1) / limit) as i32,
                 };
                 Ok(Response::new(response))
@@ -313,4 +1043,434 @@ impl post_service_server::PostService for BlogGrpcService {
             Err(err) => Err(map_domain_error(err)),
         }
     }
+
+    async fn add_comment(
+        &self,
+        request: Request<AddCommentRequest>,
+    ) -> Result<Response<Comment>, Status> {
+        // Извлекаем токен из метаданных
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing authorization token"))?;
+
+        let user_id = extract_user_id_from_token(token, &self.jwt_service)?;
+
+        let req = request.into_inner();
+
+        match self
+            .comment_service
+            .add_comment(
+                req.post_id,
+                user_id,
+                DomainCreateCommentRequest {
+                    content: req.content,
+                },
+            )
+            .await
+        {
+            Ok(comment) => Ok(Response::new(comment_to_proto(comment))),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+
+    async fn list_comments(
+        &self,
+        request: Request<ListCommentsRequest>,
+    ) -> Result<Response<ListCommentsResponse>, Status> {
+        let viewer_id = optional_user_id(&request, &self.jwt_service);
+        let req = request.into_inner();
+
+        let (limit, offset) = page_limits(req.page, req.page_size, req.offset)?;
+
+        match self
+            .comment_service
+            .list_comments(req.post_id, viewer_id, limit, offset)
+            .await
+        {
+            Ok((comments, total)) => Ok(Response::new(ListCommentsResponse {
+                comments: comments.into_iter().map(comment_to_proto).collect(),
+                total_count: total as i32,
+                page: page_number(offset, limit),
+                page_size: limit as i32,
+                total_pages: ((total + limit - 1) / limit) as i32,
+            })),
+            Err(err) => Err(map_domain_error(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::comment_repository::PostgresCommentRepository;
+    use crate::data::memory::InMemoryPostRepository;
+    use crate::data::user_repository::PostgresUserRepository;
+    use post_service_server::PostService;
+    use sqlx::postgres::PgPoolOptions;
+    use std::time::Duration;
+    use tonic::Code;
+
+    const JWT_SECRET: &str = "test-secret-key-for-grpc-tests-12345678";
+    const AUTHOR_ID: i64 = 1;
+
+    // Посты в памяти; пользователи и комментарии этим тестам не нужны, поэтому
+    // их репозитории получают ленивый пул, который так и не подключается
+    fn service() -> (BlogGrpcService, Arc<BlogService>, Arc<JwtService>) {
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let post_repo = Arc::new(InMemoryPostRepository::new());
+        let jwt_service =
+            Arc::new(JwtService::new(JWT_SECRET, chrono::Duration::hours(1)).unwrap());
+        let blog_service = Arc::new(BlogService::new(post_repo.clone(), None));
+        let service = BlogGrpcService::new(
+            Arc::new(AuthService::new(
+                Arc::new(PostgresUserRepository::new(pool.clone())),
+                jwt_service.clone(),
+            )),
+            blog_service.clone(),
+            Arc::new(CommentService::new(
+                Arc::new(PostgresCommentRepository::new(pool)),
+                post_repo,
+            )),
+            jwt_service.clone(),
+            Arc::new(RateLimiter::new(1000, Duration::from_secs(60))),
+        );
+        (service, blog_service, jwt_service)
+    }
+
+    fn with_token<T>(message: T, jwt_service: &JwtService, user_id: i64) -> Request<T> {
+        let token = jwt_service
+            .generate_token(user_id, format!("user{}", user_id), Role::User)
+            .unwrap();
+        let mut request = Request::new(message);
+        request.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        request
+    }
+
+    #[tokio::test]
+    async fn get_post_shows_draft_to_its_author_only() {
+        let (service, blog_service, jwt_service) = service();
+        let draft = blog_service
+            .create_post(
+                AUTHOR_ID,
+                Role::User,
+                DomainCreatePostRequest {
+                    title: "Title".to_string(),
+                    content: "Content".to_string(),
+                    tags: Vec::new(),
+                    content_format: ContentFormat::Plain,
+                    published: false,
+                    published_at: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let get = || GetPostRequest {
+            id: draft.id,
+            ..Default::default()
+        };
+
+        let anonymous = service.get_post(Request::new(get())).await.unwrap_err();
+        assert_eq!(anonymous.code(), Code::NotFound);
+
+        let stranger = service
+            .get_post(with_token(get(), &jwt_service, AUTHOR_ID + 1))
+            .await
+            .unwrap_err();
+        assert_eq!(stranger.code(), Code::NotFound);
+
+        let post = service
+            .get_post(with_token(get(), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(post.id, draft.id);
+        assert!(!post.published);
+    }
+
+    async fn create_published(blog_service: &BlogService, count: usize) {
+        for i in 0..count {
+            blog_service
+                .create_post(
+                    AUTHOR_ID,
+                    Role::User,
+                    DomainCreatePostRequest {
+                        title: format!("Post {}", i + 1),
+                        content: "Content".to_string(),
+                        tags: Vec::new(),
+                        content_format: ContentFormat::Plain,
+                        published: true,
+                        published_at: None,
+                    },
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    // Тот же список через HTTP-обработчик: статус и JSON ответа
+    async fn http_list(
+        blog_service: Arc<BlogService>,
+        query: &str,
+    ) -> (actix_web::http::StatusCode, serde_json::Value) {
+        use crate::presentation::caching::CachePolicy;
+        use crate::presentation::http_handlers;
+        use crate::presentation::json::JsonNaming;
+        use actix_web::{test, web, App};
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(JsonNaming::default()))
+                .app_data(web::Data::new(CachePolicy::default()))
+                .route("/api/posts", web::get().to(http_handlers::list_posts)),
+        )
+        .await;
+        let request = test::TestRequest::get()
+            .uri(&format!("/api/posts?{}", query))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        let status = response.status();
+        (status, test::read_body_json(response).await)
+    }
+
+    fn http_ids(body: &serde_json::Value) -> Vec<i64> {
+        body["posts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|post| post["id"].as_i64().unwrap())
+            .collect()
+    }
+
+    async fn grpc_list(
+        service: &BlogGrpcService,
+        limit: i32,
+        offset: i64,
+    ) -> Result<ListPostsResponse, Status> {
+        service
+            .list_posts(Request::new(ListPostsRequest {
+                page_size: Some(limit),
+                offset: Some(offset),
+                ..Default::default()
+            }))
+            .await
+            .map(Response::into_inner)
+    }
+
+    #[actix_web::test]
+    async fn list_posts_offset_slice_matches_http() {
+        let (service, blog_service, _) = service();
+        create_published(&blog_service, 12).await;
+
+        // offset не кратен limit: срез тот же, что у HTTP, а page - страница первого поста
+        for (offset, limit, page) in [(3, 2, 2), (5, 10, 1)] {
+            let (status, body) = http_list(
+                blog_service.clone(),
+                &format!("limit={}&offset={}", limit, offset),
+            )
+            .await;
+            assert_eq!(status, actix_web::http::StatusCode::OK);
+
+            let response = grpc_list(&service, limit, offset).await.unwrap();
+            let grpc_ids: Vec<i64> = response.posts.iter().map(|post| post.id).collect();
+
+            assert_eq!(
+                grpc_ids,
+                http_ids(&body),
+                "offset={} limit={}",
+                offset,
+                limit
+            );
+            assert_eq!(grpc_ids.len(), (12 - offset as usize).min(limit as usize));
+            assert_eq!(response.page, page);
+            assert_eq!(response.page_size, limit);
+            assert_eq!(response.total_count, 12);
+        }
+    }
+
+    #[actix_web::test]
+    async fn list_posts_rejects_invalid_pagination_like_http() {
+        let (service, blog_service, _) = service();
+        create_published(&blog_service, 3).await;
+
+        // Оба транспорта отклоняют (а не подрезают) неверные limit/offset с одной ошибкой
+        for (limit, offset) in [(0, 0), (1000, 0), (10, -1)] {
+            let (status, body) = http_list(
+                blog_service.clone(),
+                &format!("limit={}&offset={}", limit, offset),
+            )
+            .await;
+            assert_eq!(status, actix_web::http::StatusCode::BAD_REQUEST);
+
+            let err = grpc_list(&service, limit, offset).await.unwrap_err();
+            assert_eq!(err.code(), Code::InvalidArgument);
+            // HTTP добавляет к тексту префикс вида ошибки ("Validation error: ...")
+            let http_error = body["error"].as_str().unwrap();
+            assert!(http_error.ends_with(err.message()), "{}", http_error);
+        }
+    }
+
+    #[test]
+    fn database_unavailable_maps_to_unavailable_status() {
+        let status = map_domain_error(crate::domain::DomainError::DatabaseUnavailable);
+        assert_eq!(status.code(), Code::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn list_posts_without_published_only_hides_callers_drafts() {
+        let (service, blog_service, jwt_service) = service();
+        create_published(&blog_service, 1).await;
+        let draft = blog_service
+            .create_post(
+                AUTHOR_ID,
+                Role::User,
+                DomainCreatePostRequest {
+                    title: "Draft".to_string(),
+                    content: "Content".to_string(),
+                    tags: Vec::new(),
+                    content_format: ContentFormat::Plain,
+                    published: false,
+                    published_at: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Поле не передано: как у HTTP, только опубликованные посты, даже с токеном автора
+        let list = |published_only| ListPostsRequest {
+            published_only,
+            ..Default::default()
+        };
+        let posts = service
+            .list_posts(with_token(list(None), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(posts.total_count, 1);
+        assert!(posts.posts.iter().all(|post| post.id != draft.id));
+
+        let count = |published_only| CountPostsRequest {
+            published_only,
+            ..Default::default()
+        };
+        let counted = service
+            .count_posts(with_token(count(None), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(counted.count, 1);
+
+        // Явное false показывает автору его черновик
+        let posts = service
+            .list_posts(with_token(list(Some(false)), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(posts.posts.iter().any(|post| post.id == draft.id));
+        let counted = service
+            .count_posts(with_token(count(Some(false)), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(counted.count, 2);
+    }
+
+    #[tokio::test]
+    async fn create_post_without_published_field_publishes_like_http() {
+        let (service, blog_service, jwt_service) = service();
+        let create = |published, published_at: &str| CreatePostRequest {
+            title: "Title".to_string(),
+            content: "Content".to_string(),
+            published,
+            published_at: published_at.to_string(),
+            ..Default::default()
+        };
+
+        let post = service
+            .create_post(with_token(create(None, ""), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(post.published);
+        assert!(blog_service.get_post(post.id, None).await.is_ok());
+
+        let draft = service
+            .create_post(with_token(create(Some(false), ""), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(!draft.published);
+        assert!(blog_service.get_post(draft.id, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn create_post_schedules_publication_with_published_at() {
+        let (service, blog_service, jwt_service) = service();
+        let at = (chrono::Utc::now() + chrono::Duration::days(1)).to_rfc3339();
+        let request = |published_at: &str| CreatePostRequest {
+            title: "Title".to_string(),
+            content: "Content".to_string(),
+            published_at: published_at.to_string(),
+            ..Default::default()
+        };
+
+        let scheduled = service
+            .create_post(with_token(request(&at), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap()
+            .into_inner();
+        // Опубликован, но до published_at виден только автору
+        assert!(scheduled.published);
+        assert!(blog_service.get_post(scheduled.id, None).await.is_err());
+        assert!(blog_service
+            .get_post(scheduled.id, Some(AUTHOR_ID))
+            .await
+            .is_ok());
+
+        let err = service
+            .create_post(with_token(request("tomorrow"), &jwt_service, AUTHOR_ID))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn validate_token_accepts_the_same_forms_as_http() {
+        use auth_service_server::AuthService as _;
+
+        let (service, _, jwt_service) = service();
+        let token = jwt_service
+            .generate_token(AUTHOR_ID, "user1".to_string(), Role::User)
+            .unwrap();
+        let validate = |token: String| async {
+            service
+                .validate_token(Request::new(ValidateTokenRequest { token }))
+                .await
+                .unwrap()
+                .into_inner()
+        };
+
+        for form in [
+            token.clone(),
+            format!("Bearer {}", token),
+            format!("  bearer   {}  ", token),
+        ] {
+            let response = validate(form).await;
+            assert!(response.valid);
+            assert_eq!(response.user_id, AUTHOR_ID);
+        }
+        for form in ["".to_string(), "Bearer".to_string(), "x".repeat(10_000)] {
+            assert!(!validate(form).await.valid);
+        }
+    }
 }
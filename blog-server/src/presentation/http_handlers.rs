@@ -1,14 +1,37 @@
-use crate::application::{AuthService, BlogService};
-use crate::domain::post::{CreatePostRequest, PostResponse, UpdatePostRequest};
-use crate::domain::user::{LoginUserRequest, RegisterUserRequest, UserResponse};
-use crate::domain::DomainError;
+use crate::application::{AuthService, BlogService, CommentService};
+use crate::domain::comment::CreateCommentRequest;
+use crate::domain::post::{
+    pagination, parse_created_bound, BatchItemResult, CreatePostRequest, DashboardPost, PostFilter,
+    PostSort, UpdatePostRequest,
+};
+use crate::domain::user::{
+    LoginUserRequest, RegisterUserRequest, Role, UpdateUserRequest, UserResponse,
+};
+use crate::domain::{Comment, DomainError};
+use crate::infrastructure::database;
+use crate::infrastructure::jwt::{bearer_token, JwtService};
+use crate::infrastructure::metrics::Metrics;
+use crate::infrastructure::rate_limit::RateLimiter;
+use crate::presentation::caching::{self, CachePolicy};
+use crate::presentation::json::JsonNaming;
+use crate::presentation::metrics;
+use crate::presentation::request_id;
+use crate::presentation::versioning::{self, ApiVersion, VersionedPost, ACCEPT_VERSION_HEADER};
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::http::header::{CacheControl, CacheDirective, ETag, AUTHORIZATION, VARY};
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use futures::stream::{self, StreamExt};
+use sqlx::PgPool;
 use std::sync::Arc;
 
 // Структура для ответа с токеном
 #[derive(serde::Serialize)]
 struct AuthResponse {
     token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+    // Клиент показывает инструменты модерации только администраторам
+    is_admin: bool,
     user: UserResponse,
 }
 
@@ -19,15 +42,203 @@ pub struct PaginationQuery {
     pub offset: Option<i64>,
 }
 
+// Состав ответа: ?include=author (через запятую) добавляет данные,
+// ?fields=summary в списке убирает полный текст, оставляя excerpt
+#[derive(serde::Deserialize)]
+pub struct IncludeQuery {
+    pub include: Option<String>,
+    pub fields: Option<String>,
+}
+
+impl IncludeQuery {
+    /// Whether the embedded author was requested; unknown values are rejected
+    fn author(&self) -> Result<bool, DomainError> {
+        let mut author = false;
+        for item in self.include.as_deref().unwrap_or_default().split(',') {
+            match item.trim() {
+                "" => {}
+                "author" => author = true,
+                other => {
+                    return Err(DomainError::ValidationError(format!(
+                        "Unknown include '{}', expected: author",
+                        other
+                    )))
+                }
+            }
+        }
+        Ok(author)
+    }
+
+    /// Whether full content should be omitted; unknown values are rejected
+    fn summary(&self) -> Result<bool, DomainError> {
+        match self.fields.as_deref().map(str::trim) {
+            None | Some("") | Some("full") => Ok(false),
+            Some("summary") => Ok(true),
+            Some(other) => Err(DomainError::ValidationError(format!(
+                "Unknown fields '{}', expected one of: full, summary",
+                other
+            ))),
+        }
+    }
+}
+
+// Фильтры для списка постов
+#[derive(serde::Deserialize)]
+pub struct PostFilterQuery {
+    pub author: Option<String>,
+    pub author_id: Option<i64>,
+    pub search: Option<String>,
+    pub tag: Option<String>,
+    /// created_desc (по умолчанию), created_asc, title_asc или updated_desc
+    pub sort: Option<String>,
+    /// next_cursor предыдущей страницы; используется вместо offset
+    pub cursor: Option<String>,
+    /// Диапазон дат создания (RFC3339, границы включительно)
+    pub after: Option<String>,
+    pub before: Option<String>,
+}
+
+impl TryFrom<PostFilterQuery> for PostFilter {
+    type Error = DomainError;
+
+    fn try_from(query: PostFilterQuery) -> Result<Self, Self::Error> {
+        let sort = match query.sort.as_deref().filter(|s| !s.trim().is_empty()) {
+            Some(sort) => sort.parse()?,
+            None => PostSort::default(),
+        };
+        let after = match query.cursor.as_deref().filter(|c| !c.trim().is_empty()) {
+            Some(cursor) => Some(cursor.parse()?),
+            None => None,
+        };
+        let created_after = parse_created_bound("after", query.after.as_deref())?;
+        let created_before = parse_created_bound("before", query.before.as_deref())?;
+
+        Ok(Self {
+            author_id: query.author_id,
+            author_username: query.author.filter(|a| !a.trim().is_empty()),
+            search: query.search,
+            tag: query.tag,
+            // Публичный список: черновики не показываются никому
+            drafts_visible_to: None,
+            sort,
+            after,
+            created_after,
+            created_before,
+        })
+    }
+}
+
+// Запрос на обмен refresh-токена на новый access-токен
+#[derive(serde::Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+// Запрос на смену пароля
+#[derive(serde::Deserialize)]
+pub struct ChangePasswordRequest {
+    pub old_password: String,
+    pub new_password: String,
+}
+
+// Запрос на проверку токена
+#[derive(serde::Deserialize)]
+pub struct ValidateTokenRequest {
+    pub token: String,
+}
+
+// Ответ на проверку токена (аналог gRPC ValidateTokenResponse)
+#[derive(serde::Serialize)]
+struct ValidateTokenResponse {
+    valid: bool,
+    user_id: Option<i64>,
+}
+
+// Параметры проверки доступности имени пользователя / email
+#[derive(serde::Deserialize)]
+pub struct AvailabilityQuery {
+    pub username: Option<String>,
+    pub email: Option<String>,
+}
+
 // Структура для ответа со списком постов
 #[derive(serde::Serialize)]
 struct PostsResponse {
-    posts: Vec<PostResponse>,
+    posts: Vec<VersionedPost>,
+    total: i64,
+    limit: i64,
+    offset: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+}
+
+// Запрос на получение или удаление нескольких постов по id;
+// best_effort добавляет в ответ результат по каждому id
+#[derive(serde::Deserialize)]
+pub struct BatchPostsRequest {
+    pub ids: Vec<i64>,
+    #[serde(default)]
+    pub best_effort: bool,
+}
+
+// Запрос на создание нескольких постов: одной транзакцией или,
+// при best_effort, по одному с результатом для каждого
+#[derive(serde::Deserialize)]
+pub struct BatchCreatePostsRequest {
+    pub posts: Vec<CreatePostRequest>,
+    #[serde(default)]
+    pub best_effort: bool,
+}
+
+// Запрос на переименование тега во всех постах (модерация)
+#[derive(serde::Deserialize)]
+pub struct RenameTagRequest {
+    pub old_name: String,
+    pub new_name: String,
+}
+
+// Ответ со списком постов в порядке запрошенных id
+#[derive(serde::Serialize)]
+struct BatchPostsResponse {
+    posts: Vec<VersionedPost>,
+    // Только в режиме best_effort
+    #[serde(skip_serializing_if = "Option::is_none")]
+    results: Option<Vec<BatchItemResult>>,
+}
+
+// Ответ на пакетное удаление
+#[derive(serde::Serialize)]
+struct BatchDeleteResponse {
+    deleted: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    results: Option<Vec<BatchItemResult>>,
+}
+
+// Структура для ответа с постами автора (включая черновики)
+#[derive(serde::Serialize)]
+struct DashboardResponse {
+    posts: Vec<DashboardPost>,
+    total: i64,
+    limit: i64,
+    offset: i64,
+}
+
+// Страница комментариев поста, от старых к новым
+#[derive(serde::Serialize)]
+struct CommentsResponse {
+    comments: Vec<Comment>,
     total: i64,
     limit: i64,
     offset: i64,
 }
 
+// Ответ на лайк / снятие лайка
+#[derive(serde::Serialize)]
+struct LikeResponse {
+    post_id: i64,
+    likes_count: i64,
+}
+
 // Вспомогательная функция для извлечения user_id из request extensions
 fn get_user_id_from_request(req: &HttpRequest) -> Result<i64, DomainError> {
     req.extensions()
@@ -38,77 +249,485 @@ fn get_user_id_from_request(req: &HttpRequest) -> Result<i64, DomainError> {
         ))
 }
 
+// Роль из токена (кладется JWT middleware рядом с user_id)
+fn get_role_from_request(req: &HttpRequest) -> Role {
+    req.extensions().get::<Role>().copied().unwrap_or_default()
+}
+
+// Пользователь из необязательного Bearer-токена на публичных маршрутах (без JWT middleware);
+// недействительный токен равносилен его отсутствию
+fn optional_viewer_id(req: &HttpRequest, jwt_service: &JwtService) -> Option<i64> {
+    req.headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| bearer_token(v).ok())
+        .and_then(|token| jwt_service.verify_token(token).ok())
+}
+
+/// Header with a client-generated key that makes `create_post` safe to retry
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Retry-After for 503 responses caused by an unavailable database
+const DATABASE_RETRY_AFTER_SECONDS: u64 = 1;
+
+/// Default limit of a JSON request body (1 MiB), enough for the longest allowed post
+pub const DEFAULT_JSON_PAYLOAD_LIMIT: usize = 1024 * 1024;
+
+// Слишком большое JSON-тело - 413 в общем формате {"error", "code"};
+// остальные ошибки разбора остаются стандартными ответами actix
+pub fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    match err {
+        JsonPayloadError::Overflow { limit }
+        | JsonPayloadError::OverflowKnownLength { limit, .. } => {
+            let response = HttpResponse::PayloadTooLarge().json(request_id::error_json(
+                format!("Request body is larger than {} bytes", limit),
+                "PAYLOAD_TOO_LARGE",
+            ));
+            InternalError::from_response(err, response).into()
+        }
+        err => err.into(),
+    }
+}
+
 // Преобразование DomainError в HttpResponse
+
 fn error_to_response(err: DomainError) -> HttpResponse {
+    metrics::record_error(&err);
+    let code = err.code();
+
+    if let DomainError::RateLimited(retry_after) = err {
+        return HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.to_string()))
+            .json(request_id::error_json(err.to_string(), code));
+    }
+
     let status_code = err.to_status_code();
-    let message = err.to_string();
+    let body = request_id::error_json(err.to_string(), code);
 
     match status_code {
-        400 => HttpResponse::BadRequest().json(serde_json::json!({ "error": message })),
-        401 => HttpResponse::Unauthorized().json(serde_json::json!({ "error": message })),
-        403 => HttpResponse::Forbidden().json(serde_json::json!({ "error": message })),
-        404 => HttpResponse::NotFound().json(serde_json::json!({ "error": message })),
-        409 => HttpResponse::Conflict().json(serde_json::json!({ "error": message })),
-        _ => HttpResponse::InternalServerError()
-            .json(serde_json::json!({ "error": "Internal server error" })),
+        400 => HttpResponse::BadRequest().json(body),
+        401 => HttpResponse::Unauthorized().json(body),
+        403 => HttpResponse::Forbidden().json(body),
+        404 => HttpResponse::NotFound().json(body),
+        409 => HttpResponse::Conflict().json(body),
+        // Клиент может повторить запрос, когда освободится (или восстановится) соединение
+        503 => HttpResponse::ServiceUnavailable()
+            .insert_header(("Retry-After", DATABASE_RETRY_AFTER_SECONDS.to_string()))
+            .json(body),
+        _ => HttpResponse::InternalServerError().json(request_id::error_json(
+            "Internal server error".to_string(),
+            code,
+        )),
+    }
+}
+
+// ============== Health ==============
+
+// 503 и db: false, пока Postgres недоступен - балансировщик выводит инстанс из ротации
+pub async fn health(pool: web::Data<PgPool>) -> impl Responder {
+    if database::ping(&pool).await {
+        HttpResponse::Ok().json(serde_json::json!({ "status": "ok", "db": true }))
+    } else {
+        HttpResponse::ServiceUnavailable()
+            .insert_header(("Retry-After", DATABASE_RETRY_AFTER_SECONDS.to_string()))
+            .json(serde_json::json!({ "status": "unavailable", "db": false }))
     }
 }
 
+// Состояние пула соединений; маршрут регистрируется только при DEBUG_ENDPOINTS=true
+pub async fn pool_stats(pool: web::Data<PgPool>) -> impl Responder {
+    HttpResponse::Ok().json(database::pool_stats(&pool))
+}
+
+// Метрики в текстовом формате Prometheus; маршрут регистрируется только при ENABLE_METRICS=true
+pub async fn metrics(metrics: web::Data<Arc<Metrics>>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(metrics.render())
+}
+
 // ============== Auth Handlers ==============
 
 pub async fn register(
     auth_service: web::Data<Arc<AuthService>>,
+    naming: web::Data<JsonNaming>,
     req: web::Json<RegisterUserRequest>,
 ) -> impl Responder {
     match auth_service.register(req.into_inner()).await {
-        Ok((token, user)) => HttpResponse::Created().json(AuthResponse { token, user }),
+        Ok((token, user)) => HttpResponse::Created().json(naming.to_value(&AuthResponse {
+            token,
+            refresh_token: None,
+            is_admin: user.role.is_admin(),
+            user,
+        })),
         Err(err) => error_to_response(err),
     }
 }
 
 pub async fn login(
     auth_service: web::Data<Arc<AuthService>>,
+    naming: web::Data<JsonNaming>,
     req: web::Json<LoginUserRequest>,
 ) -> impl Responder {
     match auth_service.login(req.into_inner()).await {
-        Ok((token, user)) => HttpResponse::Ok().json(AuthResponse { token, user }),
+        Ok((tokens, user)) => HttpResponse::Ok().json(naming.to_value(&AuthResponse {
+            token: tokens.access_token,
+            refresh_token: Some(tokens.refresh_token),
+            is_admin: user.role.is_admin(),
+            user,
+        })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn refresh_token(
+    auth_service: web::Data<Arc<AuthService>>,
+    naming: web::Data<JsonNaming>,
+    req: web::Json<RefreshTokenRequest>,
+) -> impl Responder {
+    match auth_service.refresh(&req.refresh_token).await {
+        Ok((token, user)) => HttpResponse::Ok().json(naming.to_value(&AuthResponse {
+            token,
+            refresh_token: None,
+            is_admin: user.role.is_admin(),
+            user,
+        })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn check_availability(
+    req: HttpRequest,
+    auth_service: web::Data<Arc<AuthService>>,
+    rate_limiter: web::Data<Arc<RateLimiter>>,
+    query: web::Query<AvailabilityQuery>,
+) -> impl Responder {
+    let client_ip = req
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    if let Err(err) = rate_limiter.check(&client_ip) {
+        return error_to_response(err);
+    }
+
+    match auth_service
+        .check_availability(query.username.as_deref(), query.email.as_deref())
+        .await
+    {
+        Ok(available) => HttpResponse::Ok().json(serde_json::json!({ "available": available })),
         Err(err) => error_to_response(err),
     }
 }
 
+pub async fn change_password(
+    req: HttpRequest,
+    auth_service: web::Data<Arc<AuthService>>,
+    body: web::Json<ChangePasswordRequest>,
+) -> impl Responder {
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    match auth_service
+        .change_password(user_id, &body.old_password, &body.new_password)
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn current_user(
+    req: HttpRequest,
+    auth_service: web::Data<Arc<AuthService>>,
+    naming: web::Data<JsonNaming>,
+) -> impl Responder {
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    match auth_service.current_user(user_id).await {
+        Ok(user) => HttpResponse::Ok().json(naming.to_value(&user)),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn get_user(
+    req: HttpRequest,
+    auth_service: web::Data<Arc<AuthService>>,
+    jwt_service: web::Data<Arc<JwtService>>,
+    naming: web::Data<JsonNaming>,
+    cache: web::Data<CachePolicy>,
+    path: web::Path<i64>,
+) -> impl Responder {
+    let user_id = path.into_inner();
+    let viewer_id = optional_viewer_id(&req, &jwt_service);
+
+    match auth_service.get_user(user_id, viewer_id).await {
+        // Свой профиль содержит email - его нельзя класть в общие кэши
+        Ok(user) if viewer_id == Some(user_id) => HttpResponse::Ok()
+            .insert_header(CachePolicy::no_store())
+            .json(naming.to_value(&user)),
+        Ok(user) => HttpResponse::Ok()
+            .insert_header(cache.public())
+            .insert_header((VARY, AUTHORIZATION.as_str()))
+            .json(naming.to_value(&user)),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn update_profile(
+    req: HttpRequest,
+    auth_service: web::Data<Arc<AuthService>>,
+    naming: web::Data<JsonNaming>,
+    body: web::Json<UpdateUserRequest>,
+) -> impl Responder {
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    match auth_service
+        .update_profile(user_id, body.into_inner())
+        .await
+    {
+        Ok(user) => HttpResponse::Ok().json(naming.to_value(&user)),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn validate_token(
+    jwt_service: web::Data<Arc<JwtService>>,
+    naming: web::Data<JsonNaming>,
+    req: web::Json<ValidateTokenRequest>,
+) -> impl Responder {
+    let response = match bearer_token(&req.token).and_then(|t| jwt_service.verify_token(t)) {
+        Ok(user_id) => ValidateTokenResponse {
+            valid: true,
+            user_id: Some(user_id),
+        },
+        Err(_) => ValidateTokenResponse {
+            valid: false,
+            user_id: None,
+        },
+    };
+
+    HttpResponse::Ok().json(naming.to_value(&response))
+}
+
 // ============== Post Handlers ==============
 
 pub async fn list_posts(
+    req: HttpRequest,
     blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
+    cache: web::Data<CachePolicy>,
     query: web::Query<PaginationQuery>,
+    filter: web::Query<PostFilterQuery>,
+    include: web::Query<IncludeQuery>,
 ) -> impl Responder {
-    let limit = query.limit.unwrap_or(10);
-    let offset = query.offset.unwrap_or(0);
+    let Some(version) = ApiVersion::from_request(&req) else {
+        return versioning::not_acceptable();
+    };
+    let (limit, offset) = match pagination(query.limit, query.offset) {
+        Ok(page) => page,
+        Err(err) => return error_to_response(err),
+    };
+    let filter = match PostFilter::try_from(filter.into_inner()) {
+        Ok(filter) => filter,
+        Err(err) => return error_to_response(err),
+    };
+    let include_author = match include.author() {
+        Ok(author) => author,
+        Err(err) => return error_to_response(err),
+    };
+    let summary = match include.summary() {
+        Ok(summary) => summary,
+        Err(err) => return error_to_response(err),
+    };
 
-    tracing::info!("Listing posts with limit={}, offset={}", limit, offset);
+    tracing::info!(
+        "Listing posts with limit={}, offset={}, filter={:?}",
+        limit,
+        offset,
+        filter
+    );
 
-    match blog_service.list_posts(limit, offset).await {
-        Ok((posts, total)) => HttpResponse::Ok().json(PostsResponse {
-            posts,
-            total,
-            limit,
-            offset,
-        }),
+    let result = match blog_service.list_posts(limit, offset, filter).await {
+        Ok(mut page) if include_author => blog_service
+            .include_authors(&mut page.posts)
+            .await
+            .map(|()| page),
+        other => other,
+    }
+    .map(|page| if summary { page.into_summary() } else { page });
+
+    match result {
+        Ok(page) => HttpResponse::Ok()
+            .insert_header(cache.public())
+            .insert_header((VARY, ACCEPT_VERSION_HEADER))
+            .json(naming.to_value(&PostsResponse {
+                posts: page.posts.into_iter().map(|p| version.post(p)).collect(),
+                total: page.total,
+                limit,
+                offset,
+                next_cursor: page.next_cursor,
+            })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+// Только число постов под фильтрами списка, без самих постов (например, для дашборда)
+pub async fn count_posts(
+    blog_service: web::Data<Arc<BlogService>>,
+    cache: web::Data<CachePolicy>,
+    filter: web::Query<PostFilterQuery>,
+) -> impl Responder {
+    let filter = match PostFilter::try_from(filter.into_inner()) {
+        Ok(filter) => filter,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!("Counting posts with filter={:?}", filter);
+
+    match blog_service.count_posts(filter).await {
+        Ok(count) => HttpResponse::Ok()
+            .insert_header(cache.public())
+            .json(serde_json::json!({ "count": count })),
         Err(err) => error_to_response(err),
     }
 }
 
 pub async fn get_post(
+    req: HttpRequest,
     blog_service: web::Data<Arc<BlogService>>,
+    jwt_service: web::Data<Arc<JwtService>>,
+    naming: web::Data<JsonNaming>,
+    cache: web::Data<CachePolicy>,
     path: web::Path<i64>,
+    include: web::Query<IncludeQuery>,
 ) -> impl Responder {
+    let Some(version) = ApiVersion::from_request(&req) else {
+        return versioning::not_acceptable();
+    };
     let post_id = path.into_inner();
+    let include_author = match include.author() {
+        Ok(author) => author,
+        Err(err) => return error_to_response(err),
+    };
 
     tracing::info!("Getting post with id={}", post_id);
 
-    match blog_service.get_post(post_id).await {
-        // post_id уже i64
-        Ok(post) => HttpResponse::Ok().json(post),
+    // Автор видит свой черновик, как и через gRPC
+    let viewer_id = optional_viewer_id(&req, &jwt_service);
+    let result = match blog_service.get_post(post_id, viewer_id).await {
+        Ok(mut post) if include_author => blog_service
+            .include_authors(std::slice::from_mut(&mut post))
+            .await
+            .map(|()| post),
+        other => other,
+    };
+
+    match result {
+        Ok(post) => {
+            // Черновик и отложенный пост видны только автору - в общие кэши их не кладем
+            let cache_control = if post.is_public() {
+                cache.public()
+            } else {
+                CachePolicy::private()
+            };
+            let body = naming.to_value(&version.post(post));
+            let etag = caching::etag(&body);
+            // Пост не изменился с прошлого запроса клиента - тело не отправляем
+            let not_modified = caching::is_not_modified(&req, &etag);
+            let mut response = if not_modified {
+                HttpResponse::NotModified()
+            } else {
+                HttpResponse::Ok()
+            };
+            response
+                .insert_header(ETag(etag))
+                .insert_header(cache_control)
+                .insert_header((VARY, ACCEPT_VERSION_HEADER));
+
+            if not_modified {
+                response.finish()
+            } else {
+                response.json(body)
+            }
+        }
+        Err(err) => error_to_response(err),
+    }
+}
+
+/// Server-sent events with newly created public posts (`event: post`)
+pub async fn stream_posts(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
+) -> impl Responder {
+    let Some(version) = ApiVersion::from_request(&req) else {
+        return versioning::not_acceptable();
+    };
+    let naming = **naming;
+
+    tracing::info!("New SSE post subscriber");
+
+    // Комментарий сразу отправляет заголовки, чтобы клиент увидел открытое соединение
+    let events = stream::once(async { ": connected\n\n".to_string() })
+        .chain(blog_service.subscribe_posts().map(move |post| {
+            format!(
+                "event: post\ndata: {}\n\n",
+                naming.to_value(&version.post(post))
+            )
+        }))
+        .map(|event| Ok::<_, actix_web::Error>(web::Bytes::from(event)));
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        .streaming(events)
+}
+
+pub async fn get_posts_batch(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    jwt_service: web::Data<Arc<JwtService>>,
+    naming: web::Data<JsonNaming>,
+    body: web::Json<BatchPostsRequest>,
+) -> impl Responder {
+    let Some(version) = ApiVersion::from_request(&req) else {
+        return versioning::not_acceptable();
+    };
+    let BatchPostsRequest { ids, best_effort } = body.into_inner();
+    // Как и GET /api/posts/{id}: свои черновики видны автору по его токену
+    let viewer_id = optional_viewer_id(&req, &jwt_service);
+
+    tracing::info!("Getting {} posts by id", ids.len());
+
+    let result = if best_effort {
+        blog_service
+            .get_posts_best_effort(ids, viewer_id)
+            .await
+            .map(|(posts, results)| (posts, Some(results)))
+    } else {
+        blog_service
+            .get_posts(ids, viewer_id)
+            .await
+            .map(|posts| (posts, None))
+    };
+
+    match result {
+        Ok((posts, results)) => HttpResponse::Ok().json(naming.to_value(&BatchPostsResponse {
+            posts: posts.into_iter().map(|p| version.post(p)).collect(),
+            results,
+        })),
         Err(err) => error_to_response(err),
     }
 }
@@ -116,8 +735,13 @@ pub async fn get_post(
 pub async fn create_post(
     req: HttpRequest,
     blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
     post_data: web::Json<CreatePostRequest>,
 ) -> impl Responder {
+    let Some(version) = ApiVersion::from_request(&req) else {
+        return versioning::not_acceptable();
+    };
+
     // Extract user_id from JWT middleware
     let user_id = match get_user_id_from_request(&req) {
         Ok(id) => id,
@@ -126,11 +750,106 @@ pub async fn create_post(
 
     tracing::info!("Creating post for user_id={}", user_id);
 
+    let role = get_role_from_request(&req);
+
+    let idempotency_key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => match value.to_str() {
+            Ok(key) => Some(key),
+            Err(_) => {
+                return error_to_response(DomainError::ValidationError(
+                    "Idempotency key may contain only visible ASCII characters".to_string(),
+                ))
+            }
+        },
+        None => None,
+    };
+
     match blog_service
-        .create_post(user_id, post_data.into_inner())
+        .create_post(user_id, role, post_data.into_inner(), idempotency_key)
         .await
     {
-        Ok(post) => HttpResponse::Created().json(post),
+        Ok(post) => HttpResponse::Created().json(naming.to_value(&version.post(post))),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn create_posts_batch(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
+    body: web::Json<BatchCreatePostsRequest>,
+) -> impl Responder {
+    let Some(version) = ApiVersion::from_request(&req) else {
+        return versioning::not_acceptable();
+    };
+
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+    let role = get_role_from_request(&req);
+    let BatchCreatePostsRequest { posts, best_effort } = body.into_inner();
+
+    tracing::info!("Creating {} posts for user_id={}", posts.len(), user_id);
+
+    // Частичный результат - не "201 Created": часть постов могла не создаться
+    if best_effort {
+        return match blog_service
+            .create_posts_best_effort(user_id, role, posts)
+            .await
+        {
+            Ok((posts, results)) => HttpResponse::Ok().json(naming.to_value(&BatchPostsResponse {
+                posts: posts.into_iter().map(|p| version.post(p)).collect(),
+                results: Some(results),
+            })),
+            Err(err) => error_to_response(err),
+        };
+    }
+
+    match blog_service.create_posts(user_id, role, posts).await {
+        Ok(posts) => HttpResponse::Created().json(naming.to_value(&BatchPostsResponse {
+            posts: posts.into_iter().map(|p| version.post(p)).collect(),
+            results: None,
+        })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn delete_posts_batch(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
+    body: web::Json<BatchPostsRequest>,
+) -> impl Responder {
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+    let role = get_role_from_request(&req);
+    let BatchPostsRequest { ids, best_effort } = body.into_inner();
+
+    tracing::info!("Deleting {} posts for user_id={}", ids.len(), user_id);
+
+    let result = if best_effort {
+        blog_service
+            .delete_posts_best_effort(ids, user_id, role)
+            .await
+            .map(|results| BatchDeleteResponse {
+                deleted: results.iter().filter(|r| r.ok).count() as u64,
+                results: Some(results),
+            })
+    } else {
+        blog_service
+            .delete_posts(ids, user_id, role)
+            .await
+            .map(|deleted| BatchDeleteResponse {
+                deleted,
+                results: None,
+            })
+    };
+
+    match result {
+        Ok(response) => HttpResponse::Ok().json(naming.to_value(&response)),
         Err(err) => error_to_response(err),
     }
 }
@@ -138,9 +857,13 @@ pub async fn create_post(
 pub async fn update_post(
     req: HttpRequest,
     blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
     path: web::Path<i64>,
     post_data: web::Json<UpdatePostRequest>,
 ) -> impl Responder {
+    let Some(version) = ApiVersion::from_request(&req) else {
+        return versioning::not_acceptable();
+    };
     let post_id = path.into_inner();
 
     // Extract user_id from JWT middleware
@@ -151,11 +874,19 @@ pub async fn update_post(
 
     tracing::info!("Updating post id={} for user_id={}", post_id, user_id);
 
+    let role = get_role_from_request(&req);
+
+    // If-Unmodified-Since - стандартный для HTTP способ передать expected_updated_at
+    let mut changes = post_data.into_inner();
+    if let Some(since) = caching::unmodified_since(&req) {
+        changes.expected_updated_at = Some(since);
+    }
+
     match blog_service
-        .update_post(post_id, user_id, post_data.into_inner())
+        .update_post(post_id, user_id, role, changes)
         .await
     {
-        Ok(post) => HttpResponse::Ok().json(post),
+        Ok(post) => HttpResponse::Ok().json(naming.to_value(&version.post(post))),
         Err(err) => error_to_response(err),
     }
 }
@@ -175,8 +906,436 @@ pub async fn delete_post(
 
     tracing::info!("Deleting post id={} for user_id={}", post_id, user_id);
 
-    match blog_service.delete_post(post_id, user_id).await {
+    let role = get_role_from_request(&req);
+
+    match blog_service.delete_post(post_id, user_id, role).await {
         Ok(()) => HttpResponse::NoContent().finish(),
         Err(err) => error_to_response(err),
     }
 }
+
+pub async fn delete_my_posts(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+) -> impl Responder {
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!("Deleting all posts for user_id={}", user_id);
+
+    match blog_service.delete_posts_by_author(user_id).await {
+        Ok(deleted) => HttpResponse::Ok().json(serde_json::json!({ "deleted": deleted })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+// ============== Admin Handlers ==============
+
+// Модерация: удаление всех постов пользователя (маршрут защищен admin_middleware)
+pub async fn delete_user_posts(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    path: web::Path<i64>,
+) -> impl Responder {
+    let author_id = path.into_inner();
+
+    // Extract user_id from JWT middleware
+    let admin_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!(
+        "Admin {} deleting all posts of user_id={}",
+        admin_id,
+        author_id
+    );
+
+    match blog_service.delete_posts_by_author(author_id).await {
+        Ok(deleted) => HttpResponse::Ok().json(serde_json::json!({ "deleted": deleted })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+// Модерация: переименование тега во всех постах (маршрут защищен admin_middleware)
+pub async fn rename_tag(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    body: web::Json<RenameTagRequest>,
+) -> impl Responder {
+    let admin_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!(
+        "Admin {} renaming tag '{}' to '{}'",
+        admin_id,
+        body.old_name,
+        body.new_name
+    );
+
+    match blog_service
+        .rename_tag(&body.old_name, &body.new_name)
+        .await
+    {
+        Ok(renamed) => HttpResponse::Ok().json(serde_json::json!({ "renamed": renamed })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn dashboard(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
+    query: web::Query<PaginationQuery>,
+) -> impl Responder {
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    let (limit, offset) = match pagination(query.limit, query.offset) {
+        Ok(page) => page,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!(
+        "Dashboard for user_id={} with limit={}, offset={}",
+        user_id,
+        limit,
+        offset
+    );
+
+    match blog_service.dashboard(user_id, limit, offset).await {
+        Ok((posts, total)) => HttpResponse::Ok().json(naming.to_value(&DashboardResponse {
+            posts,
+            total,
+            limit,
+            offset,
+        })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn like_post(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
+    path: web::Path<i64>,
+) -> impl Responder {
+    let post_id = path.into_inner();
+
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!("Liking post id={} for user_id={}", post_id, user_id);
+
+    match blog_service.like_post(post_id, user_id).await {
+        Ok(likes_count) => HttpResponse::Ok().json(naming.to_value(&LikeResponse {
+            post_id,
+            likes_count,
+        })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+pub async fn unlike_post(
+    req: HttpRequest,
+    blog_service: web::Data<Arc<BlogService>>,
+    naming: web::Data<JsonNaming>,
+    path: web::Path<i64>,
+) -> impl Responder {
+    let post_id = path.into_inner();
+
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!("Unliking post id={} for user_id={}", post_id, user_id);
+
+    match blog_service.unlike_post(post_id, user_id).await {
+        Ok(likes_count) => HttpResponse::Ok().json(naming.to_value(&LikeResponse {
+            post_id,
+            likes_count,
+        })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+// ============== Comment Handlers ==============
+
+pub async fn add_comment(
+    req: HttpRequest,
+    comment_service: web::Data<Arc<CommentService>>,
+    naming: web::Data<JsonNaming>,
+    path: web::Path<i64>,
+    body: web::Json<CreateCommentRequest>,
+) -> impl Responder {
+    let post_id = path.into_inner();
+
+    // Extract user_id from JWT middleware
+    let user_id = match get_user_id_from_request(&req) {
+        Ok(id) => id,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!(
+        "Adding comment to post id={} for user_id={}",
+        post_id,
+        user_id
+    );
+
+    match comment_service
+        .add_comment(post_id, user_id, body.into_inner())
+        .await
+    {
+        Ok(comment) => HttpResponse::Created().json(naming.to_value(&comment)),
+        Err(err) => error_to_response(err),
+    }
+}
+
+// Публичный список; автор черновика видит его комментарии со своим токеном
+pub async fn list_comments(
+    req: HttpRequest,
+    comment_service: web::Data<Arc<CommentService>>,
+    jwt_service: web::Data<Arc<JwtService>>,
+    naming: web::Data<JsonNaming>,
+    path: web::Path<i64>,
+    query: web::Query<PaginationQuery>,
+) -> impl Responder {
+    let post_id = path.into_inner();
+    let viewer_id = optional_viewer_id(&req, &jwt_service);
+
+    let (limit, offset) = match pagination(query.limit, query.offset) {
+        Ok(page) => page,
+        Err(err) => return error_to_response(err),
+    };
+
+    tracing::info!(
+        "Listing comments of post id={} with limit={}, offset={}",
+        post_id,
+        limit,
+        offset
+    );
+
+    match comment_service
+        .list_comments(post_id, viewer_id, limit, offset)
+        .await
+    {
+        Ok((comments, total)) => HttpResponse::Ok().json(naming.to_value(&CommentsResponse {
+            comments,
+            total,
+            limit,
+            offset,
+        })),
+        Err(err) => error_to_response(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::memory::InMemoryPostRepository;
+    use actix_web::http::header::CACHE_CONTROL;
+    use actix_web::http::StatusCode;
+    use actix_web::{test, App};
+
+    const JWT_SECRET: &str = "test-secret-key-for-handler-tests-123456";
+    const AUTHOR_ID: i64 = 1;
+
+    fn services() -> (Arc<BlogService>, Arc<JwtService>) {
+        let blog_service = Arc::new(BlogService::new(
+            Arc::new(InMemoryPostRepository::new()),
+            None,
+        ));
+        let jwt_service =
+            Arc::new(JwtService::new(JWT_SECRET, chrono::Duration::hours(1)).unwrap());
+        (blog_service, jwt_service)
+    }
+
+    fn new_post(published: bool) -> CreatePostRequest {
+        CreatePostRequest {
+            title: "Title".to_string(),
+            content: "Content".to_string(),
+            tags: Vec::new(),
+            content_format: Default::default(),
+            published,
+            published_at: None,
+        }
+    }
+
+    fn bearer(
+        jwt_service: &JwtService,
+        user_id: i64,
+    ) -> (actix_web::http::header::HeaderName, String) {
+        let token = jwt_service
+            .generate_token(user_id, format!("user{}", user_id), Role::User)
+            .unwrap();
+        (AUTHORIZATION, format!("Bearer {}", token))
+    }
+
+    #[actix_web::test]
+    async fn get_post_shows_draft_to_its_author_with_private_cache() {
+        let (blog_service, jwt_service) = services();
+        let draft = blog_service
+            .create_post(AUTHOR_ID, Role::User, new_post(false), None)
+            .await
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(blog_service.clone()))
+                .app_data(web::Data::new(jwt_service.clone()))
+                .app_data(web::Data::new(JsonNaming::default()))
+                .app_data(web::Data::new(CachePolicy::default()))
+                .route("/api/posts/{id}", web::get().to(get_post)),
+        )
+        .await;
+        let uri = format!("/api/posts/{}", draft.id);
+
+        let anonymous = test::TestRequest::get().uri(&uri).to_request();
+        let response = test::call_service(&app, anonymous).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let stranger = test::TestRequest::get()
+            .uri(&uri)
+            .insert_header(bearer(&jwt_service, AUTHOR_ID + 1))
+            .to_request();
+        let response = test::call_service(&app, stranger).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let author = test::TestRequest::get()
+            .uri(&uri)
+            .insert_header(bearer(&jwt_service, AUTHOR_ID))
+            .to_request();
+        let response = test::call_service(&app, author).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(CACHE_CONTROL).unwrap(), "private");
+    }
+
+    #[actix_web::test]
+    async fn get_post_keeps_public_cache_for_published_post() {
+        let (blog_service, jwt_service) = services();
+        let post = blog_service
+            .create_post(AUTHOR_ID, Role::User, new_post(true), None)
+            .await
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(blog_service.clone()))
+                .app_data(web::Data::new(jwt_service.clone()))
+                .app_data(web::Data::new(JsonNaming::default()))
+                .app_data(web::Data::new(CachePolicy::new(60)))
+                .route("/api/posts/{id}", web::get().to(get_post)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri(&format!("/api/posts/{}", post.id))
+            .insert_header(bearer(&jwt_service, AUTHOR_ID))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CACHE_CONTROL).unwrap(),
+            "public, max-age=60"
+        );
+    }
+
+    #[actix_web::test]
+    async fn closed_database_pool_maps_to_service_unavailable() {
+        use crate::data::post_repository::PostgresPostRepository;
+        use sqlx::postgres::PgPoolOptions;
+
+        // Закрытый пул отказывает сразу (PoolClosed), настоящая база не нужна
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        pool.close().await;
+        let blog_service = Arc::new(BlogService::new(
+            Arc::new(PostgresPostRepository::new(pool)),
+            None,
+        ));
+        let err = blog_service.get_post(1, None).await.unwrap_err();
+        assert!(matches!(err, DomainError::DatabaseUnavailable), "{:?}", err);
+
+        let (_, jwt_service) = services();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(jwt_service))
+                .app_data(web::Data::new(JsonNaming::default()))
+                .app_data(web::Data::new(CachePolicy::default()))
+                .route("/api/posts/{id}", web::get().to(get_post)),
+        )
+        .await;
+        let request = test::TestRequest::get().uri("/api/posts/1").to_request();
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers().get("Retry-After").unwrap(),
+            &DATABASE_RETRY_AFTER_SECONDS.to_string()
+        );
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "SERVICE_UNAVAILABLE");
+    }
+
+    #[actix_web::test]
+    async fn get_posts_batch_returns_drafts_to_their_author_only() {
+        let (blog_service, jwt_service) = services();
+        let public = blog_service
+            .create_post(AUTHOR_ID, Role::User, new_post(true), None)
+            .await
+            .unwrap();
+        let draft = blog_service
+            .create_post(AUTHOR_ID, Role::User, new_post(false), None)
+            .await
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(blog_service.clone()))
+                .app_data(web::Data::new(jwt_service.clone()))
+                .app_data(web::Data::new(JsonNaming::default()))
+                .route("/api/posts/batch", web::post().to(get_posts_batch)),
+        )
+        .await;
+        let ids = |body: serde_json::Value| -> Vec<i64> {
+            body["posts"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|post| post["id"].as_i64().unwrap())
+                .collect()
+        };
+
+        for best_effort in [false, true] {
+            let body =
+                serde_json::json!({ "ids": [public.id, draft.id], "best_effort": best_effort });
+
+            let anonymous = test::TestRequest::post()
+                .uri("/api/posts/batch")
+                .set_json(&body)
+                .to_request();
+            let response: serde_json::Value = test::call_and_read_body_json(&app, anonymous).await;
+            assert_eq!(ids(response), vec![public.id]);
+
+            let author = test::TestRequest::post()
+                .uri("/api/posts/batch")
+                .insert_header(bearer(&jwt_service, AUTHOR_ID))
+                .set_json(&body)
+                .to_request();
+            let response: serde_json::Value = test::call_and_read_body_json(&app, author).await;
+            assert_eq!(ids(response), vec![public.id, draft.id]);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Naming convention for keys in HTTP JSON responses
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum JsonNaming {
+    /// Field names as declared in the DTOs (author_id, created_at)
+    #[default]
+    SnakeCase,
+    /// JavaScript-friendly keys (authorId, createdAt)
+    CamelCase,
+}
+
+impl JsonNaming {
+    pub fn from_flag(camel_case: bool) -> Self {
+        if camel_case {
+            Self::CamelCase
+        } else {
+            Self::SnakeCase
+        }
+    }
+
+    /// Serialize a response DTO applying the configured key naming
+    pub fn to_value<T: Serialize>(self, value: &T) -> Value {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+
+        match self {
+            Self::SnakeCase => value,
+            Self::CamelCase => camelize_keys(value),
+        }
+    }
+}
+
+fn camelize_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (snake_to_camel(&key), camelize_keys(value)))
+                .collect::<Map<String, Value>>(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(camelize_keys).collect()),
+        other => other,
+    }
+}
+
+fn snake_to_camel(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    let mut upper_next = false;
+
+    for c in key.chars() {
+        if c == '_' {
+            upper_next = !result.is_empty();
+        } else if upper_next {
+            result.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,165 @@
+use crate::domain::DomainError;
+use crate::infrastructure::metrics::Metrics;
+use actix_web::body::{BodySize, BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::CONTENT_LENGTH;
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tonic::codegen::{http, Service};
+
+// Метка endpoint для запросов, не попавших ни в один маршрут (ограничивает число серий)
+const UNMATCHED_ENDPOINT: &str = "unmatched";
+
+tokio::task_local! {
+    static ERROR_VARIANT: Cell<Option<&'static str>>;
+}
+
+/// Remember the `DomainError` the current request fails with, for the error metrics;
+/// does nothing outside the metrics middleware
+pub fn record_error(err: &DomainError) {
+    let _ = ERROR_VARIANT.try_with(|variant| variant.set(Some(err.variant())));
+}
+
+/// Counts HTTP requests by route pattern and status, records latency and body sizes;
+/// a no-op unless `Metrics` is registered in the app data
+pub async fn metrics_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let Some(metrics) = req
+        .app_data::<web::Data<Arc<Metrics>>>()
+        .map(|metrics| metrics.get_ref().clone())
+    else {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body);
+    };
+
+    // Шаблон маршрута (/api/posts/{id}), а не путь, чтобы id не плодили серии
+    let endpoint = req
+        .match_pattern()
+        .unwrap_or_else(|| UNMATCHED_ENDPOINT.to_string());
+    let request_size = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+
+    let started = Instant::now();
+    let (result, variant) = ERROR_VARIANT
+        .scope(Cell::new(None), async move {
+            let result = next.call(req).await;
+            (result, ERROR_VARIANT.with(Cell::get))
+        })
+        .await;
+
+    let (status, response_size) = match &result {
+        Ok(response) => (
+            response.status(),
+            match response.response().body().size() {
+                BodySize::Sized(size) => Some(size),
+                _ => None,
+            },
+        ),
+        Err(err) => (err.as_response_error().status_code(), None),
+    };
+    metrics.observe_request("http", &endpoint, status.as_str(), started.elapsed());
+    metrics.observe_http_sizes(&endpoint, request_size, response_size);
+    if let Some(variant) = variant {
+        metrics.observe_error("http", variant);
+    }
+
+    result.map(ServiceResponse::map_into_boxed_body)
+}
+
+/// Tower layer recording the same request metrics for gRPC calls (by method and status code)
+#[derive(Clone, Default)]
+pub struct GrpcMetricsLayer {
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl GrpcMetricsLayer {
+    /// `None` turns the layer into a pass-through
+    pub fn new(metrics: Option<Arc<Metrics>>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl<S> tower::Layer<S> for GrpcMetricsLayer {
+    type Service = GrpcMetrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcMetrics {
+            inner,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GrpcMetrics<S> {
+    inner: S,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl<S, B, ResBody> Service<http::Request<B>> for GrpcMetrics<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let Some(metrics) = self.metrics.clone() else {
+            return Box::pin(self.inner.call(req));
+        };
+
+        let method = req.uri().path().to_string();
+        let started = Instant::now();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let (result, variant) = ERROR_VARIANT
+                .scope(Cell::new(None), async move {
+                    let result = future.await;
+                    (result, ERROR_VARIANT.with(Cell::get))
+                })
+                .await;
+
+            // Ошибка приходит в заголовках (trailers-only), успешный ответ их не содержит
+            let code = match &result {
+                Ok(response) => response
+                    .headers()
+                    .get("grpc-status")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok())
+                    .map(tonic::Code::from_i32)
+                    .unwrap_or(tonic::Code::Ok),
+                Err(_) => tonic::Code::Unknown,
+            };
+            let endpoint = match code {
+                tonic::Code::Unimplemented => UNMATCHED_ENDPOINT,
+                _ => method.as_str(),
+            };
+            metrics.observe_request("grpc", endpoint, &format!("{:?}", code), started.elapsed());
+            if let Some(variant) = variant {
+                metrics.observe_error("grpc", variant);
+            }
+
+            result
+        })
+    }
+}
@@ -1,12 +1,61 @@
-use crate::infrastructure::jwt::JwtService;
-use actix_web::{dev::ServiceRequest, web, Error, HttpMessage};
-use actix_web_httpauth::extractors::bearer::{BearerAuth, Config};
+use crate::domain::user::Role;
+use crate::domain::DomainError;
+use crate::infrastructure::jwt::{bearer_token, JwtService};
+use crate::presentation::metrics;
+use crate::presentation::request_id::error_json;
+use actix_web::dev::Payload;
+use actix_web::error::InternalError;
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::{
+    dev::ServiceRequest, web, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse,
+};
+use actix_web_httpauth::extractors::bearer::{self, Config};
 use actix_web_httpauth::extractors::AuthenticationError;
+use actix_web_httpauth::headers::www_authenticate::bearer::Bearer;
+use std::future::{ready, Ready};
 use std::sync::Arc;
 
+/// Token of the Authorization header, parsed like the gRPC metadata
+/// (see `bearer_token`): the "Bearer " prefix is optional and an over-long
+/// token is rejected with 401 before it reaches the JWT decoder
+#[derive(Debug, Clone)]
+pub struct BearerToken(String);
+
+impl BearerToken {
+    pub fn token(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromRequest for BearerToken {
+    type Error = AuthenticationError<Bearer>;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let config = req.app_data::<Config>().cloned().unwrap_or_default();
+        let header = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+
+        ready(match header.map(bearer_token) {
+            Some(Ok(token)) => Ok(BearerToken(token.to_string())),
+            Some(Err(err @ DomainError::TokenInvalid(_))) => {
+                tracing::warn!("Rejected bearer token: {}", err);
+                metrics::record_error(&err);
+                Err(AuthenticationError::from(config)
+                    .with_error(bearer::Error::InvalidToken)
+                    .with_error_description(err.to_string()))
+            }
+            // Нет заголовка или токена - 401 без описания ошибки, как раньше
+            _ => Err(AuthenticationError::from(config)),
+        })
+    }
+}
+
 pub async fn jwt_middleware(
     req: ServiceRequest,
-    credentials: BearerAuth,
+    credentials: BearerToken,
 ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
     let jwt_service = match req.app_data::<web::Data<Arc<JwtService>>>() {
         Some(service) => service.get_ref().clone(),
@@ -19,14 +68,48 @@ pub async fn jwt_middleware(
     };
 
     // Verify token
-    match jwt_service.verify_token(credentials.token()) {
-        Ok(user_id) => {
+    match jwt_service.verify_token_with_role(credentials.token()) {
+        Ok((user_id, role)) => {
             req.extensions_mut().insert(user_id);
+            req.extensions_mut().insert(role);
             Ok(req)
         }
-        Err(_) => {
+        Err(err) => {
+            metrics::record_error(&err);
+            // Подсказка клиенту в WWW-Authenticate: истекший токен можно обновить через refresh
+            let description = match err {
+                DomainError::TokenExpired => "Token expired, refresh it or login again",
+                _ => "Invalid token, please login again",
+            };
             let config = req.app_data::<Config>().cloned().unwrap_or_default();
-            Err((AuthenticationError::from(config).into(), req))
+            let error = AuthenticationError::from(config)
+                .with_error(bearer::Error::InvalidToken)
+                .with_error_description(description);
+            Err((error.into(), req))
         }
     }
 }
+
+/// `jwt_middleware` for admin-only routes: a valid token of a non-admin gets 403
+pub async fn admin_middleware(
+    req: ServiceRequest,
+    credentials: BearerToken,
+) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    let req = jwt_middleware(req, credentials).await?;
+
+    if req
+        .extensions()
+        .get::<Role>()
+        .is_some_and(|role| role.is_admin())
+    {
+        return Ok(req);
+    }
+
+    let user_id = req.extensions().get::<i64>().copied();
+    tracing::warn!("User {:?} attempted to access an admin route", user_id);
+
+    let err = DomainError::Forbidden;
+    metrics::record_error(&err);
+    let response = HttpResponse::Forbidden().json(error_json(err.to_string(), err.code()));
+    Err((InternalError::from_response(err, response).into(), req))
+}
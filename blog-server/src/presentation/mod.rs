@@ -1,3 +1,8 @@
+pub mod caching;
 pub mod grpc_service;
 pub mod http_handlers;
+pub mod json;
+pub mod metrics;
 pub mod middleware;
+pub mod request_id;
+pub mod versioning;
@@ -0,0 +1,157 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::error::InternalError;
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tonic::codegen::{http, Service};
+use tracing::Instrument;
+
+/// Header (HTTP) and metadata key (gRPC) carrying the request id
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// Id of the request being handled; `None` outside `request_id_middleware`
+pub fn current() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// JSON body of an API error, with the request id the client can report
+pub fn error_json(error: String, code: &str) -> serde_json::Value {
+    let mut body = serde_json::json!({ "error": error, "code": code });
+    if let Some(request_id) = current() {
+        body["request_id"] = request_id.into();
+    }
+    body
+}
+
+// Id клиента принимается, если он непустой, не длиннее 128 символов и из видимых ASCII;
+// иначе генерируется новый
+fn resolve(header: Option<&str>) -> String {
+    header
+        .map(str::trim)
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LENGTH
+                && id.chars().all(|c| c.is_ascii_graphic())
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Reads or generates `X-Request-Id`, runs the request inside a tracing span
+/// with that id and echoes it back in the response header
+pub async fn request_id_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let request_id = resolve(
+        req.headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok()),
+    );
+
+    let span = tracing::info_span!(
+        "http_request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.path(),
+    );
+    let result = CURRENT_REQUEST_ID
+        .scope(request_id.clone(), next.call(req))
+        .instrument(span)
+        .await;
+    let header = HeaderValue::from_str(&request_id)
+        .ok()
+        .map(|value| (HeaderName::from_static(REQUEST_ID_HEADER), value));
+
+    match result {
+        Ok(mut response) => {
+            if let Some((name, value)) = header {
+                response.headers_mut().insert(name, value);
+            }
+            Ok(response.map_into_boxed_body())
+        }
+        // Ошибки внутренних middleware (например, 401 без токена) тоже получают заголовок
+        Err(err) => {
+            let mut response = err.error_response();
+            if let Some((name, value)) = header {
+                response.headers_mut().insert(name, value);
+            }
+            Err(InternalError::from_response(err, response).into())
+        }
+    }
+}
+
+/// Tower layer giving gRPC calls the same `x-request-id` handling as the HTTP middleware
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrpcRequestIdLayer;
+
+impl<S> tower::Layer<S> for GrpcRequestIdLayer {
+    type Service = GrpcRequestId<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcRequestId { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GrpcRequestId<S> {
+    inner: S,
+}
+
+impl<S, B, ResBody> Service<http::Request<B>> for GrpcRequestId<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let request_id = resolve(
+            req.headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|v| v.to_str().ok()),
+        );
+        // Сгенерированный id виден обработчикам в метаданных запроса
+        let value = http::HeaderValue::from_str(&request_id).ok();
+        if let Some(value) = &value {
+            req.headers_mut().insert(REQUEST_ID_HEADER, value.clone());
+        }
+
+        let span = tracing::info_span!(
+            "grpc_request",
+            request_id = %request_id,
+            method = %req.uri().path(),
+        );
+        let future = {
+            let _entered = span.enter();
+            self.inner.call(req)
+        };
+
+        Box::pin(
+            async move {
+                let mut response = future.await?;
+                // Для ошибочных статусов заголовки попадают в метаданные Status на клиенте
+                if let Some(value) = value {
+                    response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                }
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
@@ -0,0 +1,85 @@
+use crate::domain::post::PostResponse;
+use actix_web::{HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Header used by clients to select the response shape
+pub const ACCEPT_VERSION_HEADER: &str = "Accept-Version";
+
+/// Version of the HTTP response shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    /// Original shape: posts without tags and permalink
+    V1,
+    /// Current shape
+    V2,
+}
+
+impl ApiVersion {
+    pub const LATEST: Self = Self::V2;
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "v1" | "1" => Some(Self::V1),
+            "v2" | "2" => Some(Self::V2),
+            _ => None,
+        }
+    }
+
+    /// Version requested via Accept-Version (latest when the header is absent).
+    /// `None` means the client asked for a version we don't support.
+    pub fn from_request(req: &HttpRequest) -> Option<Self> {
+        match req.headers().get(ACCEPT_VERSION_HEADER) {
+            Some(header) => header.to_str().ok().and_then(Self::parse),
+            None => Some(Self::LATEST),
+        }
+    }
+
+    pub fn post(self, post: PostResponse) -> VersionedPost {
+        match self {
+            Self::V1 => VersionedPost::V1(PostResponseV1::from(post)),
+            Self::V2 => VersionedPost::V2(post),
+        }
+    }
+}
+
+// Пост в формате v1 (без полей, добавленных в v2)
+#[derive(Debug, Serialize)]
+pub struct PostResponseV1 {
+    pub id: i64,
+    pub title: String,
+    pub content: String,
+    pub author_id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PostResponse> for PostResponseV1 {
+    fn from(post: PostResponse) -> Self {
+        Self {
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            author_id: post.author_id,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        }
+    }
+}
+
+/// 406 response for an unknown Accept-Version
+pub fn not_acceptable() -> HttpResponse {
+    HttpResponse::NotAcceptable().json(serde_json::json!({
+        "error": "Unsupported API version",
+        "code": "UNSUPPORTED_VERSION",
+        "supported": ["v1", "v2"],
+    }))
+}
+
+/// Post DTO in the shape of the negotiated version
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum VersionedPost {
+    V1(PostResponseV1),
+    V2(PostResponse),
+}
@@ -1,11 +1,26 @@
+use crate::error::ApiError;
 use crate::models::*;
 use gloo_net::http::Request;
 use gloo_storage::{LocalStorage, Storage};
 use serde::{de::DeserializeOwned, Serialize};
 
+/// Used when the page doesn't set `<meta name="blog-api-base">`
 const API_BASE: &str = "http://localhost:3000";
+const API_BASE_META: &str = "meta[name=\"blog-api-base\"]";
 const TOKEN_KEY: &str = "blog_token";
 
+// Адрес API из <meta name="blog-api-base" content="...">, чтобы один бандл
+// можно было развернуть на разных окружениях без пересборки
+fn resolve_api_base() -> String {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.query_selector(API_BASE_META).ok().flatten())
+        .and_then(|meta| meta.get_attribute("content"))
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| API_BASE.to_string())
+}
+
 #[derive(Debug, Clone)]
 pub struct ApiClient {
     base_url: String,
@@ -13,9 +28,10 @@ pub struct ApiClient {
 
 impl ApiClient {
     pub fn new() -> Self {
-        Self {
-            base_url: API_BASE.to_string(),
-        }
+        let base_url = resolve_api_base();
+        web_sys::console::log_1(&format!("API base URL: {}", base_url).into());
+
+        Self { base_url }
     }
 
     pub fn save_token(token: &str) {
@@ -45,7 +61,7 @@ impl ApiClient {
         path: &str,
         body: Option<&impl Serialize>,
         requires_auth: bool,
-    ) -> Result<T, String> {
+    ) -> Result<T, ApiError> {
         let url = format!("{}{}", self.base_url, path);
 
         // Создаем базовый запрос в зависимости от метода
@@ -54,10 +70,11 @@ impl ApiClient {
             "POST" => Request::post(&url),
             "PUT" => Request::put(&url),
             "DELETE" => Request::delete(&url),
-            _ => return Err(format!("Unsupported method: {}", method)),
+            _ => return Err(ApiError::Server(format!("Unsupported method: {}", method))),
         };
 
-        // Добавляем заголовки
+        // Добавляем заголовки. Accept-Encoding (gzip, br) браузер выставляет сам -
+        // fetch не дает задать его вручную, а сжатый ответ распаковывается прозрачно
         let request_builder = request_builder.header("Content-Type", "application/json");
 
         let request_builder = if requires_auth {
@@ -75,54 +92,64 @@ impl ApiClient {
         let response = if let Some(body) = body {
             // Для методов, которые могут иметь тело (POST, PUT)
             if method == "GET" || method == "DELETE" {
-                return Err(format!("Method {} cannot have body", method));
+                return Err(ApiError::Server(format!(
+                    "Method {} cannot have body",
+                    method
+                )));
             }
 
             let body_json = serde_json::to_string(body)
-                .map_err(|e| format!("Failed to serialize request: {}", e))?;
+                .map_err(|e| ApiError::Validation(format!("Failed to serialize request: {}", e)))?;
 
             request_builder
                 .body(body_json)
-                .map_err(|e| format!("Failed to set request body: {}", e))?
+                .map_err(|e| ApiError::Validation(format!("Failed to set request body: {}", e)))?
                 .send()
                 .await
-                .map_err(|e| format!("Network error: {}", e))?
+                .map_err(|e| ApiError::Network(e.to_string()))?
         } else {
             // Для методов без тела
             request_builder
                 .send()
                 .await
-                .map_err(|e| format!("Network error: {}", e))?
+                .map_err(|e| ApiError::Network(e.to_string()))?
         };
 
         let status = response.status();
         let text = response
             .text()
             .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
+            .map_err(|e| ApiError::Network(format!("Failed to read response: {}", e)))?;
 
         if (200..300).contains(&status) {
-            serde_json::from_str(&text).map_err(|e| format!("Failed to parse response: {}", e))
+            serde_json::from_str(&text)
+                .map_err(|e| ApiError::Server(format!("Failed to parse response: {}", e)))
         } else {
             // Пытаемся распарсить сообщение об ошибке
-            match serde_json::from_str::<ErrorResponse>(&text) {
-                Ok(err) => Err(err.error),
-                Err(_) => Err(format!("HTTP {}: {}", status, text)),
-            }
+            let message = match serde_json::from_str::<ErrorResponse>(&text) {
+                Ok(err) => err.error,
+                Err(_) => format!("HTTP {}: {}", status, text),
+            };
+            Err(ApiError::from_status(status, message))
         }
     }
 
-    pub async fn register(&self, req: &RegisterRequest) -> Result<AuthResponse, String> {
+    pub async fn register(&self, req: &RegisterRequest) -> Result<AuthResponse, ApiError> {
         self.request("POST", "/api/auth/register", Some(req), false)
             .await
     }
 
-    pub async fn login(&self, req: &LoginRequest) -> Result<AuthResponse, String> {
+    pub async fn login(&self, req: &LoginRequest) -> Result<AuthResponse, ApiError> {
         self.request("POST", "/api/auth/login", Some(req), false)
             .await
     }
 
-    pub async fn list_posts(&self, limit: i64, offset: i64) -> Result<PostsResponse, String> {
+    pub async fn current_user(&self) -> Result<User, ApiError> {
+        self.request("GET", "/api/protected/me", None::<&()>, true)
+            .await
+    }
+
+    pub async fn list_posts(&self, limit: i64, offset: i64) -> Result<PostsResponse, ApiError> {
         self.request(
             "GET",
             &format!("/api/posts?limit={}&offset={}", limit, offset),
@@ -132,18 +159,25 @@ impl ApiClient {
         .await
     }
 
-    #[allow(dead_code)]
-    pub async fn get_post(&self, id: i64) -> Result<Post, String> {
+    // Сервер отдает ETag: по истечении max-age HTTP-кэш браузера сам повторяет
+    // запрос с If-None-Match и при 304 берет тело из кэша
+    pub async fn get_post(&self, id: i64) -> Result<Post, ApiError> {
         self.request("GET", &format!("/api/posts/{}", id), None::<&()>, false)
             .await
     }
 
-    pub async fn create_post(&self, req: &CreatePostRequest) -> Result<Post, String> {
+    // Публичный профиль (без email), например автора поста
+    pub async fn get_user(&self, id: i64) -> Result<User, ApiError> {
+        self.request("GET", &format!("/api/users/{}", id), None::<&()>, false)
+            .await
+    }
+
+    pub async fn create_post(&self, req: &CreatePostRequest) -> Result<Post, ApiError> {
         self.request("POST", "/api/protected/posts", Some(req), true)
             .await
     }
 
-    pub async fn update_post(&self, id: i64, req: &UpdatePostRequest) -> Result<Post, String> {
+    pub async fn update_post(&self, id: i64, req: &UpdatePostRequest) -> Result<Post, ApiError> {
         self.request(
             "PUT",
             &format!("/api/protected/posts/{}", id),
@@ -153,7 +187,7 @@ impl ApiClient {
         .await
     }
 
-    pub async fn delete_post(&self, id: i64) -> Result<(), String> {
+    pub async fn delete_post(&self, id: i64) -> Result<(), ApiError> {
         self.request::<serde_json::Value>(
             "DELETE",
             &format!("/api/protected/posts/{}", id),
@@ -1,9 +1,25 @@
 use crate::api::ApiClient;
+use crate::error::ApiError;
+use crate::markdown;
 use crate::models::*;
+use std::collections::HashMap;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{HtmlInputElement, HtmlTextAreaElement};
 use yew::prelude::*;
 
+/// Number of posts shown per page
+const PAGE_SIZE: i64 = 10;
+
+// Markdown превращается в HTML, обычный текст выводится как есть
+fn view_content(post: &Post) -> Html {
+    match post.content_format {
+        ContentFormat::Markdown => {
+            Html::from_html_unchecked(AttrValue::from(markdown::to_html(&post.content)))
+        }
+        ContentFormat::Plain => html! { <p>{ &post.content }</p> },
+    }
+}
+
 pub enum Msg {
     // Auth messages
     UpdateRegisterUsername(String),
@@ -15,12 +31,15 @@ pub enum Msg {
     Login,
     Logout,
     AuthSuccess(AuthResponse),
+    UserLoaded(User),
 
     // Post messages
     UpdatePostTitle(String),
     UpdatePostContent(String),
     LoadPosts,
     PostsLoaded(PostsResponse),
+    NextPage,
+    PrevPage,
     CreatePost,
     PostCreated(Post),
     EditPost(i64),
@@ -30,9 +49,13 @@ pub enum Msg {
     DeletePost(i64),
     PostDeleted(i64),
     CancelEdit,
+    ViewPost(i64),
+    PostLoaded(Post),
+    AuthorLoaded(User),
+    ClosePost,
 
     // UI messages
-    Error(String),
+    Error(ApiError),
 }
 
 #[derive(Clone, PartialEq)]
@@ -62,12 +85,19 @@ pub struct App {
     // Posts
     posts: Vec<Post>,
     posts_total: i64,
+    current_offset: i64,
+    selected_post: Option<Post>,
+    // username по author_id; заполняется при открытии поста
+    authors: HashMap<i64, String>,
 
     // UI state
     loading: bool,
     error: Option<String>,
     edit_state: EditState,
     edit_form_data: Option<(i64, String, String)>,
+    // updated_at поста на момент открытия формы - сервер отклонит сохранение,
+    // если кто-то изменил пост за это время
+    edit_loaded_at: Option<String>,
 
     // API client
     api: ApiClient,
@@ -77,10 +107,27 @@ impl Component for App {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
         // Check for existing token
         let token = ApiClient::get_token();
         let user = None;
+        let api = ApiClient::new();
+
+        // С сохраненным токеном сразу подгружаем пользователя; невалидный токен сбрасываем
+        if token.is_some() {
+            let api = api.clone();
+            let link = ctx.link().clone();
+
+            spawn_local(async move {
+                match api.current_user().await {
+                    Ok(user) => link.send_message(Msg::UserLoaded(user)),
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("Saved token rejected: {}", e).into());
+                        link.send_message(Msg::Error(e));
+                    }
+                }
+            });
+        }
 
         Self {
             user,
@@ -94,11 +141,15 @@ impl Component for App {
             post_content: String::new(),
             posts: Vec::new(),
             posts_total: 0,
+            current_offset: 0,
+            selected_post: None,
+            authors: HashMap::new(),
             loading: false,
             error: None,
             edit_state: EditState::None,
             edit_form_data: None,
-            api: ApiClient::new(),
+            edit_loaded_at: None,
+            api,
         }
     }
 
@@ -197,6 +248,9 @@ impl Component for App {
                 ApiClient::clear_token();
                 self.token = None;
                 self.user = None;
+                self.edit_state = EditState::None;
+                self.edit_form_data = None;
+                self.edit_loaded_at = None;
                 self.login_username.clear();
                 self.login_password.clear();
                 true
@@ -218,6 +272,11 @@ impl Component for App {
                 true
             }
 
+            Msg::UserLoaded(user) => {
+                self.user = Some(user);
+                true
+            }
+
             // Post form updates
             Msg::UpdatePostTitle(val) => {
                 self.post_title = val;
@@ -235,8 +294,10 @@ impl Component for App {
                 let api = self.api.clone();
                 let link = ctx.link().clone();
 
+                let offset = self.current_offset;
+
                 spawn_local(async move {
-                    match api.list_posts(10, 0).await {
+                    match api.list_posts(PAGE_SIZE, offset).await {
                         Ok(response) => link.send_message(Msg::PostsLoaded(response)),
                         Err(e) => link.send_message(Msg::Error(e)),
                     }
@@ -246,12 +307,38 @@ impl Component for App {
             }
 
             Msg::PostsLoaded(response) => {
+                // Страница опустела (например, после удаления) - переходим на последнюю
+                if response.posts.is_empty() && self.current_offset > 0 && response.total > 0 {
+                    self.current_offset = (response.total - 1) / PAGE_SIZE * PAGE_SIZE;
+                    ctx.link().send_message(Msg::LoadPosts);
+                    return false;
+                }
+
                 self.posts = response.posts;
                 self.posts_total = response.total;
                 self.loading = false;
                 true
             }
 
+            // Pagination
+            Msg::NextPage => {
+                if self.current_offset + PAGE_SIZE >= self.posts_total {
+                    return false;
+                }
+                self.current_offset += PAGE_SIZE;
+                ctx.link().send_message(Msg::LoadPosts);
+                false
+            }
+
+            Msg::PrevPage => {
+                if self.current_offset == 0 {
+                    return false;
+                }
+                self.current_offset = (self.current_offset - PAGE_SIZE).max(0);
+                ctx.link().send_message(Msg::LoadPosts);
+                false
+            }
+
             // Create post
             Msg::CreatePost => {
                 if self.post_title.is_empty() || self.post_content.is_empty() {
@@ -294,6 +381,7 @@ impl Component for App {
                 if let Some(post) = self.posts.iter().find(|p| p.id == id) {
                     // Сохраняем данные в локальное состояние
                     self.edit_form_data = Some((id, post.title.clone(), post.content.clone()));
+                    self.edit_loaded_at = Some(post.updated_at.clone());
                     self.edit_state = EditState::Editing {
                         id,
                         title: post.title.clone(),
@@ -320,6 +408,7 @@ impl Component for App {
                     let req = UpdatePostRequest {
                         title: Some(title.clone()),
                         content: Some(content.clone()),
+                        expected_updated_at: self.edit_loaded_at.clone(),
                     };
 
                     let api = self.api.clone();
@@ -337,12 +426,16 @@ impl Component for App {
 
             // Post updated successfully
             Msg::PostUpdated(post) => {
+                if let Some(selected) = self.selected_post.as_mut().filter(|p| p.id == post.id) {
+                    *selected = post.clone();
+                }
                 if let Some(index) = self.posts.iter().position(|p| p.id == post.id) {
                     self.posts[index] = post;
                 }
                 // Закрываем форму после успешного сохранения
                 self.edit_state = EditState::None;
                 self.edit_form_data = None;
+                self.edit_loaded_at = None;
                 self.loading = false;
                 true
             }
@@ -365,6 +458,9 @@ impl Component for App {
             }
 
             Msg::PostDeleted(id) => {
+                if self.selected_post.as_ref().is_some_and(|p| p.id == id) {
+                    self.selected_post = None;
+                }
                 self.posts.retain(|p| p.id != id);
                 self.posts_total -= 1;
                 self.loading = false;
@@ -375,13 +471,81 @@ impl Component for App {
             Msg::CancelEdit => {
                 self.edit_state = EditState::None;
                 self.edit_form_data = None;
+                self.edit_loaded_at = None;
+                true
+            }
+
+            // Post detail - всегда берем свежую версию с сервера
+            Msg::ViewPost(id) => {
+                self.loading = true;
+                self.error = None;
+
+                let api = self.api.clone();
+                let link = ctx.link().clone();
+
+                spawn_local(async move {
+                    match api.get_post(id).await {
+                        Ok(post) => link.send_message(Msg::PostLoaded(post)),
+                        Err(e) => link.send_message(Msg::Error(e)),
+                    }
+                });
+
+                false
+            }
+
+            Msg::PostLoaded(post) => {
+                let author_id = post.author_id;
+                if !self.authors.contains_key(&author_id) {
+                    let api = self.api.clone();
+                    let link = ctx.link().clone();
+
+                    // Без профиля автор остается "user N", поэтому ошибку не показываем
+                    spawn_local(async move {
+                        match api.get_user(author_id).await {
+                            Ok(user) => link.send_message(Msg::AuthorLoaded(user)),
+                            Err(e) => web_sys::console::log_1(
+                                &format!("Author {} not loaded: {}", author_id, e).into(),
+                            ),
+                        }
+                    });
+                }
+
+                self.selected_post = Some(post);
+                self.loading = false;
+                true
+            }
+
+            Msg::AuthorLoaded(user) => {
+                self.authors.insert(user.id, user.username);
+                true
+            }
+
+            Msg::ClosePost => {
+                self.selected_post = None;
                 true
             }
 
             // Error
             Msg::Error(e) => {
-                self.error = Some(e);
                 self.loading = false;
+
+                // 401 при сохраненном токене - сессия истекла. Без токена (неверный
+                // логин, публичные запросы) просто показываем ошибку, чтобы не зациклиться
+                if matches!(e, ApiError::Unauthorized(_)) && self.token.is_some() {
+                    ctx.link().send_message(Msg::Logout);
+                    self.error = Some("Session expired, please log in again".to_string());
+                    return true;
+                }
+
+                self.error = Some(match e {
+                    ApiError::NotFound(_) => {
+                        "This post no longer exists, refresh the list".to_string()
+                    }
+                    ApiError::Network(_) => {
+                        format!("{}. Is the server running?", e)
+                    }
+                    e => e.to_string(),
+                });
                 true
             }
         }
@@ -408,8 +572,11 @@ impl Component for App {
                     { self.view_auth_forms(ctx) }
                 }
 
-                // Posts section
-                { self.view_posts_section(ctx) }
+                // Selected post or posts section
+                { match &self.selected_post {
+                    Some(post) => self.view_post_detail(post, ctx),
+                    None => self.view_posts_section(ctx),
+                } }
             </div>
         }
     }
@@ -566,6 +733,71 @@ impl App {
                 if self.posts.is_empty() && !self.loading {
                     <p>{ "No posts yet. Be the first to create one!" }</p>
                 }
+
+                { self.view_pagination(ctx) }
+            </div>
+        }
+    }
+
+    fn view_pagination(&self, ctx: &Context<Self>) -> Html {
+        if self.posts_total <= PAGE_SIZE {
+            return html! {};
+        }
+
+        let page = self.current_offset / PAGE_SIZE + 1;
+        let pages = (self.posts_total + PAGE_SIZE - 1) / PAGE_SIZE;
+        let has_prev = self.current_offset > 0;
+        let has_next = self.current_offset + PAGE_SIZE < self.posts_total;
+
+        html! {
+            <div class="pagination">
+                <button
+                    disabled={!has_prev || self.loading}
+                    onclick={ctx.link().callback(|_| Msg::PrevPage)}
+                >
+                    { "← Prev" }
+                </button>
+                <span>{ format!("Page {} of {}", page, pages) }</span>
+                <button
+                    disabled={!has_next || self.loading}
+                    onclick={ctx.link().callback(|_| Msg::NextPage)}
+                >
+                    { "Next →" }
+                </button>
+            </div>
+        }
+    }
+
+    // Имя автора, если его профиль уже загружен, иначе только id
+    fn author_name(&self, author_id: i64) -> String {
+        self.authors
+            .get(&author_id)
+            .cloned()
+            .unwrap_or_else(|| format!("user {}", author_id))
+    }
+
+    fn view_post_detail(&self, post: &Post, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="post-detail">
+                <button onclick={ctx.link().callback(|_| Msg::ClosePost)}>
+                    { "← Back to posts" }
+                </button>
+
+                <h2>{ &post.title }</h2>
+                <small>
+                    { format!("By {} at {}", self.author_name(post.author_id), post.created_at) }
+                    if post.edited {
+                        { format!(" (updated {})", post.updated_at) }
+                    }
+                </small>
+
+                if !post.tags.is_empty() {
+                    <p class="post-tags">{ format!("Tags: {}", post.tags.join(", ")) }</p>
+                }
+
+                <div class="post-content">{ view_content(post) }</div>
+
+                <small>{ format!("❤ {}", post.likes_count) }</small>
             </div>
         }
     }
@@ -579,7 +811,8 @@ impl App {
         let post_id = post.id;
         let post_title = post.title.clone();
         let post_content = post.content.clone();
-        let post_author_id = post.author_id;
+        let post_body = view_content(post);
+        let post_author = self.author_name(post.author_id);
         let post_created_at = post.created_at.clone();
 
         match &self.edit_state {
@@ -595,12 +828,14 @@ impl App {
 
                 let delete_callback = { ctx.link().callback(move |_| Msg::DeletePost(post_id)) };
 
+                let view_callback = { ctx.link().callback(move |_| Msg::ViewPost(post_id)) };
+
                 html! {
                     <div class="post" key={post_id}>
-                        <h3>{ &post_title }</h3>
-                        <p>{ &post_content }</p>
+                        <h3 class="post-title" onclick={view_callback}>{ &post_title }</h3>
+                        { post_body }
                         <small>
-                            { format!("By user {} at {}", post_author_id, post_created_at) }
+                            { format!("By {} at {}", post_author, post_created_at) }
                         </small>
 
                         if is_author {
@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+/// Typed error returned by `ApiClient`, so the UI can react to the HTTP status
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ApiError {
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("Server error: {0}")]
+    Server(String),
+
+    // Сервер недоступен или ответ не удалось прочитать
+    #[error("Network error: {0}")]
+    Network(String),
+}
+
+impl ApiError {
+    /// Map a non-2xx HTTP status and the server message to an error
+    pub fn from_status(status: u16, message: String) -> Self {
+        match status {
+            401 => Self::Unauthorized(message),
+            404 => Self::NotFound(message),
+            400 | 409 | 422 => Self::Validation(message),
+            _ => Self::Server(message),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 mod api;
 mod app;
+mod error;
+mod markdown;
 mod models;
 
 use app::App;
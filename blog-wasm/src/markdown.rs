@@ -0,0 +1,51 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// Render post Markdown to HTML that is safe to insert into the page:
+/// raw HTML from the source is shown as text and `javascript:` links are dropped
+pub fn to_html(source: &str) -> String {
+    let options =
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(source, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        other => other,
+    });
+
+    let mut output = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut output, events);
+    output
+}
+
+// Схемы, исполняющие код в браузере, заменяются пустой ссылкой
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let scheme = url.trim_start().to_ascii_lowercase();
+    if scheme.starts_with("javascript:")
+        || scheme.starts_with("vbscript:")
+        || scheme.starts_with("data:")
+    {
+        CowStr::Borrowed("")
+    } else {
+        url
+    }
+}
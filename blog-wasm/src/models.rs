@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 pub struct User {
     pub id: i64,
     pub username: String,
+    // В публичном профиле чужого пользователя email не приходит
+    #[serde(default)]
     pub email: String,
+    #[serde(alias = "createdAt")]
     pub created_at: String,
 }
 
@@ -19,9 +22,29 @@ pub struct Post {
     pub id: i64,
     pub title: String,
     pub content: String,
+    #[serde(alias = "authorId")]
     pub author_id: i64,
+    #[serde(alias = "createdAt")]
     pub created_at: String,
+    #[serde(alias = "updatedAt")]
     pub updated_at: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, alias = "likesCount")]
+    pub likes_count: i64,
+    #[serde(default, alias = "contentFormat")]
+    pub content_format: ContentFormat,
+    #[serde(default)]
+    pub edited: bool,
+}
+
+/// How the post content is rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    #[default]
+    Plain,
+    Markdown,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -35,6 +58,9 @@ pub struct PostsResponse {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ErrorResponse {
     pub error: String,
+    /// Machine-readable error code, e.g. `VALIDATION_ERROR`
+    #[serde(default)]
+    pub code: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,4 +86,7 @@ pub struct CreatePostRequest {
 pub struct UpdatePostRequest {
     pub title: Option<String>,
     pub content: Option<String>,
+    // Точное значение (до микросекунд), в отличие от заголовка If-Unmodified-Since
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_updated_at: Option<String>,
 }